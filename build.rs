@@ -0,0 +1,63 @@
+use std::{
+    env,
+    path::PathBuf,
+    process::Command,
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_FFI").is_some() {
+        ffi_shim();
+    }
+}
+
+/**
+Compile the C shim used to test the `ffi` feature.
+
+The shim uses GCC's decimal types, which are only encoded using BID on x86-64. If the shim can't
+be compiled then the tests that need it are skipped.
+*/
+fn ffi_shim() {
+    println!("cargo:rerun-if-changed=include/decstr.h");
+    println!("cargo:rerun-if-changed=tests/ffi/shim.c");
+
+    if env::var("CARGO_CFG_TARGET_ARCH").as_deref() != Ok("x86_64")
+        || env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("linux")
+    {
+        return;
+    }
+
+    let out =
+        PathBuf::from(env::var_os("OUT_DIR").expect("missing `OUT_DIR`")).join("decstr_ffi_shim.o");
+
+    let compiled = Command::new("gcc")
+        .args([
+            "-c",
+            "-Wall",
+            "-Werror",
+            "-Iinclude",
+            "tests/ffi/shim.c",
+            "-o",
+        ])
+        .arg(&out)
+        .status()
+        .is_ok_and(|status| status.success());
+
+    // GCC's decimal arithmetic is implemented in `libgcc`
+    let libgcc = Command::new("gcc")
+        .arg("-print-libgcc-file-name")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+
+    match (compiled, libgcc) {
+        (true, Some(libgcc)) => {
+            println!("cargo:rustc-link-arg-tests={}", out.display());
+            println!("cargo:rustc-link-arg-tests={}", libgcc.trim());
+            println!("cargo:rustc-cfg=decstr_ffi_shim");
+        }
+        _ => println!("cargo:warning=failed to compile the C shim with gcc; skipping its tests"),
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * C bindings for decstr, enabled by its `ffi` feature.
+ *
+ * Decimals are passed by pointer, using GCC's `_Decimal32`, `_Decimal64`, and `_Decimal128` types.
+ * Functions that can fail return `DECSTR_OK` on success and `DECSTR_ERR` on failure, in which case
+ * their output isn't written. `decstr_*_format` follows the same convention as `snprintf`, and
+ * returns `DECSTR_FORMAT_ERR` if the decimal is null.
+ *
+ * See the documentation of the `decstr::ffi` module for details.
+ */
+
+#ifndef DECSTR_H
+#define DECSTR_H
+
+/* This file is generated by cbindgen. Don't edit it by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define DECSTR_OK 0
+
+#define DECSTR_ERR -1
+
+#define DECSTR_FORMAT_ERR SIZE_MAX
+
+int decstr_d32_parse(const char *str, size_t len, _Decimal32 *out);
+
+size_t decstr_d32_format(const _Decimal32 *value, char *buf, size_t len);
+
+int decstr_d32_from_i64(int64_t value, _Decimal32 *out);
+
+int decstr_d32_to_i64(const _Decimal32 *value, int64_t *out);
+
+int decstr_d32_from_u64(uint64_t value, _Decimal32 *out);
+
+int decstr_d32_to_u64(const _Decimal32 *value, uint64_t *out);
+
+int decstr_d32_from_double(double value, _Decimal32 *out);
+
+int decstr_d32_to_double(const _Decimal32 *value, double *out);
+
+int decstr_d64_parse(const char *str, size_t len, _Decimal64 *out);
+
+size_t decstr_d64_format(const _Decimal64 *value, char *buf, size_t len);
+
+int decstr_d64_from_i64(int64_t value, _Decimal64 *out);
+
+int decstr_d64_to_i64(const _Decimal64 *value, int64_t *out);
+
+int decstr_d64_from_u64(uint64_t value, _Decimal64 *out);
+
+int decstr_d64_to_u64(const _Decimal64 *value, uint64_t *out);
+
+int decstr_d64_from_double(double value, _Decimal64 *out);
+
+int decstr_d64_to_double(const _Decimal64 *value, double *out);
+
+int decstr_d128_parse(const char *str, size_t len, _Decimal128 *out);
+
+size_t decstr_d128_format(const _Decimal128 *value, char *buf, size_t len);
+
+int decstr_d128_from_i64(int64_t value, _Decimal128 *out);
+
+int decstr_d128_to_i64(const _Decimal128 *value, int64_t *out);
+
+int decstr_d128_from_u64(uint64_t value, _Decimal128 *out);
+
+int decstr_d128_to_u64(const _Decimal128 *value, uint64_t *out);
+
+int decstr_d128_from_double(double value, _Decimal128 *out);
+
+int decstr_d128_to_double(const _Decimal128 *value, double *out);
+
+#endif  /* DECSTR_H */
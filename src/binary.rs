@@ -8,12 +8,14 @@ a single byte. These buffers can be persisted or sent over networks to other pro
 This module is organized around _features_ of the encoded decimal.
 */
 
+mod bid;
 mod buf;
 mod combination;
 mod exponent;
 mod significand;
 
 pub use self::{
+    bid::*,
     buf::*,
     combination::*,
     exponent::*,
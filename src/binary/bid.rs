@@ -0,0 +1,310 @@
+/*!
+The binary-integer-decimal (BID) interchange encoding.
+
+IEEE754-2019 defines two encodings for the significand of a decimal: densely-packed-decimal (DPD),
+which is what the rest of this library uses, and binary-integer-decimal (BID), which encodes the
+significand as a single binary integer. BID is used by Intel's decimal library, by GCC's decimal
+types on x86, and by MongoDB's `Decimal128`.
+
+Both encodings share the same sign bit and the same encoding of infinities and NaNs. For finite
+numbers, BID stores the biased exponent directly after the sign bit, followed by the significand.
+If the significand is too large to fit in the bits that follow, its implicit leading bits are `100`,
+and the exponent is shifted down by two bits behind the marker `11`.
+
+Conversions between the two encodings are lossless for canonical values. BID can encode
+significands that are larger than the precision of the decimal, which are non-canonical encodings
+of zero. NaN payloads that are too large are likewise treated as zero.
+
+These functions only support decimals that are 128 bits wide or less.
+*/
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        decode_significand_trailing_declets,
+        encode_combination_finite,
+        encode_combination_infinity,
+        encode_combination_nan,
+        encode_significand_trailing_digits,
+        is_finite,
+        is_nan,
+        is_sign_negative,
+        is_signaling_nan,
+        BinaryBuf,
+        BinaryExponent,
+    },
+    num::Integer,
+};
+
+// The combination bits that mark a large significand with implicit leading bits `100`.
+const LARGE_SIGNIFICAND: u128 = 0b11;
+
+// The combination bits that identify an infinity.
+const INFINITY: u128 = 0b1_1110;
+
+// The combination bits that identify a NaN.
+const NAN: u128 = 0b1_1111;
+
+/**
+Encode a DPD decimal into BID, returning the bits of the result.
+
+Only the lowest `decimal.storage_width_bits()` bits of the result are used.
+*/
+pub fn encode_bid<D: BinaryBuf>(decimal: &D) -> u128 {
+    let storage_width_bits = decimal.storage_width_bits();
+    let trailing_significand_width_bits = decimal.trailing_significand_width_bits();
+
+    debug_assert!(
+        storage_width_bits <= 128,
+        "BID is only supported for decimals up to 128 bits"
+    );
+
+    let sign = (is_sign_negative(decimal) as u128) << (storage_width_bits - 1);
+
+    if !is_finite(decimal) {
+        if is_nan(decimal) {
+            let signaling = (is_signaling_nan(decimal) as u128) << (storage_width_bits - 7);
+
+            // A NaN payload has fewer digits than the precision, so always fits in the trailing
+            // significand bits
+            let payload = u128::try_from_ascii(
+                false,
+                decode_significand_trailing_declets(decimal).flatten(),
+            )
+            .expect("NaN payloads always fit in a 128bit integer");
+
+            return sign | (NAN << (storage_width_bits - 6)) | signaling | payload;
+        }
+
+        return sign | (INFINITY << (storage_width_bits - 6));
+    }
+
+    let (exponent, msd) = decode_combination_finite(decimal);
+
+    let coefficient = u128::try_from_ascii(
+        false,
+        Some(msd.get_ascii())
+            .into_iter()
+            .chain(decode_significand_trailing_declets(decimal).flatten()),
+    )
+    .expect("the coefficient of a decimal up to 128 bits always fits in a 128bit integer");
+
+    let exponent = exponent
+        .bias(decimal)
+        .to_i32()
+        .expect("the exponent of a decimal up to 128 bits always fits in a 32bit integer")
+        as u128;
+
+    // The significand fits in the bits following the exponent
+    if coefficient >> (trailing_significand_width_bits + 3) == 0 {
+        sign | (exponent << (trailing_significand_width_bits + 3)) | coefficient
+    }
+    // The significand has the implicit leading bits `100`, which are dropped
+    else {
+        sign | (LARGE_SIGNIFICAND << (storage_width_bits - 3))
+            | (exponent << (trailing_significand_width_bits + 1))
+            | (coefficient & mask(trailing_significand_width_bits + 1))
+    }
+}
+
+/**
+Decode the bits of a BID decimal into a DPD decimal.
+
+The decimal must be zeroed, and only the lowest `decimal.storage_width_bits()` bits of `bid`
+are used.
+*/
+pub fn decode_bid<D: BinaryBuf>(bid: u128, decimal: &mut D) {
+    let storage_width_bits = decimal.storage_width_bits();
+    let trailing_significand_width_bits = decimal.trailing_significand_width_bits();
+    let exponent_width_bits = decimal.exponent_width_bits();
+    let precision_digits = decimal.precision_digits();
+
+    debug_assert!(
+        storage_width_bits <= 128,
+        "BID is only supported for decimals up to 128 bits"
+    );
+
+    let is_negative = (bid >> (storage_width_bits - 1)) & 1 == 1;
+
+    match (bid >> (storage_width_bits - 6)) & mask(5) {
+        NAN => {
+            let is_signaling = (bid >> (storage_width_bits - 7)) & 1 == 1;
+
+            let payload = bid & mask(trailing_significand_width_bits);
+
+            // Payloads that don't fit in the trailing significand digits are non-canonical
+            // encodings of a zero payload
+            if payload < 10u128.pow(precision_digits as u32 - 1) {
+                let mut digits = itoa::Buffer::new();
+
+                encode_significand_trailing_digits(decimal, [digits.format(payload).as_bytes()]);
+            }
+
+            encode_combination_nan(decimal, is_negative, is_signaling);
+        }
+        INFINITY => encode_combination_infinity(decimal, is_negative),
+        combination => {
+            let (exponent, mut coefficient) = if combination >> 3 == LARGE_SIGNIFICAND {
+                (
+                    (bid >> (trailing_significand_width_bits + 1)) & mask(exponent_width_bits),
+                    (0b100 << (trailing_significand_width_bits + 1))
+                        | (bid & mask(trailing_significand_width_bits + 1)),
+                )
+            } else {
+                (
+                    (bid >> (trailing_significand_width_bits + 3)) & mask(exponent_width_bits),
+                    bid & mask(trailing_significand_width_bits + 3),
+                )
+            };
+
+            // Significands that have more digits than the precision are non-canonical
+            // encodings of zero
+            if coefficient >= 10u128.pow(precision_digits as u32) {
+                coefficient = 0;
+            }
+
+            let exponent = D::Exponent::from_i32(exponent as i32).unbias(decimal);
+
+            let mut digits = itoa::Buffer::new();
+
+            let msd = encode_significand_trailing_digits(
+                decimal,
+                [digits.format(coefficient).as_bytes()],
+            );
+
+            encode_combination_finite(decimal, is_negative, exponent, msd);
+        }
+    }
+}
+
+/**
+Get a mask covering the lowest `bits` bits.
+*/
+fn mask(bits: usize) -> u128 {
+    (1u128 << bits) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+        Rng,
+    };
+
+    use crate::binary::{
+        canonicalize,
+        FixedBinaryBuf,
+    };
+
+    fn decode<const N: usize>(bid: u128) -> FixedBinaryBuf<N, i32> {
+        let mut decimal = FixedBinaryBuf::ZERO;
+        decode_bid(bid, &mut decimal);
+        decimal
+    }
+
+    #[test]
+    fn known_vectors_32() {
+        for (d, bid) in [
+            ("0", 0x3280_0000),
+            ("1", 0x3280_0001),
+            ("-1", 0xb280_0001),
+            ("1.23", 0x3180_007b),
+            ("9999999", 0x6cb8_967f),
+            ("9.999999e96", 0x77f8_967f),
+            ("1e-101", 0x0000_0001),
+            ("inf", 0x7800_0000),
+            ("-inf", 0xf800_0000),
+            ("nan", 0x7c00_0000),
+            ("-snan(123)", 0xfe00_007b),
+        ] {
+            assert_eq!(bid, encode_bid(&parse::<4>(d)), "{}", d);
+            assert_eq!(d, to_string(&decode::<4>(bid)), "{}", d);
+        }
+    }
+
+    #[test]
+    fn known_vectors_64() {
+        for (d, bid) in [
+            ("1", 0x31c0_0000_0000_0001),
+            ("-7.50", 0xb180_0000_0000_02ee),
+            ("9999999999999999", 0x6c73_86f2_6fc0_ffff),
+            ("1e-398", 0x0000_0000_0000_0001),
+            ("-inf", 0xf800_0000_0000_0000),
+            ("nan(1)", 0x7c00_0000_0000_0001),
+        ] {
+            assert_eq!(bid, encode_bid(&parse::<8>(d)), "{}", d);
+            assert_eq!(d, to_string(&decode::<8>(bid)), "{}", d);
+        }
+    }
+
+    #[test]
+    fn known_vectors_128() {
+        for (d, bid) in [
+            ("1", 0x3040_0000_0000_0000_0000_0000_0000_0001),
+            ("-0.1", 0xb03e_0000_0000_0000_0000_0000_0000_0001),
+            ("1.000", 0x303a_0000_0000_0000_0000_0000_0000_03e8),
+            (
+                "9.999999999999999999999999999999999e6144",
+                0x5fff_ed09_bead_87c0_378d_8e63_ffff_ffff,
+            ),
+            ("inf", 0x7800_0000_0000_0000_0000_0000_0000_0000),
+            ("snan", 0x7e00_0000_0000_0000_0000_0000_0000_0000),
+        ] {
+            assert_eq!(bid, encode_bid(&parse::<16>(d)), "{}", d);
+            assert_eq!(d, to_string(&decode::<16>(bid)), "{}", d);
+        }
+    }
+
+    #[test]
+    fn decode_non_canonical() {
+        // A significand of 2^24 - 1 has more digits than the precision
+        assert_eq!("0", to_string(&decode::<4>(0x6cbf_ffff)));
+        assert_eq!("-0.00", to_string(&decode::<4>(0xec7f_ffff)));
+        assert_eq!("0e90", to_string(&decode::<4>(0x77ff_ffff)));
+
+        // A payload of 2^20 - 1 has as many digits as the precision
+        assert_eq!("nan", to_string(&decode::<4>(0x7c0f_ffff)));
+
+        // Infinities ignore their trailing bits
+        assert_eq!("inf", to_string(&decode::<4>(0x7900_0001)));
+    }
+
+    fn roundtrip_case<const N: usize>(bytes: [u8; N]) {
+        let decimal = FixedBinaryBuf::<N, i32>::from(bytes);
+
+        // Skip combinations that aren't finite, infinite, or NaN
+        if !is_finite(&decimal) && !is_nan(&decimal) && !crate::binary::is_infinite(&decimal) {
+            return;
+        }
+
+        let mut expected = decimal;
+        canonicalize(&mut expected);
+
+        let bid = encode_bid(&decimal);
+        let actual = decode::<N>(bid);
+
+        assert_eq!(expected.as_ref(), actual.as_ref(), "{:?}", bytes);
+        assert_eq!(bid, encode_bid(&actual), "{:?}", bytes);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..10_000 {
+            let a = rng.next(u64::MAX).to_le_bytes();
+            let b = rng.next(u64::MAX).to_le_bytes();
+
+            roundtrip_case::<4>([a[0], a[1], a[2], a[3]]);
+            roundtrip_case::<8>(a);
+
+            let mut c = [0; 16];
+            c[..8].copy_from_slice(&a);
+            c[8..].copy_from_slice(&b);
+            roundtrip_case::<16>(c);
+        }
+    }
+}
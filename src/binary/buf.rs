@@ -39,7 +39,7 @@ pub trait BinaryBuf {
     always have enough precision to represent any exponent value that can be encoded in
     this binary buffer.
     */
-    type Exponent: BinaryExponent;
+    type Exponent: BinaryExponent + BinaryExponentMath;
 
     /**
     Try convert a pre-validated stream of ASCII digits into a binary exponent.
@@ -119,6 +119,16 @@ pub trait BinaryBuf {
         self.bytes().len() * 8
     }
 
+    /**
+    The largest bit-width this buffer could have.
+
+    Buffers that can grow to fit larger numbers will return their maximum width. The result
+    of arithmetic is rounded to fit this width.
+    */
+    fn max_storage_width_bits(&self) -> usize {
+        self.storage_width_bits()
+    }
+
     /**
     The number of significant digits this buffer can fit.
     */
@@ -174,6 +184,8 @@ pub trait BinaryBuf {
     are encoded together in the combination field. This value determines how many bits of the exponent
     should be written directly.
     */
+    // Not used by the encoders, which split the exponent themselves, but documents the layout
+    #[allow(dead_code)]
     fn trailing_exponent_width_bits(&self) -> usize {
         self.combination_width_bits() - 5
     }
@@ -208,6 +220,14 @@ pub(crate) fn try_with_at_least_precision<D: BinaryBuf, N: BinaryExponentMath>(
         "decimals always have at least 1 integer digit"
     );
 
+    // Precisions this large would overflow the calculation of their storage width.
+    if integer_digits > usize::MAX / 64 {
+        return Err(OverflowError::precision_out_of_range(
+            usize::MAX / 8,
+            "the precision would overflow",
+        ));
+    }
+
     // First, calculate the minimum storage width needed to fit the digits.
     let minimum_digit_precision_width_bytes =
         minimum_storage_width_bits_for_precision_digits(integer_digits) / 8;
@@ -1,6 +1,12 @@
 use core::{
     fmt,
-    ops::Index,
+    ops::{
+        Add,
+        Div,
+        Index,
+        Mul,
+        Sub,
+    },
 };
 
 use crate::{
@@ -32,6 +38,7 @@ A buffer that can represent any sized decimal.
 #[derive(Debug, Clone)]
 pub(crate) struct ArbitrarySizedBinaryBuf(Vec<u8>);
 
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct ArbitrarySizedBinaryExponent(BigInt);
 
 pub(crate) struct ArbitrarySizedBinaryExponentBytes(Vec<u8>);
@@ -149,37 +156,89 @@ impl Integer for BigInt {
 }
 
 impl BinaryExponent for ArbitrarySizedBinaryExponent {
-    #[must_use]
     fn raise(&self, integer_digits: usize) -> Self {
         ArbitrarySizedBinaryExponent(&self.0 + integer_digits)
     }
 
-    #[must_use]
     fn lower(&self, fractional_digits: usize) -> Self {
         ArbitrarySizedBinaryExponent(&self.0 - fractional_digits)
     }
 
-    #[must_use]
     fn bias<D: BinaryBuf>(&self, decimal: &D) -> Self {
         ArbitrarySizedBinaryExponent(add_bias(decimal, self.0.clone()))
     }
 
-    #[must_use]
     fn unbias<D: BinaryBuf>(&self, decimal: &D) -> Self {
         ArbitrarySizedBinaryExponent(sub_bias(decimal, self.0.clone()))
     }
 
-    #[must_use]
     fn emax<D: BinaryBuf>(decimal: &D) -> Self {
         ArbitrarySizedBinaryExponent(emax(decimal.storage_width_bits()))
     }
 
-    #[must_use]
     fn emin<D: BinaryBuf>(decimal: &D) -> Self {
         ArbitrarySizedBinaryExponent(emin(decimal.storage_width_bits()))
     }
 }
 
+impl BinaryExponentMath for ArbitrarySizedBinaryExponent {
+    fn abs(self) -> Self {
+        ArbitrarySizedBinaryExponent(self.0.abs())
+    }
+
+    fn pow2(e: u32) -> Self {
+        ArbitrarySizedBinaryExponent(BigInt::pow2(e))
+    }
+
+    fn log2(self) -> usize {
+        self.0.log2()
+    }
+}
+
+impl Add for ArbitrarySizedBinaryExponent {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ArbitrarySizedBinaryExponent(self.0 + rhs.0)
+    }
+}
+
+impl Sub for ArbitrarySizedBinaryExponent {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        ArbitrarySizedBinaryExponent(self.0 - rhs.0)
+    }
+}
+
+impl Mul for ArbitrarySizedBinaryExponent {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ArbitrarySizedBinaryExponent(self.0 * rhs.0)
+    }
+}
+
+impl Div for ArbitrarySizedBinaryExponent {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        ArbitrarySizedBinaryExponent(self.0 / rhs.0)
+    }
+}
+
+impl From<BigInt> for ArbitrarySizedBinaryExponent {
+    fn from(exponent: BigInt) -> Self {
+        ArbitrarySizedBinaryExponent(exponent)
+    }
+}
+
+impl From<ArbitrarySizedBinaryExponent> for BigInt {
+    fn from(exponent: ArbitrarySizedBinaryExponent) -> Self {
+        exponent.0
+    }
+}
+
 impl fmt::Display for ArbitrarySizedBinaryExponent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.0, f)
@@ -239,8 +298,6 @@ impl BinaryExponentMath for BigInt {
 
         let significant_bit_index = 8 - i_base2[significant_byte_index].leading_zeros() as usize;
 
-        let log2 = (significant_bit_index + (significant_byte_index * 8)).saturating_sub(1);
-
-        log2
+        (significant_bit_index + (significant_byte_index * 8)).saturating_sub(1)
     }
 }
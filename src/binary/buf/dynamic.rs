@@ -1,11 +1,20 @@
 use core::{
     fmt,
-    ops::Index,
+    ops::{
+        Add,
+        Div,
+        Index,
+        Mul,
+        Sub,
+    },
 };
 
 use crate::{
     binary::{
-        exponent::BinaryExponent,
+        exponent::{
+            BinaryExponent,
+            BinaryExponentMath,
+        },
         try_with_at_least_precision,
         BinaryBuf,
     },
@@ -30,6 +39,7 @@ impl<const N: usize> DynamicBinaryBuf<N> {
     };
 }
 
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct DynamicBinaryExponent(i32);
 
 pub(crate) struct DynamicBinaryExponentBytes([u8; 4]);
@@ -69,6 +79,10 @@ impl<const N: usize> BinaryBuf for DynamicBinaryBuf<N> {
         try_with_at_least_precision(integer_digits, integer_exponent.map(|e| e.0))
     }
 
+    fn max_storage_width_bits(&self) -> usize {
+        N * 8
+    }
+
     fn bytes_mut(&mut self) -> &mut [u8] {
         &mut self.buf[..self.len as usize]
     }
@@ -114,38 +128,84 @@ impl Integer for DynamicBinaryExponent {
     }
 }
 
+impl From<DynamicBinaryExponent> for i32 {
+    fn from(exponent: DynamicBinaryExponent) -> Self {
+        exponent.0
+    }
+}
+
 impl BinaryExponent for DynamicBinaryExponent {
-    #[must_use]
     fn raise(&self, integer_digits: usize) -> Self {
         DynamicBinaryExponent(self.0.raise(integer_digits))
     }
 
-    #[must_use]
     fn lower(&self, fractional_digits: usize) -> Self {
         DynamicBinaryExponent(self.0.lower(fractional_digits))
     }
 
-    #[must_use]
     fn bias<D: BinaryBuf>(&self, decimal: &D) -> Self {
         DynamicBinaryExponent(self.0.bias(decimal))
     }
 
-    #[must_use]
     fn unbias<D: BinaryBuf>(&self, decimal: &D) -> Self {
         DynamicBinaryExponent(self.0.unbias(decimal))
     }
 
-    #[must_use]
     fn emax<D: BinaryBuf>(decimal: &D) -> Self {
         DynamicBinaryExponent(i32::emax(decimal))
     }
 
-    #[must_use]
     fn emin<D: BinaryBuf>(decimal: &D) -> Self {
         DynamicBinaryExponent(i32::emin(decimal))
     }
 }
 
+impl BinaryExponentMath for DynamicBinaryExponent {
+    fn abs(self) -> Self {
+        DynamicBinaryExponent(self.0.saturating_abs())
+    }
+
+    fn pow2(e: u32) -> Self {
+        DynamicBinaryExponent(i32::pow2(e))
+    }
+
+    fn log2(self) -> usize {
+        self.0.log2()
+    }
+}
+
+impl Add for DynamicBinaryExponent {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        DynamicBinaryExponent(self.0 + rhs.0)
+    }
+}
+
+impl Sub for DynamicBinaryExponent {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        DynamicBinaryExponent(self.0 - rhs.0)
+    }
+}
+
+impl Mul for DynamicBinaryExponent {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        DynamicBinaryExponent(self.0 * rhs.0)
+    }
+}
+
+impl Div for DynamicBinaryExponent {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        DynamicBinaryExponent(self.0 / rhs.0)
+    }
+}
+
 impl fmt::Display for DynamicBinaryExponent {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self.0, f)
@@ -15,13 +15,18 @@ For more details, see the respective encoding functions.
 
 use crate::{
     binary::{
+        canonicalize_significand_trailing,
+        is_significand_trailing_canonical,
         BinaryBuf,
         BinaryExponent,
         MostSignificantDigit,
     },
     num::Integer,
 };
-use core::iter;
+use core::{
+    iter,
+    ops::Range,
+};
 
 // The sign is encoded into the most significant bit of the decimal number
 // For negative numbers, the bit is set to `1`
@@ -329,7 +334,7 @@ pub fn decode_combination_finite<D: BinaryBuf>(decimal: &D) -> (D::Exponent, Mos
     // If the bytes of the exponent are already aligned with the bytes of the decimal then
     // they can be read directly from the decimal buffer.
     let biased_exponent = if decimal_byte_shift == 0 {
-        D::Exponent::from_le_bytes(iter::from_fn(|| {
+        D::Exponent::from_le_bytes(zero_extend(iter::from_fn(|| {
             // If there's more bytes in the decimal then read the next one as the next byte
             // of the exponent
             if decimal_byte_index < max_decimal_byte_index {
@@ -357,7 +362,7 @@ pub fn decode_combination_finite<D: BinaryBuf>(decimal: &D) -> (D::Exponent, Mos
             else {
                 None
             }
-        }))
+        })))
     }
     // If the bytes of the exponent are not already aligned with the bytes of the decimal
     // then they'll need to be offset.
@@ -367,7 +372,7 @@ pub fn decode_combination_finite<D: BinaryBuf>(decimal: &D) -> (D::Exponent, Mos
     else {
         let decimal_byte_plus_1_shift = 8 - decimal_byte_shift;
 
-        D::Exponent::from_le_bytes(iter::from_fn(|| {
+        D::Exponent::from_le_bytes(zero_extend(iter::from_fn(|| {
             // If there are more than 2 bytes left then squash them into the next byte of the exponent.
             if decimal_byte_index + 1 < max_decimal_byte_index {
                 // Read the first part of the next byte in the exponent
@@ -420,7 +425,7 @@ pub fn decode_combination_finite<D: BinaryBuf>(decimal: &D) -> (D::Exponent, Mos
             else {
                 None
             }
-        }))
+        })))
     };
 
     debug_assert!(
@@ -470,6 +475,16 @@ fn most_significant_exponent_offset(exponent_bits: usize) -> (u32, usize) {
     }
 }
 
+/**
+Append a zero byte to the bytes of a biased exponent.
+
+Biased exponents are never negative, but if the most significant bit of the exponent is set then
+an exponent type that's exactly as wide as it would read it as a sign bit.
+*/
+fn zero_extend(bytes: impl Iterator<Item = u8>) -> impl Iterator<Item = u8> {
+    bytes.chain(iter::once(0))
+}
+
 /**
 Whether or not the decimal is finite.
 */
@@ -524,12 +539,91 @@ pub fn is_sign_negative<D: BinaryBuf>(decimal: &D) -> bool {
     buf[buf.len() - 1] & SIGN_NEGATIVE == SIGN_NEGATIVE
 }
 
+/**
+Whether or not the decimal uses the canonical encoding of its value.
+
+Finite numbers are canonical if all of their declets are. Infinities are canonical if all of the
+bits after their combination field are `0`. NaNs are canonical if the exponent continuation bits
+of their combination field are `0` and all of the declets in their payload are canonical.
+*/
+pub fn is_canonical<D: BinaryBuf>(decimal: &D) -> bool {
+    let storage_width_bits = decimal.storage_width_bits();
+
+    if is_infinite(decimal) {
+        // The sign bit and `11110` are the only bits set in a canonical infinity
+        bits_are_zero(decimal.bytes(), 0..storage_width_bits - 6)
+    } else if is_nan(decimal) {
+        // The sign bit, `11111`, and the signaling bit come before the exponent continuation
+        bits_are_zero(
+            decimal.bytes(),
+            decimal.trailing_significand_width_bits()..storage_width_bits - 7,
+        ) && is_significand_trailing_canonical(decimal)
+    } else {
+        is_significand_trailing_canonical(decimal)
+    }
+}
+
+/**
+Replace the encoding of a decimal with the canonical encoding of its value.
+
+This clears any bits that are ignored while decoding, and re-encodes any non-canonical declets.
+DPD can't encode a coefficient with more digits than the precision, so every decimal has a
+canonical encoding of the same value.
+*/
+pub fn canonicalize<D: BinaryBuf>(decimal: &mut D) {
+    let storage_width_bits = decimal.storage_width_bits();
+
+    if is_infinite(decimal) {
+        clear_bits(decimal.bytes_mut(), 0..storage_width_bits - 6);
+    } else {
+        if is_nan(decimal) {
+            let trailing_significand_width_bits = decimal.trailing_significand_width_bits();
+
+            clear_bits(
+                decimal.bytes_mut(),
+                trailing_significand_width_bits..storage_width_bits - 7,
+            );
+        }
+
+        canonicalize_significand_trailing(decimal);
+    }
+}
+
+/**
+Set all of the given bits to `0`.
+*/
+fn clear_bits(buf: &mut [u8], bits: Range<usize>) {
+    for bit in bits {
+        buf[bit / 8] &= !(1 << (bit % 8));
+    }
+}
+
+/**
+Whether or not all of the given bits are `0`.
+*/
+fn bits_are_zero(buf: &[u8], mut bits: Range<usize>) -> bool {
+    bits.all(|bit| buf[bit / 8] & (1 << (bit % 8)) == 0)
+}
+
+/**
+Flip the sign bit.
+
+This works for any decimal, including infinities and NaNs.
+*/
+pub fn negate<D: BinaryBuf>(decimal: &mut D) {
+    let buf = decimal.bytes_mut();
+
+    buf[buf.len() - 1] ^= SIGN_NEGATIVE;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::binary::{
+        decode_significand_trailing_declets,
         emax,
         DynamicBinaryBuf,
+        FixedBinaryBuf,
     };
 
     fn encode_decode_case<D: BinaryBuf + Clone>(decimal: D) {
@@ -593,4 +687,74 @@ mod tests {
     fn encode_decode_combination_decimal160_all() {
         encode_decode_case(DynamicBinaryBuf::<20>::ZERO);
     }
+
+    #[test]
+    fn canonical() {
+        let parse = |s: &str| -> FixedBinaryBuf<4, i32> {
+            crate::convert::decimal_from_str(s).expect("failed to parse decimal")
+        };
+
+        for d in ["0", "-999", "9.999999e96", "inf", "-nan(999)", "snan"] {
+            assert!(is_canonical(&parse(d)), "{}", d);
+        }
+
+        // The declet `999` ignores 2 of its bits
+        let mut d = parse("999");
+        d.bytes_mut()[1] |= 0b0000_0011;
+        assert!(!is_canonical(&d));
+
+        // Infinities ignore all bits after their combination field
+        let mut d = parse("inf");
+        d.bytes_mut()[0] = 1;
+        assert!(!is_canonical(&d));
+
+        // NaNs ignore their exponent continuation bits
+        let mut d = parse("nan(1)");
+        d.bytes_mut()[3] |= 0b0000_0001;
+        assert!(!is_canonical(&d));
+    }
+
+    #[test]
+    fn canonicalize_all_declets() {
+        // Every possible 10 bit declet decodes to the same digits after canonicalizing
+        for dpd in 0..1024u16 {
+            let mut d = FixedBinaryBuf::<4, i32>::from_le_bytes([0, 0, 0, 0b0010_0010]);
+            d.bytes_mut()[0] = dpd as u8;
+            d.bytes_mut()[1] = (dpd >> 8) as u8;
+
+            let digits = decode_significand_trailing_declets(&d).collect::<Vec<_>>();
+
+            let mut canonical = d;
+            canonicalize(&mut canonical);
+
+            assert!(is_canonical(&canonical), "{:010b}", dpd);
+            assert_eq!(
+                digits,
+                decode_significand_trailing_declets(&canonical).collect::<Vec<_>>(),
+                "{:010b}",
+                dpd
+            );
+            assert_eq!(
+                is_canonical(&d),
+                d.bytes() == canonical.bytes(),
+                "{:010b}",
+                dpd
+            );
+        }
+    }
+
+    #[test]
+    fn canonicalize_specials() {
+        let mut d = FixedBinaryBuf::<4, i32>::from_le_bytes([0xff, 0xff, 0xff, 0xf9]);
+        assert!(is_infinite(&d));
+        canonicalize(&mut d);
+        assert_eq!([0, 0, 0, 0xf8], d.bytes());
+
+        // NaNs keep their sign, signaling bit, and payload
+        let mut d = FixedBinaryBuf::<4, i32>::from_le_bytes([0xff, 0x03, 0xff, 0xff]);
+        assert!(is_signaling_nan(&d));
+        canonicalize(&mut d);
+        assert!(is_canonical(&d));
+        assert_eq!([0xff, 0x00, 0x0f, 0xfe], d.bytes());
+    }
 }
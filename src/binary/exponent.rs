@@ -20,11 +20,13 @@ pub trait BinaryExponent: Integer {
     /**
     Account for digits on the integral side of the decimal point by raising the exponent.
     */
+    #[must_use]
     fn raise(&self, by: usize) -> Self;
 
     /**
     Account for digits on the fractional side of the decimal point by lowering the exponent.
     */
+    #[must_use]
     fn lower(&self, by: usize) -> Self;
 
     /**
@@ -32,21 +34,25 @@ pub trait BinaryExponent: Integer {
 
     After this operation, the exponent _must_ be non-negative.
     */
+    #[must_use]
     fn bias<D: BinaryBuf>(&self, decimal: &D) -> Self;
 
     /**
     Subtract the bias from the exponent.
     */
+    #[must_use]
     fn unbias<D: BinaryBuf>(&self, decimal: &D) -> Self;
 
     /**
     Get a value representing the maximum exponent that can be encoded into the given decimal.
     */
+    #[must_use]
     fn emax<D: BinaryBuf>(decimal: &D) -> Self;
 
     /**
     Get a value representing the minimum exponent that can be encoded into the given decimal.
     */
+    #[must_use]
     fn emin<D: BinaryBuf>(decimal: &D) -> Self;
 }
 
@@ -65,10 +71,13 @@ pub(crate) trait BinaryExponentMath:
     + Div<Output = Self>
     + Debug
     + Clone
+    + Ord
     + Sized
 {
     /**
     Calculate the absolute value of this integer.
+
+    Fixed-size integers saturate, so the absolute value of their minimum is their maximum.
     */
     fn abs(self) -> Self;
 
@@ -87,32 +96,26 @@ macro_rules! impl_binary_exponent {
     ($(($i:ty, $bytes:ty)),*) => {
         $(
             impl BinaryExponent for $i {
-                #[must_use]
                 fn raise(&self, by: usize) -> Self {
                     *self + (by as $i)
                 }
 
-                #[must_use]
                 fn lower(&self, by: usize) -> Self {
                     *self - (by as $i)
                 }
 
-                #[must_use]
                 fn bias<D: BinaryBuf>(&self, decimal: &D) -> Self {
                     add_bias(decimal, *self)
                 }
 
-                #[must_use]
                 fn unbias<D: BinaryBuf>(&self, decimal: &D) -> Self {
                     sub_bias(decimal, *self)
                 }
 
-                #[must_use]
                 fn emax<D: BinaryBuf>(decimal: &D) -> Self {
                     emax(decimal.storage_width_bits())
                 }
 
-                #[must_use]
                 fn emin<D: BinaryBuf>(decimal: &D) -> Self {
                     emin(decimal.storage_width_bits())
                 }
@@ -120,7 +123,7 @@ macro_rules! impl_binary_exponent {
 
             impl BinaryExponentMath for $i {
                 fn abs(self) -> Self {
-                    <$i>::abs(self)
+                    <$i>::saturating_abs(self)
                 }
 
                 fn pow2(e: u32) -> Self {
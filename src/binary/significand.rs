@@ -5,12 +5,12 @@ This module converts between text representations of integers and their encoding
 IEEE754-2019 decimal interchange format. The encodings used along the way are:
 
 - **ASCII digits**: like `b"555"`. These are the ASCII-coded digits that make up the number.
-As bytes, they might look like `[0b0000_0101, 0b0000_0101, 0b0000_0101]`. These typically come
-from the parser in the `text` module.
+  As bytes, they might look like `[0b0000_0101, 0b0000_0101, 0b0000_0101]`. These typically come
+  from the parser in the `text` module.
 - **Binary coded digits**: like `0b0000_0101_0101_0101`. These are the lower 4 bits of the ASCII
-digits that occupy 12 bits of a single `u16`. These are produced by `encode_ascii_digits_to_bcd`.
+  digits that occupy 12 bits of a single `u16`. These are produced by `encode_ascii_digits_to_bcd`.
 - **Densely packed decimal**: like `0b0000_0010_1101_0101`. These are the 12 binary coded bits
-further squashed into 10 bits. These are produced by `encode_bcd_to_dpd`.
+  further squashed into 10 bits. These are produced by `encode_bcd_to_dpd`.
 
 For more details on these formats, see their respective encoding functions.
 */
@@ -127,6 +127,71 @@ pub fn decode_significand_trailing_declets<D: BinaryBuf>(
     })
 }
 
+/**
+Whether or not every declet in the trailing significand uses its canonical encoding.
+
+DPD has 24 declets that can be encoded in more than one way, because some of their bits are
+ignored while decoding. Only the encoding with those bits set to `0` is canonical.
+*/
+pub fn is_significand_trailing_canonical<D: BinaryBuf>(decimal: &D) -> bool {
+    let mut bit_index = decimal.trailing_significand_width_bits();
+
+    let decimal = decimal.bytes();
+
+    while bit_index > 0 {
+        let bcd = decode_dpd_declet_to_bcd(decimal, &mut bit_index);
+
+        // Re-encoding the digits always produces the canonical declet
+        let mut canonical = [0; 2];
+        encode_bcd_declet_to_dpd(bcd, &mut canonical, &mut 0);
+
+        if read_dpd_declet(decimal, bit_index) != read_dpd_declet(&canonical, 0) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/**
+Replace every declet in the trailing significand with its canonical encoding.
+
+The digits encoded by the significand don't change.
+*/
+pub fn canonicalize_significand_trailing<D: BinaryBuf>(decimal: &mut D) {
+    let mut bit_index = decimal.trailing_significand_width_bits();
+
+    let decimal = decimal.bytes_mut();
+
+    while bit_index > 0 {
+        let bcd = decode_dpd_declet_to_bcd(decimal, &mut bit_index);
+
+        // Clear the declet before re-encoding it, because encoding only sets bits
+        let decimal_byte_shift = (bit_index % 8) as u32;
+        let decimal_byte_index = bit_index / 8;
+
+        let mask = 0b0000_0011_1111_1111u16 << decimal_byte_shift;
+
+        decimal[decimal_byte_index] &= !(mask as u8);
+        decimal[decimal_byte_index + 1] &= !((mask >> 8) as u8);
+
+        encode_bcd_declet_to_dpd(bcd, decimal, &mut bit_index.clone());
+    }
+}
+
+/**
+Read the raw 10 bits of a declet, without decoding them.
+*/
+fn read_dpd_declet(decimal: &[u8], decimal_bit_index: usize) -> u16 {
+    let decimal_byte_shift = (decimal_bit_index % 8) as u32;
+    let decimal_byte_index = decimal_bit_index / 8;
+
+    let dpd0 = (decimal[decimal_byte_index] as u16) >> decimal_byte_shift;
+    let dpd1 = (decimal[decimal_byte_index + 1] as u16) << (8 - decimal_byte_shift);
+
+    (dpd0 | dpd1) & 0b0000_0011_1111_1111
+}
+
 /**
 Get the next 3 digits from the back of the buffer to encode.
 
@@ -46,6 +46,1344 @@ macro_rules! classify {
             pub fn is_signaling_nan(&self) -> bool {
                 $crate::binary::is_signaling_nan(&self.0)
             }
+
+            /**
+            Whether the decimal is a positive or negative zero, with any exponent.
+            */
+            pub fn is_zero(&self) -> bool {
+                $crate::math::decimal_is_zero(&self.0)
+            }
+
+            /**
+            Whether the decimal is a finite number without any non-zero fractional digits.
+
+            The exponent doesn't need to be zero, so `1.20e2` and `100e-2` are both integers.
+            */
+            pub fn is_integer(&self) -> bool {
+                $crate::math::decimal_is_integer(&self.0)
+            }
+
+            /**
+            Whether the decimal is non-zero and has an adjusted exponent below the normal range
+            of its format.
+            */
+            pub fn is_subnormal(&self) -> bool {
+                $crate::math::decimal_is_subnormal(&self.0)
+            }
+
+            /**
+            Whether the decimal is non-zero, finite, and not subnormal.
+            */
+            pub fn is_normal(&self) -> bool {
+                matches!(
+                    self.classify(),
+                    $crate::DecimalCategory::NegativeNormal
+                        | $crate::DecimalCategory::PositiveNormal
+                )
+            }
+
+            /**
+            Whether the decimal uses the canonical encoding of its value.
+
+            Decimals produced by this library are always canonical, but decimals read from
+            bytes may not be.
+            */
+            pub fn is_canonical(&self) -> bool {
+                $crate::binary::is_canonical(&self.0)
+            }
+
+            /**
+            Get the canonical encoding of this decimal's value.
+
+            DPD can encode some digits in more than one way, and some bits of infinities and NaNs
+            are ignored. Canonicalizing a decimal re-encodes it so those redundant bits are all `0`.
+            The value of the decimal, including its exponent and any NaN payload, doesn't change.
+            */
+            pub fn canonicalize(&self) -> Self {
+                let mut buf = self.0.clone();
+                $crate::binary::canonicalize(&mut buf);
+
+                $d(buf)
+            }
+
+            /**
+            Get the class of the decimal.
+            */
+            pub fn classify(&self) -> $crate::DecimalCategory {
+                $crate::math::decimal_classify(&self.0)
+            }
+
+            /**
+            Convert a sNaN into a qNaN, keeping its sign and payload.
+
+            Decimals that aren't sNaNs are returned unchanged.
+            */
+            pub fn quieten(&self) -> Self {
+                $d($crate::convert::decimal_quieten(&self.0))
+            }
+        }
+    };
+}
+
+macro_rules! cmp {
+    ($a:ident => $b:ident) => {
+        impl PartialEq<$crate::$b> for $a {
+            fn eq(&self, other: &$crate::$b) -> bool {
+                $crate::math::decimal_eq(&self.0, &other.0)
+            }
+        }
+
+        impl PartialOrd<$crate::$b> for $a {
+            fn partial_cmp(&self, other: &$crate::$b) -> Option<core::cmp::Ordering> {
+                $crate::math::decimal_partial_cmp(&self.0, &other.0)
+            }
+        }
+    };
+}
+
+macro_rules! total_cmp {
+    ($d:ident) => {
+        impl $d {
+            /**
+            Return the ordering between `self` and `other` using the IEEE 754 `totalOrder` predicate.
+
+            Unlike the `PartialOrd` implementation, this method orders every decimal, including NaNs,
+            signed zeros, and different representations of the same number. The order is:
+
+            - negative quiet NaN
+            - negative signaling NaN
+            - negative infinity
+            - negative numbers
+            - negative zero
+            - positive zero
+            - positive numbers
+            - positive infinity
+            - positive signaling NaN
+            - positive quiet NaN
+
+            Representations of the same number are ordered by their exponent, so `1.0` is ordered
+            before `1`, but `-1` is ordered before `-1.0`. NaNs with the same sign and kind are
+            ordered by their payload.
+            */
+            pub fn total_cmp(&self, other: &Self) -> core::cmp::Ordering {
+                $crate::math::decimal_total_cmp(&self.0, &other.0)
+            }
+        }
+    };
+}
+
+macro_rules! hash {
+    ($d:ident) => {
+        impl core::hash::Hash for $d {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::math::decimal_hash(&self.0, state)
+            }
+        }
+
+        impl $d {
+            /**
+            Feed the encoded bytes of this decimal into the given [`Hasher`](core::hash::Hasher).
+
+            The `Hash` implementation is consistent with numeric equality, so `1.0` and `1.00`
+            produce the same hash. This method hashes the representation instead, so `1.0` and `1.00`
+            will usually produce different hashes. It's consistent with comparing the results of
+            `as_le_bytes`.
+            */
+            pub fn hash_repr<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::math::decimal_hash_repr(&self.0, state)
+            }
+        }
+
+        impl PartialEq for $crate::NumericEq<$d> {
+            fn eq(&self, other: &Self) -> bool {
+                (self.0.is_nan() && other.0.is_nan())
+                    || $crate::math::decimal_eq(&(self.0).0, &(other.0).0)
+            }
+        }
+
+        impl Eq for $crate::NumericEq<$d> {}
+
+        impl core::hash::Hash for $crate::NumericEq<$d> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::math::decimal_hash(&(self.0).0, state)
+            }
+        }
+
+        impl PartialEq for $crate::ReprEq<$d> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0.as_le_bytes() == other.0.as_le_bytes()
+            }
+        }
+
+        impl Eq for $crate::ReprEq<$d> {}
+
+        impl core::hash::Hash for $crate::ReprEq<$d> {
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                $crate::math::decimal_hash_repr(&(self.0).0, state)
+            }
+        }
+    };
+}
+
+macro_rules! add {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Add `other` to this decimal.
+
+            The result is calculated exactly and then rounded to the precision of the decimal,
+            with ties rounding to even. Results that are too large to encode overflow to infinity.
+            If either operand is a NaN then the result is that NaN, quietened. Adding infinities
+            with opposite signs returns a NaN.
+            */
+            pub fn add(&self, other: &Self) -> Self {
+                self.add_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Add `other` to this decimal, using the given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn add_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &other.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_add::<_, $c>(
+                        &self.0, &other.0, limits, mode, flags,
+                    ))
+                })
+            }
+
+            /**
+            Subtract `other` from this decimal.
+
+            The result is calculated exactly and then rounded to the precision of the decimal,
+            with ties rounding to even. Results that are too large to encode overflow to infinity.
+            If either operand is a NaN then the result is that NaN, quietened. Subtracting infinities
+            with the same sign returns a NaN.
+            */
+            pub fn sub(&self, other: &Self) -> Self {
+                self.sub_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Subtract `other` from this decimal, using the given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn sub_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &other.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_sub::<_, $c>(
+                        &self.0, &other.0, limits, mode, flags,
+                    ))
+                })
+            }
+        }
+    };
+}
+
+macro_rules! mul {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Multiply this decimal by `other`.
+
+            The result is calculated exactly and then rounded to the precision of the decimal,
+            with ties rounding to even. Results that are too large to encode overflow to infinity.
+            If either operand is a NaN then the result is that NaN, quietened. Multiplying an infinity
+            by zero returns a NaN.
+            */
+            pub fn mul(&self, other: &Self) -> Self {
+                self.mul_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Multiply this decimal by `other`, using the given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn mul_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &other.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_mul::<_, $c>(
+                        &self.0, &other.0, limits, mode, flags,
+                    ))
+                })
+            }
+
+            /**
+            Multiply this decimal by `a` and add `b`, with a single rounding step.
+
+            This is a fused multiply-add, `self * a + b`. The product isn't rounded before being added,
+            so the result may be different to calling `mul` and then `add`. The result is rounded to
+            the precision of the decimal, with ties rounding to even.
+            */
+            pub fn fma(&self, a: &Self, b: &Self) -> Self {
+                self.fma_with(a, b, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Multiply this decimal by `a` and add `b`, with a single rounding step, using the
+            given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn fma_with(
+                &self,
+                a: &Self,
+                b: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &a.0, &b.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_fma::<_, $c>(
+                        &self.0, &a.0, &b.0, limits, mode, flags,
+                    ))
+                })
+            }
+        }
+    };
+}
+
+macro_rules! div {
+    ($c:ty => $d:ident, width_precision) => {
+        div!(
+            $c => $d,
+            /**
+            The precision of the wider operand comes from its width, not from a fixed minimum, so it
+            can be much less than you might expect from an arbitrary-precision decimal. Small numbers
+            are encoded in 32 bits, so dividing them only gives 7 digits. The same applies to
+            `div_integer`, `rem`, and `remainder`. Use `div_with` with a [`Context`](crate::Context)
+            to set the precision:
+
+            ```
+            use decstr::{BigBitstring, Context};
+
+            let one = BigBitstring::from(1u8);
+            let three = BigBitstring::from(3u8);
+
+            assert_eq!("0.3333333", one.div(&three).to_string());
+
+            let mut ctx = Context::new().with_precision(50);
+
+            assert_eq!(
+                "0.33333333333333333333333333333333333333333333333333",
+                one.div_with(&three, &mut ctx)?.to_string()
+            );
+            # Ok::<(), decstr::Error>(())
+            ```
+            */
+        );
+    };
+    ($c:ty => $d:ident $(, #[$div_doc:meta])*) => {
+        impl $d {
+            /**
+            Divide this decimal by `other`.
+
+            The result is calculated to the precision of the wider operand, with ties rounding to even.
+            Exact results use the exponent closest to the difference between the exponents of the
+            operands, so `8.00 / 2` is `4.00`. Dividing a non-zero number by zero returns an infinity
+            with the sign of the result. Dividing zero by zero, or an infinity by an infinity, returns a NaN.
+            */
+            $(#[$div_doc])*
+            pub fn div(&self, other: &Self) -> Self {
+                self.div_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Divide this decimal by `other`, using the given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn div_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                self.divide(other, $crate::math::Division::Quotient, ctx)
+            }
+
+            /**
+            Divide this decimal by `other`, truncating the quotient to an integer.
+
+            The result always has an exponent of zero. If the integer quotient has more digits than
+            the precision of the wider operand then the result is a NaN.
+            */
+            pub fn div_integer(&self, other: &Self) -> Self {
+                self.div_integer_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Divide this decimal by `other`, truncating the quotient to an integer, using the
+            given context.
+
+            If the integer quotient has more digits than the precision of the context then the
+            result is a NaN.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn div_integer_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                self.divide(other, $crate::math::Division::Integer, ctx)
+            }
+
+            /**
+            Get the remainder of dividing this decimal by `other`, where the quotient is truncated
+            to an integer.
+
+            The result has the same sign as this decimal. If the integer quotient has more digits than
+            the precision of the wider operand, or if `other` is zero, then the result is a NaN.
+            */
+            pub fn rem(&self, other: &Self) -> Self {
+                self.rem_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the remainder of dividing this decimal by `other`, where the quotient is truncated
+            to an integer, using the given context.
+
+            If the integer quotient has more digits than the precision of the context then the
+            result is a NaN.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn rem_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                self.divide(other, $crate::math::Division::Remainder, ctx)
+            }
+
+            /**
+            Get the IEEE 754 remainder of dividing this decimal by `other`, where the quotient is rounded
+            to the nearest integer, with ties going to even.
+
+            The result is between `-other / 2` and `other / 2`, so it may have a different sign to
+            this decimal. If the integer quotient has more digits than the precision of the wider
+            operand, or if `other` is zero, then the result is a NaN.
+            */
+            pub fn remainder(&self, other: &Self) -> Self {
+                self.remainder_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the IEEE 754 remainder of dividing this decimal by `other`, using the given context.
+
+            If the integer quotient has more digits than the precision of the context then the
+            result is a NaN.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn remainder_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                self.divide(other, $crate::math::Division::RemainderNear, ctx)
+            }
+
+            fn divide(
+                &self,
+                other: &Self,
+                division: $crate::math::Division,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &other.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_div::<_, $c>(
+                        &self.0, &other.0, division, limits, mode, flags,
+                    ))
+                })
+            }
+        }
+    };
+}
+
+macro_rules! func {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Get the square root of this decimal.
+
+            The result is correctly rounded to the precision of this decimal, with ties rounding to even.
+            Exact results use the exponent closest to half the exponent of this decimal, so the square
+            root of `1.00` is `1.0`. The square root of a negative number is a NaN.
+            */
+            pub fn sqrt(&self) -> Self {
+                self.sqrt_with(&mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Raise `e` to the power of this decimal.
+
+            The result is correctly rounded to the precision of this decimal, with ties rounding to even.
+            */
+            pub fn exp(&self) -> Self {
+                self.exp_with(&mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the natural logarithm of this decimal.
+
+            The result is correctly rounded to the precision of this decimal, with ties rounding to even.
+            The logarithm of zero is negative infinity, and the logarithm of a negative number is a NaN.
+            */
+            pub fn ln(&self) -> Self {
+                self.ln_with(&mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the base-10 logarithm of this decimal.
+
+            The result is correctly rounded to the precision of this decimal, with ties rounding to even.
+            The logarithm of an exact power of ten is an exact integer.
+            */
+            pub fn log10(&self) -> Self {
+                self.log10_with(&mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Raise this decimal to the power of `other`.
+
+            The result is correctly rounded to the precision of the wider operand, with ties rounding
+            to even. Integer powers may be exact, like `2 ^ 10`. Raising a negative number to a power
+            that isn't an integer, or raising zero to the power of zero, returns a NaN.
+            */
+            pub fn pow(&self, other: &Self) -> Self {
+                self.pow_with(other, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+        }
+
+        func!($c => $d: with);
+    };
+    ($c:ty => $d:ident, precision) => {
+        impl $d {
+            /**
+            Get the square root of this decimal, rounded to the given number of digits.
+
+            Ties round to even. Exact results use the exponent closest to half the exponent of this
+            decimal, so the square root of `1.00` is `1.0`. The square root of a negative number is a NaN.
+            */
+            pub fn sqrt(&self, precision: usize) -> Self {
+                self.sqrt_with(&mut $crate::Context::new().with_precision(precision))
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Raise `e` to the power of this decimal, rounded to the given number of digits.
+
+            The result is correctly rounded, with ties rounding to even.
+            */
+            pub fn exp(&self, precision: usize) -> Self {
+                self.exp_with(&mut $crate::Context::new().with_precision(precision))
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the natural logarithm of this decimal, rounded to the given number of digits.
+
+            The result is correctly rounded, with ties rounding to even. The logarithm of zero is
+            negative infinity, and the logarithm of a negative number is a NaN.
+            */
+            pub fn ln(&self, precision: usize) -> Self {
+                self.ln_with(&mut $crate::Context::new().with_precision(precision))
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Get the base-10 logarithm of this decimal, rounded to the given number of digits.
+
+            The result is correctly rounded, with ties rounding to even. The logarithm of an exact
+            power of ten is an exact integer.
+            */
+            pub fn log10(&self, precision: usize) -> Self {
+                self.log10_with(&mut $crate::Context::new().with_precision(precision))
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Raise this decimal to the power of `other`, rounded to the given number of digits.
+
+            The result is correctly rounded, with ties rounding to even. Integer powers may be exact,
+            like `2 ^ 10`. Raising a negative number to a power that isn't an integer, or raising zero
+            to the power of zero, returns a NaN.
+            */
+            pub fn pow(&self, other: &Self, precision: usize) -> Self {
+                self.pow_with(other, &mut $crate::Context::new().with_precision(precision))
+                    .expect("the default context doesn't trap")
+            }
+        }
+
+        func!($c => $d: with);
+    };
+    ($c:ty => $d:ident: with) => {
+        impl $d {
+            /**
+            Get the square root of this decimal, using the given context.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn sqrt_with(&self, ctx: &mut $crate::Context) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_sqrt::<_, $c>(
+                        &self.0, limits, mode, flags,
+                    ))
+                })
+            }
+
+            /**
+            Raise `e` to the power of this decimal, using the given context.
+
+            The result is correctly rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn exp_with(&self, ctx: &mut $crate::Context) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_exp::<_, $c>(&self.0, limits, mode, flags))
+                })
+            }
+
+            /**
+            Get the natural logarithm of this decimal, using the given context.
+
+            The result is correctly rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn ln_with(&self, ctx: &mut $crate::Context) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_ln::<_, $c>(&self.0, limits, mode, flags))
+                })
+            }
+
+            /**
+            Get the base-10 logarithm of this decimal, using the given context.
+
+            The result is correctly rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn log10_with(&self, ctx: &mut $crate::Context) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_log10::<_, $c>(&self.0, limits, mode, flags))
+                })
+            }
+
+            /**
+            Raise this decimal to the power of `other`, using the given context.
+
+            The result is correctly rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn pow_with(
+                &self,
+                other: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &other.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_pow::<_, $c>(
+                        &self.0, &other.0, limits, mode, flags,
+                    ))
+                })
+            }
+        }
+    };
+}
+
+macro_rules! quantize {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Change the exponent of this decimal to match the exponent of `like`.
+
+            The coefficient is padded with zeros, or rounded with ties going to even, so
+            `1.235` quantized like `0.01` is `1.24`. If the result would need more digits than the
+            precision of the decimal, or if only one of the operands is infinite, then the result is a NaN.
+            */
+            pub fn quantize(&self, like: &Self) -> Self {
+                self.quantize_with(like, &mut $crate::Context::new())
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Change the exponent of this decimal to match the exponent of `like`, using the
+            given context.
+
+            Any digits rounded away are rounded using the rounding mode of the context. If the
+            result would need more digits than the precision of the context then the result is a
+            NaN.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn quantize_with(
+                &self,
+                like: &Self,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0, &like.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_quantize::<_, $c>(
+                        &self.0, &like.0, limits, mode, flags,
+                    ))
+                })
+            }
+
+            /**
+            Change the exponent of this decimal to `exp`, rounding away any digits using the given
+            rounding mode.
+
+            The exponent is the power of ten of the least significant digit, so rescaling to `-2`
+            gives a number with two decimal places. If the result would need more digits than the
+            precision of the decimal, or if the exponent can't be encoded, then the result is a NaN.
+            */
+            pub fn rescale(&self, exp: i32, mode: $crate::RoundingMode) -> Self {
+                self.rescale_with(exp, &mut $crate::Context::new().with_rounding(mode))
+                    .expect("the default context doesn't trap")
+            }
+
+            /**
+            Change the exponent of this decimal to `exp`, using the given context.
+
+            Any digits rounded away are rounded using the rounding mode of the context. If the
+            result would need more digits than the precision of the context then the result is a
+            NaN.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn rescale_with(
+                &self,
+                exp: i32,
+                ctx: &mut $crate::Context,
+            ) -> Result<Self, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_rescale::<_, $c>(
+                        &self.0, exp, limits, mode, flags,
+                    ))
+                })
+            }
+
+            /**
+            Whether this decimal has the same exponent as `other`.
+
+            The values of the decimals don't matter, so `1.00` and `-9.99` have the same quantum.
+            Infinities have the same quantum as each other, and so do NaNs.
+            */
+            pub fn same_quantum(&self, other: &Self) -> bool {
+                $crate::math::decimal_same_quantum(&self.0, &other.0)
+            }
+        }
+    };
+}
+
+macro_rules! reduce {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Remove trailing zeros from the significand of this decimal, raising its exponent.
+
+            This is also known as normalizing. Decimals that are numerically equal reduce to the same
+            encoding, so `1.200e3` and `1200` both reduce to `12e2`. Zeros reduce to an exponent of zero,
+            keeping their sign. The exponent is never raised beyond the largest one that can be encoded.
+            */
+            #[doc(alias = "normalize")]
+            pub fn reduce(&self) -> Self {
+                self.reduce_to(None)
+            }
+
+            /**
+            Remove trailing zeros from the significand of this decimal, keeping at least `min_scale`
+            digits after the decimal point.
+
+            The scale of a decimal is the number of digits after its decimal point, so `1.2000` trimmed
+            to a scale of `2` is `1.20`. Decimals that already have fewer digits after their decimal point
+            are left unchanged, so `1.2` trimmed to a scale of `2` is still `1.2`.
+            */
+            pub fn trim_to_scale(&self, min_scale: i32) -> Self {
+                self.reduce_to(Some(min_scale.saturating_neg()))
+            }
+
+            fn reduce_to(&self, max_exponent: Option<i32>) -> Self {
+                let mut ctx = $crate::Context::new();
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, _, flags| {
+                    $d($crate::math::decimal_reduce::<_, $c>(
+                        &self.0,
+                        max_exponent,
+                        limits,
+                        flags,
+                    ))
+                })
+                .expect("the default context doesn't trap")
+            }
+        }
+    };
+}
+
+macro_rules! integral {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Round this decimal to an integer, using the given rounding mode.
+
+            The result has an exponent of zero, unless this decimal is already an integer. Integers,
+            infinities, and NaNs are returned unchanged, so `1.2e3` rounds to `1.2e3`.
+            */
+            pub fn round_to_integral(&self, mode: $crate::RoundingMode) -> Self {
+                let mut ctx = $crate::Context::new().with_rounding(mode);
+                let storage_width_bits = ctx.storage_width_bits(&[&self.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_round_to_integral::<_, $c>(
+                        &self.0, limits, mode, flags,
+                    ))
+                })
+                .expect("the default context doesn't trap")
+            }
+
+            /**
+            Round this decimal to an integer towards zero.
+            */
+            pub fn trunc(&self) -> Self {
+                self.round_to_integral($crate::RoundingMode::Down)
+            }
+
+            /**
+            Round this decimal to an integer towards negative infinity.
+            */
+            pub fn floor(&self) -> Self {
+                self.round_to_integral($crate::RoundingMode::Floor)
+            }
+
+            /**
+            Round this decimal to an integer towards positive infinity.
+            */
+            pub fn ceil(&self) -> Self {
+                self.round_to_integral($crate::RoundingMode::Ceiling)
+            }
+
+            /**
+            Round this decimal to the nearest integer, with ties rounding away from zero.
+
+            This matches the behavior of `f64::round`, so `2.5` rounds to `3` and `-2.5` rounds to `-3`.
+            */
+            pub fn round(&self) -> Self {
+                self.round_to_integral($crate::RoundingMode::HalfUp)
+            }
+        }
+    };
+}
+
+macro_rules! parts {
+    ($d:ident) => {
+        impl $d {
+            /**
+            Get the sign, coefficient, and exponent of this decimal.
+
+            The value of the decimal is `coefficient * 10^exponent`, negated if the sign is negative.
+            The coefficient and exponent are returned exactly as they're encoded, so `1.20` returns
+            `(false, 120, -2)`.
+
+            This method returns `None` if the decimal is infinite or NaN, or if its coefficient doesn't
+            fit in a `u128`.
+            */
+            pub fn to_parts(&self) -> Option<(bool, u128, i32)> {
+                let (is_negative, coefficient, exponent) =
+                    $crate::convert::decimal_to_parts::<_, u128>(&self.0)?;
+
+                Some((
+                    is_negative,
+                    coefficient,
+                    $crate::num::Integer::to_i32(&exponent)
+                        .expect("the exponent always fits in an i32"),
+                ))
+            }
+
+            /**
+            Create a decimal from its sign, coefficient, and exponent.
+
+            The value of the decimal is `coefficient * 10^exponent`, negated if `is_negative` is `true`.
+            The decimal is encoded exactly, so this method returns an error if the coefficient has
+            more digits than the precision of the decimal, or if the exponent is out of range.
+            */
+            pub fn from_parts(
+                is_negative: bool,
+                coefficient: u128,
+                exponent: i32,
+            ) -> Result<$d, $crate::Error> {
+                let mut buf = itoa::Buffer::new();
+                let coefficient = buf.format(coefficient);
+
+                Ok($d($crate::convert::decimal_from_parts(
+                    is_negative,
+                    coefficient.as_bytes(),
+                    $crate::num::Integer::from_i32(exponent),
+                )?))
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn parts() {
+            for s in ["0", "-0.00", "1.20", "-1.23e7", "nan", "-inf"] {
+                let d = $d::try_parse_str(s).expect("failed to parse decimal");
+
+                match d.to_parts() {
+                    Some((is_negative, coefficient, exponent)) => {
+                        let roundtrip = $d::from_parts(is_negative, coefficient, exponent)
+                            .expect("failed to convert decimal");
+
+                        assert_eq!(d.to_string(), roundtrip.to_string());
+                    }
+                    None => assert!(!d.is_finite()),
+                }
+            }
+
+            assert!($d::from_parts(false, 1, i32::MAX).is_err());
+        }
+    };
+    ($d:ident, big) => {
+        impl $d {
+            /**
+            Get the sign, coefficient, and exponent of this decimal.
+
+            The value of the decimal is `coefficient * 10^exponent`, negated if the sign is negative.
+            The coefficient and exponent are returned exactly as they're encoded, so `1.20` returns
+            `(false, 120, -2)`.
+
+            This method returns `None` if the decimal is infinite or NaN.
+            */
+            pub fn to_parts(&self) -> Option<(bool, num_bigint::BigUint, num_bigint::BigInt)> {
+                let (is_negative, coefficient, exponent) =
+                    $crate::convert::decimal_to_parts::<_, num_bigint::BigInt>(&self.0)?;
+
+                Some((
+                    is_negative,
+                    coefficient
+                        .to_biguint()
+                        .expect("the coefficient is non-negative"),
+                    exponent.into(),
+                ))
+            }
+
+            /**
+            Create a decimal from its sign, coefficient, and exponent.
+
+            The value of the decimal is `coefficient * 10^exponent`, negated if `is_negative` is `true`.
+            The decimal is encoded exactly, using as much precision as the coefficient needs.
+            */
+            pub fn from_parts(
+                is_negative: bool,
+                coefficient: &num_bigint::BigUint,
+                exponent: &num_bigint::BigInt,
+            ) -> $d {
+                let coefficient = coefficient.to_str_radix(10);
+
+                $d($crate::convert::decimal_from_parts(
+                    is_negative,
+                    coefficient.as_bytes(),
+                    exponent.clone().into(),
+                )
+                .expect("arbitrary precision decimals can always be encoded"))
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn parts() {
+            for s in [
+                "0",
+                "-0.00",
+                "1.20",
+                "-1.23e7",
+                "12345678901234567890123456789012345678901234567890e-9999",
+                "nan",
+                "-inf",
+            ] {
+                let d = $d::try_parse_str(s).expect("failed to parse decimal");
+
+                match d.to_parts() {
+                    Some((is_negative, coefficient, exponent)) => {
+                        let roundtrip = $d::from_parts(is_negative, &coefficient, &exponent);
+
+                        assert_eq!(d.to_string(), roundtrip.to_string());
+                    }
+                    None => assert!(!d.is_finite()),
+                }
+            }
+        }
+    };
+}
+
+macro_rules! digits {
+    ($d:ident => $e:ty) => {
+        impl $d {
+            /**
+            Get an iterator over the digits of the coefficient of this decimal, from most to least
+            significant.
+
+            Leading zeros aren't included, so `0.0120` yields the digits `1`, `2`, `0`. A zero
+            coefficient yields the single digit `0`. Infinities and NaNs don't yield any digits.
+            */
+            pub fn digits(&self) -> impl Iterator<Item = u8> + '_ {
+                $crate::convert::decimal_digits(&self.0)
+            }
+
+            /**
+            Get the number of significant digits in the coefficient of this decimal.
+
+            Trailing zeros are significant, so `1.20` has `3` significant digits. A zero has `1`
+            significant digit, and infinities and NaNs have `0`.
+            */
+            pub fn significant_digits(&self) -> usize {
+                self.digits().count()
+            }
+
+            /**
+            Get the exponent of this decimal.
+
+            The value of a decimal is its coefficient multiplied by `10^exponent`, so `1.20` has an
+            exponent of `-2`. This method returns `None` if the decimal is infinite or NaN.
+            */
+            pub fn exponent(&self) -> Option<$e> {
+                $crate::convert::decimal_exponent(&self.0).map(Into::into)
+            }
+
+            /**
+            Get the scale of this decimal, which is the number of digits after the decimal point.
+
+            The scale is the negated exponent, so `1.20` has a scale of `2` and `1.2e3` has a scale
+            of `-2`. This method returns `None` if the decimal is infinite or NaN.
+            */
+            pub fn scale(&self) -> Option<$e> {
+                self.exponent().map(|exponent| -exponent)
+            }
+
+            /**
+            Get the adjusted exponent of this decimal, which is the exponent of its most significant
+            digit.
+
+            This is the exponent the decimal would have in scientific notation, so `1.20` has an
+            adjusted exponent of `0` and `123.4` has an adjusted exponent of `2`. This method returns
+            `None` if the decimal is infinite or NaN.
+            */
+            pub fn adjusted_exponent(&self) -> Option<$e> {
+                let exponent = self.exponent()?;
+
+                Some(exponent + <$e as $crate::num::Integer>::from_i32(self.significant_digits() as i32 - 1))
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn digits() {
+            let d = $d::try_parse_str("-0.01230").expect("failed to parse decimal");
+
+            assert_eq!(vec![1, 2, 3, 0], d.digits().collect::<Vec<_>>());
+            assert_eq!(4, d.significant_digits());
+            assert_eq!(Some(<$e>::from(-5i8)), d.exponent());
+            assert_eq!(Some(<$e>::from(5i8)), d.scale());
+            assert_eq!(Some(<$e>::from(-2i8)), d.adjusted_exponent());
+
+            let d = $d::try_parse_str("inf").expect("failed to parse decimal");
+
+            assert_eq!(0, d.significant_digits());
+            assert_eq!(None, d.exponent());
+            assert_eq!(None, d.adjusted_exponent());
+        }
+    };
+}
+
+macro_rules! nan {
+    ($d:ident) => {
+        impl $d {
+            /**
+            Get the payload of this decimal if it's a NaN.
+
+            A NaN without a payload, like `nan`, has a payload of `0`. This method returns `None` if
+            the decimal isn't a NaN, or if its payload doesn't fit in a `u128`.
+            */
+            pub fn nan_payload(&self) -> Option<u128> {
+                $crate::convert::decimal_nan_payload(&self.0)
+            }
+
+            /**
+            Create a positive NaN with the given payload.
+
+            The payload is stored in the trailing digits of the significand, so it must have fewer
+            digits than the precision of the decimal. If it doesn't fit then this method returns
+            an error.
+            */
+            pub fn nan_with_payload(payload: u128, signaling: bool) -> Result<$d, $crate::Error> {
+                let mut buf = itoa::Buffer::new();
+                let payload = buf.format(payload);
+
+                Ok($d($crate::convert::decimal_nan_with_payload(
+                    payload.as_bytes(),
+                    signaling,
+                )?))
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn nan_payload() {
+            let d = $d::nan_with_payload(42, true).expect("failed to create NaN");
+            assert_eq!("snan(42)", d.to_string());
+            assert_eq!(Some(42), d.nan_payload());
+            assert_eq!(Some(42), d.quieten().nan_payload());
+            assert!(d.quieten().is_quiet_nan());
+
+            assert_eq!(None, $d::zero().nan_payload());
+        }
+    };
+    ($d:ident, big) => {
+        impl $d {
+            /**
+            Get the payload of this decimal if it's a NaN.
+
+            A NaN without a payload, like `nan`, has a payload of `0`. This method returns `None` if
+            the decimal isn't a NaN.
+            */
+            pub fn nan_payload(&self) -> Option<num_bigint::BigUint> {
+                $crate::convert::decimal_nan_payload::<_, num_bigint::BigInt>(&self.0)?.to_biguint()
+            }
+
+            /**
+            Create a positive NaN with the given payload.
+
+            The decimal is encoded with as much precision as the payload needs.
+            */
+            pub fn nan_with_payload(payload: &num_bigint::BigUint, signaling: bool) -> $d {
+                let payload = payload.to_str_radix(10);
+
+                $d(
+                    $crate::convert::decimal_nan_with_payload(payload.as_bytes(), signaling)
+                        .expect("arbitrary precision decimals can always be encoded"),
+                )
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn nan_payload() {
+            let payload = num_bigint::BigUint::from(u128::MAX);
+
+            let d = $d::nan_with_payload(&payload, true);
+            assert_eq!(format!("snan({})", u128::MAX), d.to_string());
+            assert_eq!(Some(payload.clone()), d.nan_payload());
+            assert_eq!(Some(payload), d.quieten().nan_payload());
+            assert!(d.quieten().is_quiet_nan());
+
+            assert_eq!(None, $d::zero().nan_payload());
+        }
+    };
+}
+
+macro_rules! bid {
+    ($d:ident: $n:literal) => {
+        impl $d {
+            /**
+            Create a decimal from its binary-integer-decimal (BID) encoding as a byte array in
+            little endian.
+
+            BID is the IEEE754-2019 decimal encoding used by Intel's decimal library, GCC's decimal
+            types on x86, and MongoDB. Non-canonical encodings are decoded the same way as those
+            implementations, so a significand that's larger than the precision is decoded as zero.
+            */
+            pub fn from_bid_bytes(bytes: [u8; $n]) -> Self {
+                let mut bid = [0; 16];
+                bid[..$n].copy_from_slice(&bytes);
+
+                let mut buf = $crate::binary::FixedBinaryBuf::ZERO;
+                $crate::binary::decode_bid(u128::from_le_bytes(bid), &mut buf);
+
+                $d(buf)
+            }
+
+            /**
+            Return the binary-integer-decimal (BID) encoding of this decimal as a byte array in
+            little endian.
+
+            The result is always canonical, and converts back to the same decimal losslessly.
+            */
+            pub fn to_bid_bytes(&self) -> [u8; $n] {
+                let bid = $crate::binary::encode_bid(&self.0).to_le_bytes();
+
+                let mut bytes = [0; $n];
+                bytes.copy_from_slice(&bid[..$n]);
+
+                bytes
+            }
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn bid() {
+            for d in ["0", "-1.23", "1e-10", "-inf", "snan(42)"] {
+                let d = $d::try_parse_str(d).expect("failed to parse decimal");
+
+                assert_eq!(
+                    d.as_le_bytes(),
+                    $d::from_bid_bytes(d.to_bid_bytes()).as_le_bytes()
+                );
+            }
+
+            assert_eq!($d::ONE.to_bid_bytes()[0], 1);
+        }
+    };
+}
+
+macro_rules! ops {
+    ($d:ident) => {
+        ops!($d: Add, add, AddAssign, add_assign);
+        ops!($d: Sub, sub, SubAssign, sub_assign);
+        ops!($d: Mul, mul, MulAssign, mul_assign);
+        ops!($d: Div, div, DivAssign, div_assign);
+        ops!($d: Rem, rem, RemAssign, rem_assign);
+
+        impl core::ops::Neg for $d {
+            type Output = $d;
+
+            fn neg(mut self) -> $d {
+                $crate::binary::negate(&mut self.0);
+                self
+            }
+        }
+
+        impl<'a> core::ops::Neg for &'a $d {
+            type Output = $d;
+
+            fn neg(self) -> $d {
+                -*self
+            }
+        }
+
+        impl core::iter::Sum for $d {
+            fn sum<I: Iterator<Item = $d>>(mut iter: I) -> $d {
+                // Start from the first value instead of zero, so the exponent of the sum
+                // isn't affected by the exponent of the zero
+                match iter.next() {
+                    Some(first) => iter.fold(first, |sum, d| sum + d),
+                    None => $d::ZERO,
+                }
+            }
+        }
+
+        impl<'a> core::iter::Sum<&'a $d> for $d {
+            fn sum<I: Iterator<Item = &'a $d>>(iter: I) -> $d {
+                iter.copied().sum()
+            }
+        }
+
+        impl core::iter::Product for $d {
+            fn product<I: Iterator<Item = $d>>(mut iter: I) -> $d {
+                match iter.next() {
+                    Some(first) => iter.fold(first, |product, d| product * d),
+                    None => $d::ONE,
+                }
+            }
+        }
+
+        impl<'a> core::iter::Product<&'a $d> for $d {
+            fn product<I: Iterator<Item = &'a $d>>(iter: I) -> $d {
+                iter.copied().product()
+            }
+        }
+    };
+    ($d:ident: $op:ident, $f:ident, $op_assign:ident, $f_assign:ident) => {
+        impl core::ops::$op<$d> for $d {
+            type Output = $d;
+
+            fn $f(self, rhs: $d) -> $d {
+                $d::$f(&self, &rhs)
+            }
+        }
+
+        impl<'a> core::ops::$op<&'a $d> for $d {
+            type Output = $d;
+
+            fn $f(self, rhs: &'a $d) -> $d {
+                $d::$f(&self, rhs)
+            }
+        }
+
+        impl<'a> core::ops::$op<$d> for &'a $d {
+            type Output = $d;
+
+            fn $f(self, rhs: $d) -> $d {
+                $d::$f(self, &rhs)
+            }
+        }
+
+        impl<'a, 'b> core::ops::$op<&'b $d> for &'a $d {
+            type Output = $d;
+
+            fn $f(self, rhs: &'b $d) -> $d {
+                $d::$f(self, rhs)
+            }
+        }
+
+        impl core::ops::$op_assign<$d> for $d {
+            fn $f_assign(&mut self, rhs: $d) {
+                *self = $d::$f(self, &rhs);
+            }
+        }
+
+        impl<'a> core::ops::$op_assign<&'a $d> for $d {
+            fn $f_assign(&mut self, rhs: &'a $d) {
+                *self = $d::$f(self, rhs);
+            }
         }
     };
 }
@@ -106,6 +1444,50 @@ macro_rules! try_s2d {
     };
 }
 
+macro_rules! round_s2d {
+    ($c:ty => $d:ident) => {
+        impl $d {
+            /**
+            Parse a decimal from a string, rounding it to fit if it has too many digits.
+
+            The significand is rounded to `DIGITS` digits using the given rounding mode. Numbers
+            that are too large to encode overflow, and numbers that are too small underflow, following
+            IEEE 754. The returned flags report whether the result was inexact, along with any other
+            conditions encountered while rounding.
+            */
+            pub fn parse_rounded(
+                s: &str,
+                mode: $crate::RoundingMode,
+            ) -> Result<($d, $crate::Flags), $crate::Error> {
+                let mut ctx = $crate::Context::new().with_rounding(mode);
+
+                let d = $d::try_parse_str_with(s, &mut ctx)?;
+
+                Ok((d, ctx.flags()))
+            }
+
+            /**
+            Parse a decimal from a string, using the given context.
+
+            If the number can't be encoded exactly then it's rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn try_parse_str_with(
+                s: &str,
+                ctx: &mut $crate::Context,
+            ) -> Result<$d, $crate::Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&$d::ZERO.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $crate::convert::decimal_from_str_rounded::<_, $c>(s, limits, mode, flags)
+                        .map($d)
+                })?
+            }
+        }
+    };
+}
+
 macro_rules! i2d {
     ($i:ident => $convert:ident => $d:ident) => {
         impl $d {
@@ -134,7 +1516,7 @@ macro_rules! i2d {
 }
 
 macro_rules! try_i2d {
-    ($i:ident => $convert:ident => $d:ident) => {
+    ($i:ident => $convert:ident, $convert_with:ident => $d:ident) => {
         impl $d {
             /**
             Try convert an integer into a decimal.
@@ -142,6 +1524,29 @@ macro_rules! try_i2d {
             pub fn $convert(i: $i) -> Option<$d> {
                 Some($d($crate::convert::decimal_from_int(i).ok()?))
             }
+
+            /**
+            Convert an integer into a decimal, using the given context.
+
+            If the number can't be encoded exactly then it's rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn $convert_with(i: $i, ctx: &mut $crate::Context) -> Result<$d, $crate::Error> {
+                // Convert into a decimal that's wide enough to hold the number exactly first
+                let wide: $crate::binary::DynamicBinaryBuf<20> =
+                    $crate::convert::decimal_from_int(i)?;
+
+                let storage_width_bits = ctx.storage_width_bits(&[&$d::ZERO.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_narrow::<
+                        _,
+                        _,
+                        $crate::math::ArrayDigitBuf<64>,
+                    >(&wide, limits, mode, flags))
+                })
+            }
         }
 
         impl TryFrom<$i> for $d {
@@ -174,12 +1579,19 @@ macro_rules! try_i2d {
                 "{} should not have been converted",
                 $i::MAX
             );
+            let mut ctx = $crate::Context::new();
+            assert!(
+                $d::$convert_with($i::MAX, &mut ctx).is_ok(),
+                "{} should have been rounded",
+                $i::MAX
+            );
+            assert!(ctx.flags().contains($crate::Flags::INEXACT));
         }
     };
 }
 
 macro_rules! try_d2i {
-    ($d:ident => $convert:ident => $i:ident) => {
+    ($d:ident => $convert:ident, $rounded:ident, $saturating:ident, $truncated:ident => $i:ident) => {
         impl $d {
             /**
             Try convert a decimal into an integer.
@@ -187,6 +1599,45 @@ macro_rules! try_d2i {
             pub fn $convert(&self) -> Option<$i> {
                 $crate::convert::decimal_to_int(&self.0).ok()
             }
+
+            /**
+            Try convert a decimal into an integer, first rounding it to an integer using the given
+            rounding mode.
+
+            This method returns `None` if the rounded decimal is out of range, or if it's infinite or NaN.
+            */
+            pub fn $rounded(&self, mode: $crate::RoundingMode) -> Option<$i> {
+                self.round_to_integral(mode).$convert()
+            }
+
+            /**
+            Convert a decimal into an integer, truncating any fractional digits and saturating
+            at the bounds of the integer.
+
+            Values below the minimum integer, including negative infinity, convert to the minimum.
+            Values above the maximum integer, including positive infinity, convert to the maximum.
+            NaNs convert to zero. This matches the behavior of casting a binary floating point with `as`.
+            */
+            pub fn $saturating(&self) -> $i {
+                if self.is_nan() {
+                    return 0;
+                }
+
+                match self.trunc().$convert() {
+                    Some(i) => i,
+                    None if self.is_sign_negative() => $i::MIN,
+                    None => $i::MAX,
+                }
+            }
+
+            /**
+            Try convert a decimal into an integer, truncating any fractional digits.
+
+            This method returns `None` if the truncated decimal is out of range, or if it's infinite or NaN.
+            */
+            pub fn $truncated(&self) -> Option<$i> {
+                self.trunc().$convert()
+            }
         }
 
         impl TryFrom<$d> for $i {
@@ -222,8 +1673,82 @@ macro_rules! try_d2i {
                     "{} should not have been converted",
                     max
                 );
+                assert!(max.$truncated().is_none());
+                assert!(max.$rounded($crate::RoundingMode::Floor).is_none());
+                assert_eq!($i::MAX, max.$saturating());
+            }
+
+            if let Some::<$d>(min) = $d::min().into() {
+                assert_eq!($i::MIN, min.$saturating());
+            }
+
+            let d = $d::try_parse_str("12.7").expect("failed to parse decimal");
+            assert_eq!(None, d.$convert());
+            assert_eq!(Some(13), d.$rounded($crate::RoundingMode::HalfEven));
+            assert_eq!(Some(12), d.$truncated());
+            assert_eq!(12, d.$saturating());
+
+            let d = $d::try_parse_str("nan").expect("failed to parse decimal");
+            assert_eq!(0, d.$saturating());
+            assert_eq!(None, d.$truncated());
+        }
+    };
+}
+
+macro_rules! scaled {
+    ($d:ident => $to_scaled:ident, $from_scaled:ident => $i:ident) => {
+        impl $d {
+            /**
+            Convert a decimal into an integer scaled by `10^scale`.
+
+            This is the inverse of
+            #[doc = concat!("[`", stringify!($d), "::", stringify!($from_scaled), "`],")]
+            so a decimal with a scale of `4`, like `12.3456`, converts to `123456`. Trailing zeros are
+            padded or removed as needed, so `12.3` converts to `123000` with the same scale.
+
+            This method returns an error if the scaled value would need rounding to fit in an integer,
+            if it would overflow the integer, or if the decimal is infinite or NaN.
+            */
+            pub fn $to_scaled(&self, scale: i32) -> Result<$i, $crate::ConvertError> {
+                $crate::convert::decimal_to_scaled_int(&self.0, scale)
+            }
+
+            /**
+            Convert an integer scaled by `10^scale` into a decimal.
+
+            The decimal has an exponent of `-scale`, so the integer `123000` with a scale of `4`
+            converts to `12.3000`.
+
+            This method returns an error if the integer has more digits than the precision of the
+            decimal, or if the exponent is out of range. The integer is never rounded.
+            */
+            pub fn $from_scaled(value: $i, scale: i32) -> Result<$d, $crate::ConvertError> {
+                $crate::convert::decimal_from_scaled_int(value, scale)
+                    .map($d)
+                    .ok_or_else(|| $crate::ConvertError::would_overflow(stringify!($d)))
             }
         }
+
+        #[cfg(test)]
+        #[test]
+        fn $to_scaled() {
+            let d = $d::$from_scaled(123456, 4).expect("failed to convert decimal");
+            assert_eq!("12.3456", d.to_string());
+            assert_eq!(123456, d.$to_scaled(4).expect("failed to convert decimal"));
+            assert_eq!(
+                12345600,
+                d.$to_scaled(6).expect("failed to convert decimal")
+            );
+            assert!(d.$to_scaled(3).is_err());
+
+            let d = $d::$from_scaled(-5, 0).expect("failed to convert decimal");
+            assert_eq!(-5, d.$to_scaled(0).expect("failed to convert decimal"));
+
+            assert!($d::$from_scaled(1, i32::MIN).is_err());
+
+            let d = $d::try_parse_str("inf").expect("failed to parse decimal");
+            assert!(d.$to_scaled(0).is_err());
+        }
     };
 }
 
@@ -297,7 +1822,7 @@ macro_rules! d2f {
 }
 
 macro_rules! try_f2d {
-    ($f:ident => $convert:ident => $d:ident) => {
+    ($f:ident => $convert:ident, $convert_with:ident => $d:ident) => {
         impl $d {
             /**
             Try convert a binary floating point into a decimal.
@@ -305,6 +1830,29 @@ macro_rules! try_f2d {
             pub fn $convert(f: $f) -> Option<$d> {
                 Some($d($crate::convert::decimal_from_binary_float(f).ok()?))
             }
+
+            /**
+            Convert a binary floating point into a decimal, using the given context.
+
+            If the number can't be encoded exactly then it's rounded.
+
+            See [`Context`](crate::Context) for how the result is rounded and trapped.
+            */
+            pub fn $convert_with(f: $f, ctx: &mut $crate::Context) -> Result<$d, $crate::Error> {
+                // Convert into a decimal that's wide enough to hold the number exactly first
+                let wide: $crate::binary::DynamicBinaryBuf<20> =
+                    $crate::convert::decimal_from_binary_float(f)?;
+
+                let storage_width_bits = ctx.storage_width_bits(&[&$d::ZERO.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    $d($crate::math::decimal_narrow::<
+                        _,
+                        _,
+                        $crate::math::ArrayDigitBuf<64>,
+                    >(&wide, limits, mode, flags))
+                })
+            }
         }
 
         impl TryFrom<$f> for $d {
@@ -339,6 +1887,14 @@ macro_rules! try_f2d {
                 $f::MIN_POSITIVE
             );
 
+            let mut ctx = $crate::Context::new();
+            assert!(
+                $d::$convert_with($f::MAX, &mut ctx).is_ok(),
+                "{} should have been rounded",
+                $f::MAX
+            );
+            assert!(ctx.flags().contains($crate::Flags::INEXACT));
+
             // NAN and INFINITY constants should still convert
             assert!($d::$convert($f::NAN).unwrap().is_nan());
             assert!($d::$convert($f::INFINITY).unwrap().is_infinite());
@@ -398,6 +1954,7 @@ mod dynamic;
 mod fixed128;
 mod fixed32;
 mod fixed64;
+mod key;
 
 #[cfg(feature = "arbitrary-precision")]
 mod arbitrary;
@@ -410,4 +1967,5 @@ pub use self::{
     fixed128::*,
     fixed32::*,
     fixed64::*,
+    key::*,
 };
@@ -4,6 +4,7 @@ use crate::{
         DynamicBinaryBuf,
     },
     text::ArrayTextBuf,
+    ConvertError,
     Error,
     OverflowError,
 };
@@ -15,7 +16,7 @@ use crate::binary::encode_max;
 A dynamically sized decimal number with enough precision to fit any Rust primitive number.
 */
 #[derive(Clone, Copy)]
-pub struct Bitstring(DynamicBinaryBuf<20>);
+pub struct Bitstring(pub(crate) DynamicBinaryBuf<20>);
 
 impl Bitstring {
     /**
@@ -25,6 +26,7 @@ impl Bitstring {
     This method will fail if the buffer length is not a multiple of 4 bytes, or it's too
     big to fit in a `Bitstring`.
     */
+    #[allow(clippy::manual_is_multiple_of)]
     pub fn try_from_le_bytes(bytes: &[u8]) -> Result<Self, Error> {
         if bytes.is_empty() || bytes.len() % 4 != 0 {
             Err(OverflowError::exact_size_mismatch(
@@ -41,6 +43,24 @@ impl Bitstring {
         Ok(Self(buf))
     }
 
+    /**
+    Try create a decimal from the given buffer, only accepting canonical encodings.
+
+    This method will fail for the same reasons as [`Bitstring::try_from_le_bytes`], and also if
+    the buffer isn't the canonical encoding of its value. Use this method for bytes from untrusted
+    sources, where a non-canonical encoding could be used to produce decimals that are equal but
+    have different bytes.
+    */
+    pub fn try_from_le_bytes_canonical(bytes: &[u8]) -> Result<Self, Error> {
+        let decimal = Self::try_from_le_bytes(bytes)?;
+
+        if !decimal.is_canonical() {
+            Err(ConvertError::non_canonical("Bitstring"))?;
+        }
+
+        Ok(decimal)
+    }
+
     /**
     Get a reference to the underlying bitstring buffer.
 
@@ -52,6 +72,63 @@ impl Bitstring {
         self.0.bytes()
     }
 
+    /**
+    Try create a decimal from its representation in IBM's decimal floating point (DFP) format.
+
+    DFP uses the same densely-packed-decimal encoding as this library, but in big endian. IBM
+    z/Architecture and POWER store decimals this way, as does DB2 for `DECFLOAT`. This method will
+    fail for the same reasons as [`Bitstring::try_from_le_bytes`].
+
+    This is the counterpart of `from_ibm_dfp_bytes` on the fixed-width decimals. Like
+    [`Bitstring::try_from_le_bytes`], it's fallible because the width of the bytes isn't known
+    until runtime, so it's named `try_from_ibm_dfp_bytes` instead.
+    */
+    pub fn try_from_ibm_dfp_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut decimal = Self::try_from_le_bytes(bytes)?;
+
+        decimal.0.bytes_mut().reverse();
+
+        Ok(decimal)
+    }
+
+    /**
+    Return the representation of this decimal in IBM's decimal floating point (DFP) format.
+
+    The bytes are yielded in big endian, starting from the byte containing the sign bit.
+    */
+    pub fn to_ibm_dfp_bytes(&self) -> impl ExactSizeIterator<Item = u8> + '_ {
+        self.as_le_bytes().iter().rev().copied()
+    }
+
+    /**
+    Try create a decimal from its representation in Postgres' binary format for `NUMERIC`.
+
+    The decimal is encoded exactly, with the scale of the `NUMERIC` as its exponent, so `1.20`
+    keeps its trailing zero. Postgres' `NaN`, `Infinity`, and `-Infinity` are converted into
+    a NaN and infinities. This method will fail if the bytes aren't a valid `NUMERIC`, or if
+    the number has too many digits to fit in a `Bitstring`.
+    */
+    pub fn try_from_pg_numeric_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self(crate::convert::decimal_from_pg_numeric::<
+            _,
+            crate::math::ArrayDigitBuf<176>,
+        >(bytes, "Bitstring")?))
+    }
+
+    /**
+    Return the representation of this decimal in Postgres' binary format for `NUMERIC`.
+
+    `NUMERIC` doesn't support negative zero, NaN payloads, or positive exponents, so `-0` is
+    written as `0`, `snan(1)` as `NaN`, and `1.2e3` as `1200`. This method will fail if the
+    exponent is outside the range of a `NUMERIC`.
+    */
+    pub fn to_pg_numeric_bytes(&self) -> Result<impl Iterator<Item = u8>, Error> {
+        Ok(
+            crate::convert::decimal_to_pg_numeric::<_, crate::math::ArrayDigitBuf<176>>(&self.0)?
+                .into_bytes(),
+        )
+    }
+
     /**
     Create a decimal with the finite value zero.
     */
@@ -80,6 +157,23 @@ impl Bitstring {
 
 classify!(Bitstring);
 
+cmp!(Bitstring => Bitstring32);
+cmp!(Bitstring => Bitstring64);
+cmp!(Bitstring => Bitstring128);
+cmp!(Bitstring => Bitstring);
+#[cfg(feature = "arbitrary-precision")]
+cmp!(Bitstring => BigBitstring);
+
+total_cmp!(Bitstring);
+hash!(Bitstring);
+add!(crate::math::ArrayDigitBuf<176> => Bitstring);
+quantize!(crate::math::ArrayDigitBuf<176> => Bitstring);
+reduce!(crate::math::ArrayDigitBuf<176> => Bitstring);
+integral!(crate::math::ArrayDigitBuf<176> => Bitstring);
+parts!(Bitstring);
+digits!(Bitstring => i32);
+nan!(Bitstring);
+
 try_s2d!(ArrayTextBuf::<128> => Bitstring);
 d2s!(Bitstring);
 
@@ -95,11 +189,11 @@ i2d!(i32 => from_i32 => Bitstring);
 i2d!(i64 => from_i64 => Bitstring);
 i2d!(i128 => from_i128 => Bitstring);
 
-try_d2i!(Bitstring => to_i8 => i8);
-try_d2i!(Bitstring => to_i16 => i16);
-try_d2i!(Bitstring => to_i32 => i32);
-try_d2i!(Bitstring => to_i64 => i64);
-try_d2i!(Bitstring => to_i128 => i128);
+try_d2i!(Bitstring => to_i8, to_i8_rounded, to_i8_saturating, to_i8_truncated => i8);
+try_d2i!(Bitstring => to_i16, to_i16_rounded, to_i16_saturating, to_i16_truncated => i16);
+try_d2i!(Bitstring => to_i32, to_i32_rounded, to_i32_saturating, to_i32_truncated => i32);
+try_d2i!(Bitstring => to_i64, to_i64_rounded, to_i64_saturating, to_i64_truncated => i64);
+try_d2i!(Bitstring => to_i128, to_i128_rounded, to_i128_saturating, to_i128_truncated => i128);
 
 i2d!(u8 => from_u8 => Bitstring);
 i2d!(u16 => from_u16 => Bitstring);
@@ -107,8 +201,11 @@ i2d!(u32 => from_u32 => Bitstring);
 i2d!(u64 => from_u64 => Bitstring);
 i2d!(u128 => from_u128 => Bitstring);
 
-try_d2i!(Bitstring => to_u8 => u8);
-try_d2i!(Bitstring => to_u16 => u16);
-try_d2i!(Bitstring => to_u32 => u32);
-try_d2i!(Bitstring => to_u64 => u64);
-try_d2i!(Bitstring => to_u128 => u128);
+try_d2i!(Bitstring => to_u8, to_u8_rounded, to_u8_saturating, to_u8_truncated => u8);
+try_d2i!(Bitstring => to_u16, to_u16_rounded, to_u16_saturating, to_u16_truncated => u16);
+try_d2i!(Bitstring => to_u32, to_u32_rounded, to_u32_saturating, to_u32_truncated => u32);
+try_d2i!(Bitstring => to_u64, to_u64_rounded, to_u64_saturating, to_u64_truncated => u64);
+try_d2i!(Bitstring => to_u128, to_u128_rounded, to_u128_saturating, to_u128_truncated => u128);
+
+scaled!(Bitstring => to_scaled_i64, from_scaled_i64 => i64);
+scaled!(Bitstring => to_scaled_i128, from_scaled_i128 => i128);
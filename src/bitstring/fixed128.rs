@@ -5,13 +5,15 @@ use crate::{
         FixedBinaryBuf,
     },
     text::ArrayTextBuf,
+    ConvertError,
+    Error,
 };
 
 /**
 A [128bit decimal number](https://en.wikipedia.org/wiki/Decimal128_floating-point_format).
 */
 #[derive(Clone, Copy)]
-pub struct Bitstring128(FixedBinaryBuf<16, i32>);
+pub struct Bitstring128(pub(crate) FixedBinaryBuf<16, i32>);
 
 /**
 Basic mathematical constants.
@@ -202,6 +204,24 @@ impl Bitstring128 {
         Self(FixedBinaryBuf::from_le_bytes(bytes))
     }
 
+    /**
+    Try create a decimal from its representation as a byte array in little endian, only accepting
+    canonical encodings.
+
+    Use this method instead of [`Bitstring128::from_le_bytes`] for bytes from untrusted sources, where
+    a non-canonical encoding could be used to produce decimals that are equal but have different
+    bytes.
+    */
+    pub fn try_from_le_bytes_canonical(bytes: [u8; 16]) -> Result<Self, Error> {
+        let decimal = Self::from_le_bytes(bytes);
+
+        if !decimal.is_canonical() {
+            Err(ConvertError::non_canonical("Bitstring128"))?;
+        }
+
+        Ok(decimal)
+    }
+
     /**
     Create a decimal from its representation as a byte array in big endian.
     */
@@ -237,6 +257,28 @@ impl Bitstring128 {
         ]
     }
 
+    /**
+    Create a decimal from its representation in IBM's decimal floating point (DFP) format.
+
+    DFP uses the same densely-packed-decimal encoding as this library, but in big endian, so this
+    method is equivalent to [`Bitstring128::from_be_bytes`]. IBM z/Architecture and POWER store
+    decimal128 values this way, as does DB2 for `DECFLOAT(34)`.
+    */
+    #[inline]
+    pub const fn from_ibm_dfp_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_be_bytes(bytes)
+    }
+
+    /**
+    Return the representation of this decimal in IBM's decimal floating point (DFP) format.
+
+    This method is equivalent to [`Bitstring128::to_be_bytes`].
+    */
+    #[inline]
+    pub const fn to_ibm_dfp_bytes(&self) -> [u8; 16] {
+        self.to_be_bytes()
+    }
+
     /**
     Create a decimal with the finite value zero.
     */
@@ -280,7 +322,30 @@ impl Bitstring128 {
 
 classify!(Bitstring128);
 
+cmp!(Bitstring128 => Bitstring32);
+cmp!(Bitstring128 => Bitstring64);
+cmp!(Bitstring128 => Bitstring128);
+cmp!(Bitstring128 => Bitstring);
+#[cfg(feature = "arbitrary-precision")]
+cmp!(Bitstring128 => BigBitstring);
+
+total_cmp!(Bitstring128);
+hash!(Bitstring128);
+add!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+mul!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+div!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+func!(crate::math::ArrayDigitBuf<320> => Bitstring128);
+quantize!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+reduce!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+integral!(crate::math::ArrayDigitBuf<144> => Bitstring128);
+parts!(Bitstring128);
+digits!(Bitstring128 => i32);
+nan!(Bitstring128);
+bid!(Bitstring128: 16);
+ops!(Bitstring128);
+
 try_s2d!(ArrayTextBuf::<128> => Bitstring128);
+round_s2d!(crate::math::ArrayDigitBuf<144> => Bitstring128);
 d2s!(Bitstring128);
 
 f2d!(f32 => from_f32 => Bitstring128);
@@ -293,25 +358,28 @@ i2d!(i8 => from_i8 => Bitstring128);
 i2d!(i16 => from_i16 => Bitstring128);
 i2d!(i32 => from_i32 => Bitstring128);
 i2d!(i64 => from_i64 => Bitstring128);
-try_i2d!(i128 => from_i128 => Bitstring128);
+try_i2d!(i128 => from_i128, from_i128_with => Bitstring128);
 
-try_d2i!(Bitstring128 => to_i8 => i8);
-try_d2i!(Bitstring128 => to_i16 => i16);
-try_d2i!(Bitstring128 => to_i32 => i32);
-try_d2i!(Bitstring128 => to_i64 => i64);
-try_d2i!(Bitstring128 => to_i128 => i128);
+try_d2i!(Bitstring128 => to_i8, to_i8_rounded, to_i8_saturating, to_i8_truncated => i8);
+try_d2i!(Bitstring128 => to_i16, to_i16_rounded, to_i16_saturating, to_i16_truncated => i16);
+try_d2i!(Bitstring128 => to_i32, to_i32_rounded, to_i32_saturating, to_i32_truncated => i32);
+try_d2i!(Bitstring128 => to_i64, to_i64_rounded, to_i64_saturating, to_i64_truncated => i64);
+try_d2i!(Bitstring128 => to_i128, to_i128_rounded, to_i128_saturating, to_i128_truncated => i128);
 
 i2d!(u8 => from_u8 => Bitstring128);
 i2d!(u16 => from_u16 => Bitstring128);
 i2d!(u32 => from_u32 => Bitstring128);
 i2d!(u64 => from_u64 => Bitstring128);
-try_i2d!(u128 => from_u128 => Bitstring128);
+try_i2d!(u128 => from_u128, from_u128_with => Bitstring128);
+
+try_d2i!(Bitstring128 => to_u8, to_u8_rounded, to_u8_saturating, to_u8_truncated => u8);
+try_d2i!(Bitstring128 => to_u16, to_u16_rounded, to_u16_saturating, to_u16_truncated => u16);
+try_d2i!(Bitstring128 => to_u32, to_u32_rounded, to_u32_saturating, to_u32_truncated => u32);
+try_d2i!(Bitstring128 => to_u64, to_u64_rounded, to_u64_saturating, to_u64_truncated => u64);
+try_d2i!(Bitstring128 => to_u128, to_u128_rounded, to_u128_saturating, to_u128_truncated => u128);
 
-try_d2i!(Bitstring128 => to_u8 => u8);
-try_d2i!(Bitstring128 => to_u16 => u16);
-try_d2i!(Bitstring128 => to_u32 => u32);
-try_d2i!(Bitstring128 => to_u64 => u64);
-try_d2i!(Bitstring128 => to_u128 => u128);
+scaled!(Bitstring128 => to_scaled_i64, from_scaled_i64 => i64);
+scaled!(Bitstring128 => to_scaled_i128, from_scaled_i128 => i128);
 
 #[cfg(test)]
 mod tests {
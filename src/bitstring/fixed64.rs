@@ -5,13 +5,15 @@ use crate::{
         FixedBinaryBuf,
     },
     text::ArrayTextBuf,
+    ConvertError,
+    Error,
 };
 
 /**
 A [64bit decimal number](https://en.wikipedia.org/wiki/Decimal64_floating-point_format).
 */
 #[derive(Clone, Copy)]
-pub struct Bitstring64(FixedBinaryBuf<8, i32>);
+pub struct Bitstring64(pub(crate) FixedBinaryBuf<8, i32>);
 
 /**
 Basic mathematical constants.
@@ -190,6 +192,24 @@ impl Bitstring64 {
         Self(FixedBinaryBuf::from_le_bytes(bytes))
     }
 
+    /**
+    Try create a decimal from its representation as a byte array in little endian, only accepting
+    canonical encodings.
+
+    Use this method instead of [`Bitstring64::from_le_bytes`] for bytes from untrusted sources, where
+    a non-canonical encoding could be used to produce decimals that are equal but have different
+    bytes.
+    */
+    pub fn try_from_le_bytes_canonical(bytes: [u8; 8]) -> Result<Self, Error> {
+        let decimal = Self::from_le_bytes(bytes);
+
+        if !decimal.is_canonical() {
+            Err(ConvertError::non_canonical("Bitstring64"))?;
+        }
+
+        Ok(decimal)
+    }
+
     /**
     Create a decimal from its representation as a byte array in big endian.
     */
@@ -221,6 +241,28 @@ impl Bitstring64 {
         [b[7], b[6], b[5], b[4], b[3], b[2], b[1], b[0]]
     }
 
+    /**
+    Create a decimal from its representation in IBM's decimal floating point (DFP) format.
+
+    DFP uses the same densely-packed-decimal encoding as this library, but in big endian, so this
+    method is equivalent to [`Bitstring64::from_be_bytes`]. IBM z/Architecture and POWER store
+    decimal64 values this way, as does DB2 for `DECFLOAT(16)`.
+    */
+    #[inline]
+    pub const fn from_ibm_dfp_bytes(bytes: [u8; 8]) -> Self {
+        Self::from_be_bytes(bytes)
+    }
+
+    /**
+    Return the representation of this decimal in IBM's decimal floating point (DFP) format.
+
+    This method is equivalent to [`Bitstring64::to_be_bytes`].
+    */
+    #[inline]
+    pub const fn to_ibm_dfp_bytes(&self) -> [u8; 8] {
+        self.to_be_bytes()
+    }
+
     /**
     Create a decimal with the finite value zero.
     */
@@ -264,11 +306,34 @@ impl Bitstring64 {
 
 classify!(Bitstring64);
 
+cmp!(Bitstring64 => Bitstring32);
+cmp!(Bitstring64 => Bitstring64);
+cmp!(Bitstring64 => Bitstring128);
+cmp!(Bitstring64 => Bitstring);
+#[cfg(feature = "arbitrary-precision")]
+cmp!(Bitstring64 => BigBitstring);
+
+total_cmp!(Bitstring64);
+hash!(Bitstring64);
+add!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+mul!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+div!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+func!(crate::math::ArrayDigitBuf<192> => Bitstring64);
+quantize!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+reduce!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+integral!(crate::math::ArrayDigitBuf<64> => Bitstring64);
+parts!(Bitstring64);
+digits!(Bitstring64 => i32);
+nan!(Bitstring64);
+bid!(Bitstring64: 8);
+ops!(Bitstring64);
+
 try_s2d!(ArrayTextBuf::<64> => Bitstring64);
+round_s2d!(crate::math::ArrayDigitBuf<64> => Bitstring64);
 d2s!(Bitstring64);
 
 f2d!(f32 => from_f32 => Bitstring64);
-try_f2d!(f64 => from_f64 => Bitstring64);
+try_f2d!(f64 => from_f64, from_f64_with => Bitstring64);
 
 try_d2f!(Bitstring64 => to_f32 => f32);
 try_d2f!(Bitstring64 => to_f64 => f64);
@@ -276,26 +341,29 @@ try_d2f!(Bitstring64 => to_f64 => f64);
 i2d!(i8 => from_i8 => Bitstring64);
 i2d!(i16 => from_i16 => Bitstring64);
 i2d!(i32 => from_i32 => Bitstring64);
-try_i2d!(i64 => from_i64 => Bitstring64);
-try_i2d!(i128 => from_i128 => Bitstring64);
+try_i2d!(i64 => from_i64, from_i64_with => Bitstring64);
+try_i2d!(i128 => from_i128, from_i128_with => Bitstring64);
 
-try_d2i!(Bitstring64 => to_i8 => i8);
-try_d2i!(Bitstring64 => to_i16 => i16);
-try_d2i!(Bitstring64 => to_i32 => i32);
-try_d2i!(Bitstring64 => to_i64 => i64);
-try_d2i!(Bitstring64 => to_i128 => i128);
+try_d2i!(Bitstring64 => to_i8, to_i8_rounded, to_i8_saturating, to_i8_truncated => i8);
+try_d2i!(Bitstring64 => to_i16, to_i16_rounded, to_i16_saturating, to_i16_truncated => i16);
+try_d2i!(Bitstring64 => to_i32, to_i32_rounded, to_i32_saturating, to_i32_truncated => i32);
+try_d2i!(Bitstring64 => to_i64, to_i64_rounded, to_i64_saturating, to_i64_truncated => i64);
+try_d2i!(Bitstring64 => to_i128, to_i128_rounded, to_i128_saturating, to_i128_truncated => i128);
 
 i2d!(u8 => from_u8 => Bitstring64);
 i2d!(u16 => from_u16 => Bitstring64);
 i2d!(u32 => from_u32 => Bitstring64);
-try_i2d!(u64 => from_u64 => Bitstring64);
-try_i2d!(u128 => from_u128 => Bitstring64);
-
-try_d2i!(Bitstring64 => to_u8 => u8);
-try_d2i!(Bitstring64 => to_u16 => u16);
-try_d2i!(Bitstring64 => to_u32 => u32);
-try_d2i!(Bitstring64 => to_u64 => u64);
-try_d2i!(Bitstring64 => to_u128 => u128);
+try_i2d!(u64 => from_u64, from_u64_with => Bitstring64);
+try_i2d!(u128 => from_u128, from_u128_with => Bitstring64);
+
+try_d2i!(Bitstring64 => to_u8, to_u8_rounded, to_u8_saturating, to_u8_truncated => u8);
+try_d2i!(Bitstring64 => to_u16, to_u16_rounded, to_u16_saturating, to_u16_truncated => u16);
+try_d2i!(Bitstring64 => to_u32, to_u32_rounded, to_u32_saturating, to_u32_truncated => u32);
+try_d2i!(Bitstring64 => to_u64, to_u64_rounded, to_u64_saturating, to_u64_truncated => u64);
+try_d2i!(Bitstring64 => to_u128, to_u128_rounded, to_u128_saturating, to_u128_truncated => u128);
+
+scaled!(Bitstring64 => to_scaled_i64, from_scaled_i64 => i64);
+scaled!(Bitstring64 => to_scaled_i128, from_scaled_i128 => i128);
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,18 @@
+/**
+A decimal that's compared and hashed by its numeric value.
+
+This wrapper implements `Eq` and `Hash`, so it can be used as a key in a `HashMap` or `HashSet`.
+Decimals like `1`, `1.0`, and `1.00` are equal, as are `0` and `-0`. Unlike the `PartialEq`
+implementation on the decimals themselves, all NaNs are considered equal to each other.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NumericEq<T>(pub T);
+
+/**
+A decimal that's compared and hashed by its encoding.
+
+This wrapper implements `Eq` and `Hash`, so it can be used as a key in a `HashMap` or `HashSet`.
+Decimals are only equal if their encoded bytes are identical, so `1.0` and `1.00` are different keys.
+*/
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReprEq<T>(pub T);
@@ -0,0 +1,261 @@
+/*!
+Conversions to and from MongoDB's `Decimal128`.
+
+This module is only available when the `bson` feature is enabled.
+
+BSON stores decimals using the 128-bit binary-integer-decimal (BID) encoding, so they convert to and
+from [`Bitstring128`] losslessly. [`Bitstring`] and [`BigBitstring`] can hold more digits and a wider
+exponent range than `Decimal128`, so converting them either fails if the decimal can't be encoded
+exactly, or rounds it using a [`Context`].
+
+Conversions are exact, so the quantum of finite numbers, like the trailing zero in `1.20`, is kept,
+along with the sign and payload of NaNs, and whether they're signaling.
+*/
+
+use crate::{
+    binary::FixedBinaryBuf,
+    math::Limits,
+    Bitstring,
+    Bitstring128,
+    Context,
+    ConvertError,
+    Error,
+};
+
+#[cfg(feature = "arbitrary-precision")]
+use crate::BigBitstring;
+
+impl From<Bitstring128> for ::bson::Decimal128 {
+    fn from(d: Bitstring128) -> ::bson::Decimal128 {
+        ::bson::Decimal128::from_bytes(d.to_bid_bytes())
+    }
+}
+
+impl From<::bson::Decimal128> for Bitstring128 {
+    fn from(d: ::bson::Decimal128) -> Bitstring128 {
+        Bitstring128::from_bid_bytes(d.bytes())
+    }
+}
+
+macro_rules! bson {
+    ($c:ty => $d:ident) => {
+        impl From<::bson::Decimal128> for $d {
+            fn from(d: ::bson::Decimal128) -> $d {
+                $d::try_from_le_bytes(Bitstring128::from(d).as_le_bytes())
+                    .expect("a 128bit decimal always fits")
+            }
+        }
+
+        impl TryFrom<$d> for ::bson::Decimal128 {
+            type Error = Error;
+
+            fn try_from(d: $d) -> Result<::bson::Decimal128, Self::Error> {
+                TryFrom::try_from(&d)
+            }
+        }
+
+        impl<'a> TryFrom<&'a $d> for ::bson::Decimal128 {
+            type Error = Error;
+
+            fn try_from(d: &'a $d) -> Result<::bson::Decimal128, Self::Error> {
+                let limits = Limits::from_storage_width_bits(128);
+
+                let Some(narrow) =
+                    $crate::math::decimal_narrow_exact::<_, FixedBinaryBuf<16, i32>, $c>(
+                        &d.0, &limits,
+                    )
+                else {
+                    Err(ConvertError::would_overflow("Decimal128"))?
+                };
+
+                Ok(Bitstring128(narrow).into())
+            }
+        }
+
+        impl $d {
+            /**
+            Convert this decimal into a BSON `Decimal128`, using the given context.
+
+            If the decimal can't be encoded exactly then it's rounded to 34 digits of precision
+            and the exponent range of `Decimal128`. Numbers that are too small underflow towards
+            zero and numbers that are too large overflow, however large their exponent is. NaN
+            payloads that are too long are truncated.
+
+            See [`Context`] for how the result is rounded and trapped.
+            */
+            pub fn to_bson_decimal128_with(
+                &self,
+                ctx: &mut Context,
+            ) -> Result<::bson::Decimal128, Error> {
+                let storage_width_bits = ctx.storage_width_bits(&[&Bitstring128::ZERO.0]);
+
+                ctx.calculate(storage_width_bits, |limits, mode, flags| {
+                    Bitstring128($crate::math::decimal_narrow::<_, _, $c>(
+                        &self.0, limits, mode, flags,
+                    ))
+                    .into()
+                })
+            }
+        }
+    };
+}
+
+bson!(crate::math::ArrayDigitBuf<176> => Bitstring);
+
+#[cfg(feature = "arbitrary-precision")]
+bson!(Vec<u8> => BigBitstring);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        Flags,
+        RoundingMode,
+    };
+
+    #[test]
+    fn bitstring128_roundtrip() {
+        for s in ["0", "-0.00", "1.20", "-1.05e3", "inf", "-nan(42)", "snan"] {
+            let d = Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+
+            let bson = ::bson::Decimal128::from(d);
+
+            assert_eq!(d.to_bid_bytes(), bson.bytes(), "{}", s);
+            assert_eq!(s, Bitstring128::from(bson).to_string());
+        }
+    }
+
+    #[test]
+    fn bitstring_exact() {
+        for s in [
+            "1.20",
+            "-1.05e3",
+            "9.999999999999999999999999999999999e6144",
+            "1e-6176",
+            "-inf",
+            "-snan(123)",
+        ] {
+            let d = Bitstring::try_parse_str(s).expect("failed to parse decimal");
+
+            let bson = ::bson::Decimal128::try_from(&d).expect("failed to convert decimal");
+
+            assert_eq!(s, Bitstring::from(bson).to_string());
+        }
+
+        for s in [
+            "1.2345678901234567890123456789012345",
+            "1e6145",
+            "1e-6177",
+            "nan(1234567890123456789012345678901234)",
+        ] {
+            let d = Bitstring::try_parse_str(s).expect("failed to parse decimal");
+
+            assert!(::bson::Decimal128::try_from(&d).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn bitstring_rounded() {
+        let d = Bitstring::try_parse_str("1.2345678901234567890123456789012345")
+            .expect("failed to parse decimal");
+
+        let mut ctx = Context::new();
+
+        let bson = d
+            .to_bson_decimal128_with(&mut ctx)
+            .expect("failed to convert decimal");
+
+        assert_eq!(
+            "1.234567890123456789012345678901234",
+            Bitstring128::from(bson).to_string()
+        );
+        assert!(ctx.flags().contains(Flags::INEXACT | Flags::ROUNDED));
+
+        let mut ctx = Context::new().with_traps(Flags::INEXACT);
+
+        assert!(d.to_bson_decimal128_with(&mut ctx).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn big_bitstring() {
+        let d = BigBitstring::try_parse_str("-1.20").expect("failed to parse decimal");
+
+        let bson = ::bson::Decimal128::try_from(&d).expect("failed to convert decimal");
+
+        assert_eq!("-1.20", BigBitstring::from(bson).to_string());
+
+        let d = BigBitstring::try_parse_str("1e-99999999999").expect("failed to parse decimal");
+
+        assert!(::bson::Decimal128::try_from(&d).is_err());
+
+        // Exponents far outside the range of `Decimal128` underflow or overflow
+        for (s, mode, expected, expected_flags) in [
+            (
+                "1e-99999999999",
+                RoundingMode::HalfEven,
+                "0e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED | Flags::CLAMPED,
+            ),
+            (
+                "-1e-99999999999",
+                RoundingMode::HalfEven,
+                "-0e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED | Flags::CLAMPED,
+            ),
+            (
+                "1e-99999999999",
+                RoundingMode::Ceiling,
+                "1e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "1e99999999999",
+                RoundingMode::HalfEven,
+                "inf",
+                Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "-1e99999999999",
+                RoundingMode::Down,
+                "-9.999999999999999999999999999999999e6144",
+                Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "0e99999999999",
+                RoundingMode::HalfEven,
+                "0e6111",
+                Flags::CLAMPED,
+            ),
+            (
+                "-0e-99999999999",
+                RoundingMode::HalfEven,
+                "-0e-6176",
+                Flags::CLAMPED,
+            ),
+        ] {
+            let d = BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+            let mut ctx = Context::new().with_rounding(mode);
+
+            let bson = d
+                .to_bson_decimal128_with(&mut ctx)
+                .expect("failed to convert decimal");
+
+            assert_eq!(expected, Bitstring128::from(bson).to_string(), "{}", s);
+            assert_eq!(expected_flags, ctx.flags(), "{}", s);
+        }
+
+        let d = BigBitstring::try_parse_str("1e-6177").expect("failed to parse decimal");
+
+        let mut ctx = Context::new();
+
+        let bson = d
+            .to_bson_decimal128_with(&mut ctx)
+            .expect("failed to convert decimal");
+
+        assert_eq!("0e-6176", Bitstring128::from(bson).to_string());
+        assert!(ctx.flags().contains(Flags::UNDERFLOW));
+    }
+}
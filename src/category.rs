@@ -0,0 +1,71 @@
+/*!
+The classes of decimal values.
+*/
+
+use core::num::FpCategory;
+
+/**
+The class of a decimal value.
+
+These are the ten classes of the IEEE754-2019 `class` operation. They can be converted into
+the coarser [`FpCategory`] used by binary floating points.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DecimalCategory {
+    /**
+    A signaling NaN, like `snan`.
+    */
+    SignalingNan,
+    /**
+    A quiet NaN, like `nan`.
+    */
+    QuietNan,
+    /**
+    Negative infinity.
+    */
+    NegativeInfinity,
+    /**
+    A negative number with an adjusted exponent in the normal range.
+    */
+    NegativeNormal,
+    /**
+    A negative number with an adjusted exponent below the normal range.
+    */
+    NegativeSubnormal,
+    /**
+    A negative zero.
+    */
+    NegativeZero,
+    /**
+    A positive zero.
+    */
+    PositiveZero,
+    /**
+    A positive number with an adjusted exponent below the normal range.
+    */
+    PositiveSubnormal,
+    /**
+    A positive number with an adjusted exponent in the normal range.
+    */
+    PositiveNormal,
+    /**
+    Positive infinity.
+    */
+    PositiveInfinity,
+}
+
+impl From<DecimalCategory> for FpCategory {
+    fn from(category: DecimalCategory) -> FpCategory {
+        match category {
+            DecimalCategory::SignalingNan | DecimalCategory::QuietNan => FpCategory::Nan,
+            DecimalCategory::NegativeInfinity | DecimalCategory::PositiveInfinity => {
+                FpCategory::Infinite
+            }
+            DecimalCategory::NegativeNormal | DecimalCategory::PositiveNormal => FpCategory::Normal,
+            DecimalCategory::NegativeSubnormal | DecimalCategory::PositiveSubnormal => {
+                FpCategory::Subnormal
+            }
+            DecimalCategory::NegativeZero | DecimalCategory::PositiveZero => FpCategory::Zero,
+        }
+    }
+}
@@ -0,0 +1,550 @@
+/*!
+The environment that decimal arithmetic is performed in.
+
+A context determines how results are rounded to fit their format, and records the conditions
+that were encountered while calculating them. It follows the _context_ of the
+[General Decimal Arithmetic](https://speleotrove.com/decimal/damodel.html) specification.
+*/
+
+use core::{
+    fmt,
+    ops::{
+        BitAnd,
+        BitOr,
+        BitOrAssign,
+    },
+};
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::Limits,
+    ArithmeticError,
+    Error,
+};
+
+/**
+The way to round a result that can't be represented exactly.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /**
+    Round to the nearest number, with ties going to the number with an even least significant digit.
+    */
+    #[default]
+    HalfEven,
+    /**
+    Round to the nearest number, with ties going away from zero.
+    */
+    HalfUp,
+    /**
+    Round to the nearest number, with ties going towards zero.
+    */
+    HalfDown,
+    /**
+    Round away from zero.
+    */
+    Up,
+    /**
+    Round towards zero.
+    */
+    Down,
+    /**
+    Round towards positive infinity.
+    */
+    Ceiling,
+    /**
+    Round towards negative infinity.
+    */
+    Floor,
+    /**
+    Round towards zero, unless the least significant digit would be `0` or `5`, in which case
+    round away from zero.
+    */
+    ZeroFiveUp,
+}
+
+/**
+Conditions that were encountered while calculating a result.
+
+Flags can be combined using `|`.
+*/
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Flags(u8);
+
+impl Flags {
+    /**
+    The result was rounded and some non-zero digits were lost.
+    */
+    pub const INEXACT: Flags = Flags(1 << 0);
+    /**
+    The result was rounded, but the discarded digits may all have been zero.
+    */
+    pub const ROUNDED: Flags = Flags(1 << 1);
+    /**
+    The result was too large to represent.
+    */
+    pub const OVERFLOW: Flags = Flags(1 << 2);
+    /**
+    The result was too small to represent exactly.
+    */
+    pub const UNDERFLOW: Flags = Flags(1 << 3);
+    /**
+    The operation was invalid, like adding infinities with opposite signs.
+    */
+    pub const INVALID: Flags = Flags(1 << 4);
+    /**
+    A finite number was divided by zero.
+    */
+    pub const DIVISION_BY_ZERO: Flags = Flags(1 << 5);
+    /**
+    The exponent of the result was changed to fit the format.
+    */
+    pub const CLAMPED: Flags = Flags(1 << 6);
+
+    const NAMES: [(Flags, &'static str); 7] = [
+        (Flags::INEXACT, "INEXACT"),
+        (Flags::ROUNDED, "ROUNDED"),
+        (Flags::OVERFLOW, "OVERFLOW"),
+        (Flags::UNDERFLOW, "UNDERFLOW"),
+        (Flags::INVALID, "INVALID"),
+        (Flags::DIVISION_BY_ZERO, "DIVISION_BY_ZERO"),
+        (Flags::CLAMPED, "CLAMPED"),
+    ];
+
+    /**
+    No flags.
+    */
+    pub const fn empty() -> Self {
+        Flags(0)
+    }
+
+    /**
+    Whether no flags are set.
+    */
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /**
+    Whether all of the given flags are set.
+    */
+    pub const fn contains(&self, flags: Flags) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    /**
+    Whether any of the given flags are set.
+    */
+    pub const fn intersects(&self, flags: Flags) -> bool {
+        self.0 & flags.0 != 0
+    }
+
+    /**
+    Set the given flags.
+    */
+    pub(crate) fn set(&mut self, flags: Flags) {
+        self.0 |= flags.0;
+    }
+}
+
+impl BitOr for Flags {
+    type Output = Flags;
+
+    fn bitor(self, rhs: Flags) -> Flags {
+        Flags(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Flags {
+    fn bitor_assign(&mut self, rhs: Flags) {
+        self.set(rhs);
+    }
+}
+
+impl BitAnd for Flags {
+    type Output = Flags;
+
+    fn bitand(self, rhs: Flags) -> Flags {
+        Flags(self.0 & rhs.0)
+    }
+}
+
+impl fmt::Debug for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Flags(")?;
+        fmt::Display::fmt(self, f)?;
+        f.write_str(")")
+    }
+}
+
+impl fmt::Display for Flags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+
+        for (flag, name) in Flags::NAMES {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+
+        if first {
+            f.write_str("empty")?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+The precision, rounding, and exponent range used for arithmetic, along with the conditions
+encountered while calculating results.
+
+A context can narrow the precision and exponent range of results, but can't widen them beyond
+what their format can encode. If a limit isn't set then the limit of the format is used.
+
+Methods that accept a context, like `add_with`, round their result to the precision and exponent
+range of the context, using its rounding mode. Any conditions encountered while calculating the
+result are added to the flags of the context. If any of them are trapped then the method returns
+an error instead of a result. If the exponent range of the context doesn't overlap the exponent
+range of the format then the method also returns an error.
+
+Flags are sticky. Once a flag is set it stays set across multiple calculations until it's
+cleared with [`Context::clear_flags`]. If a flag is also a _trap_ then any calculation that sets
+it returns an error instead of a result.
+
+```
+use decstr::{Bitstring64, Context, Flags, RoundingMode};
+
+let mut ctx = Context::new().with_rounding(RoundingMode::Floor);
+
+let a = Bitstring64::try_parse_str("2")?;
+let b = Bitstring64::try_parse_str("3")?;
+
+let quotient = a.div_with(&b, &mut ctx)?;
+
+assert_eq!("0.6666666666666666", quotient.to_string());
+assert!(ctx.flags().contains(Flags::INEXACT));
+# Ok::<(), decstr::Error>(())
+```
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Context {
+    precision: Option<usize>,
+    rounding: RoundingMode,
+    emin: Option<i32>,
+    emax: Option<i32>,
+    flags: Flags,
+    traps: Flags,
+}
+
+impl Context {
+    /**
+    Create a context that rounds half-even and uses the limits of each format.
+    */
+    pub const fn new() -> Self {
+        Context {
+            precision: None,
+            rounding: RoundingMode::HalfEven,
+            emin: None,
+            emax: None,
+            flags: Flags::empty(),
+            traps: Flags::empty(),
+        }
+    }
+
+    /**
+    Set the maximum number of significant digits in results.
+
+    The precision can't be less than `1`. A precision wider than the format of a result is
+    narrowed to fit it.
+    */
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision.max(1));
+        self
+    }
+
+    /**
+    Set the way results are rounded.
+    */
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /**
+    Set the minimum adjusted exponent of normal numbers.
+
+    Numbers with a smaller adjusted exponent are subnormal. If the minimum exponent is greater
+    than the maximum exponent, either of the context or of the format of a result, then
+    calculations using this context return an error.
+    */
+    pub fn with_emin(mut self, emin: i32) -> Self {
+        self.emin = Some(emin);
+        self
+    }
+
+    /**
+    Set the maximum adjusted exponent of results.
+
+    Results with a larger adjusted exponent overflow. If the maximum exponent is less than the
+    minimum exponent, either of the context or of the format of a result, then calculations
+    using this context return an error.
+    */
+    pub fn with_emax(mut self, emax: i32) -> Self {
+        self.emax = Some(emax);
+        self
+    }
+
+    /**
+    Set the flags that should return errors when they're encountered.
+    */
+    pub fn with_traps(mut self, traps: Flags) -> Self {
+        self.traps = traps;
+        self
+    }
+
+    /**
+    The maximum number of significant digits in results.
+    */
+    pub fn precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /**
+    The way results are rounded.
+    */
+    pub fn rounding(&self) -> RoundingMode {
+        self.rounding
+    }
+
+    /**
+    The minimum adjusted exponent of normal numbers.
+    */
+    pub fn emin(&self) -> Option<i32> {
+        self.emin
+    }
+
+    /**
+    The maximum adjusted exponent of results.
+    */
+    pub fn emax(&self) -> Option<i32> {
+        self.emax
+    }
+
+    /**
+    The flags that return errors when they're encountered.
+    */
+    pub fn traps(&self) -> Flags {
+        self.traps
+    }
+
+    /**
+    The flags that have been set by calculations using this context.
+    */
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /**
+    Clear any flags that have been set.
+    */
+    pub fn clear_flags(&mut self) {
+        self.flags = Flags::empty();
+    }
+
+    /**
+    Get the bit-width to calculate a result in.
+
+    Fixed-width decimals always use their own width. Decimals that can grow use the width of
+    their widest operand, or the width needed for the precision of this context.
+    */
+    pub(crate) fn storage_width_bits<D: BinaryBuf>(&self, operands: &[&D]) -> usize {
+        let operands = operands
+            .iter()
+            .map(|operand| operand.max_storage_width_bits())
+            .max()
+            .unwrap_or(0);
+
+        let precision = self
+            .precision
+            .and_then(|precision| D::try_with_at_least_precision(precision, None).ok())
+            .map(|buf| buf.max_storage_width_bits())
+            .unwrap_or(0);
+
+        operands.max(precision)
+    }
+
+    /**
+    Get the limits of results in a format with the given bit-width.
+    */
+    pub(crate) fn limits<E: BinaryExponent + BinaryExponentMath>(
+        &self,
+        storage_width_bits: usize,
+    ) -> Limits<E> {
+        let mut limits = Limits::<E>::from_storage_width_bits(storage_width_bits);
+
+        if let Some(precision) = self.precision {
+            limits.precision = limits.precision.min(precision);
+        }
+
+        if let Some(emax) = self.emax {
+            limits.emax = limits.emax.min(E::from_i32(emax));
+        }
+
+        if let Some(emin) = self.emin {
+            limits.emin = limits.emin.max(E::from_i32(emin));
+        }
+
+        limits
+    }
+
+    /**
+    Calculate a result in this context.
+
+    Any flags set by the calculation are added to the context. If any of them are traps then
+    an error is returned instead of the result. If the exponent range of the context doesn't
+    overlap the exponent range of the format then an error is returned without calculating
+    anything.
+    */
+    pub(crate) fn calculate<T, E: BinaryExponent + BinaryExponentMath>(
+        &mut self,
+        storage_width_bits: usize,
+        f: impl FnOnce(&Limits<E>, RoundingMode, &mut Flags) -> T,
+    ) -> Result<T, Error> {
+        let limits = self.limits::<E>(storage_width_bits);
+
+        if limits.emin > limits.emax {
+            Err(ArithmeticError::invalid_context(
+                "its minimum exponent is greater than its maximum exponent",
+            ))?
+        }
+
+        let mut flags = Flags::empty();
+        let result = f(&limits, self.rounding, &mut flags);
+
+        self.flags.set(flags);
+
+        if flags.intersects(self.traps) {
+            Err(ArithmeticError::trapped(flags & self.traps))?
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_fmt() {
+        assert_eq!("Flags(empty)", format!("{:?}", Flags::empty()));
+        assert_eq!(
+            "Flags(INEXACT | ROUNDED)",
+            format!("{:?}", Flags::ROUNDED | Flags::INEXACT)
+        );
+    }
+
+    #[test]
+    fn flags_ops() {
+        let flags = Flags::INEXACT | Flags::ROUNDED;
+
+        assert!(flags.contains(Flags::INEXACT));
+        assert!(!flags.contains(Flags::INEXACT | Flags::OVERFLOW));
+        assert!(flags.intersects(Flags::INEXACT | Flags::OVERFLOW));
+        assert!(!flags.intersects(Flags::OVERFLOW));
+        assert_eq!(Flags::INEXACT, flags & Flags::INEXACT);
+        assert!(Flags::empty().is_empty());
+    }
+
+    #[test]
+    fn limits() {
+        let limits: Limits<i32> = Context::new().limits(64);
+
+        assert_eq!(16, limits.precision);
+        assert_eq!(384, limits.emax);
+        assert_eq!(-383, limits.emin);
+
+        let limits: Limits<i32> = Context::new()
+            .with_precision(9)
+            .with_emax(99)
+            .with_emin(-99)
+            .limits(64);
+
+        assert_eq!(9, limits.precision);
+        assert_eq!(99, limits.emax);
+        assert_eq!(-99, limits.emin);
+
+        // Contexts can't widen the limits of a format
+        let limits: Limits<i32> = Context::new()
+            .with_precision(50)
+            .with_emax(999)
+            .with_emin(-999)
+            .limits(64);
+
+        assert_eq!(16, limits.precision);
+        assert_eq!(384, limits.emax);
+        assert_eq!(-383, limits.emin);
+    }
+
+    #[test]
+    fn calculate_traps() {
+        let mut ctx = Context::new().with_traps(Flags::DIVISION_BY_ZERO);
+
+        let result = ctx.calculate::<_, i32>(64, |_, _, flags| {
+            flags.set(Flags::INEXACT);
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(Flags::INEXACT, ctx.flags());
+
+        let result = ctx.calculate::<_, i32>(64, |_, _, flags| {
+            flags.set(Flags::DIVISION_BY_ZERO);
+        });
+
+        assert!(result.is_err());
+        assert_eq!(Flags::INEXACT | Flags::DIVISION_BY_ZERO, ctx.flags());
+
+        ctx.clear_flags();
+        assert!(ctx.flags().is_empty());
+    }
+
+    #[test]
+    fn calculate_invalid() {
+        for ctx in [
+            Context::new().with_emin(10).with_emax(-10),
+            Context::new().with_emin(500),
+            Context::new().with_emax(-500),
+        ] {
+            let mut ctx = ctx.with_traps(Flags::empty());
+
+            let result = ctx.calculate::<_, i32>(64, |_, _, flags| {
+                flags.set(Flags::INEXACT);
+            });
+
+            assert!(result.is_err(), "{:?}", ctx);
+            assert!(ctx.flags().is_empty(), "{:?}", ctx);
+        }
+    }
+
+    #[test]
+    fn storage_width_bits_large_precision() {
+        let operand = crate::binary::FixedBinaryBuf::<8, i32>::ZERO;
+
+        assert_eq!(
+            64,
+            Context::new()
+                .with_precision(usize::MAX)
+                .storage_width_bits(&[&operand])
+        );
+    }
+}
@@ -18,6 +18,15 @@ use crate::{
         BinaryBuf,
         BinaryExponent,
     },
+    math::{
+        pack,
+        round_finite,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+    },
     num::Integer,
     text::{
         ParsedDecimal,
@@ -38,12 +47,16 @@ use core::{
 
 mod from_binary_float;
 mod from_int;
+mod from_parts;
 mod from_str;
+mod pg_numeric;
 
 pub(crate) use self::{
     from_binary_float::*,
     from_int::*,
+    from_parts::*,
     from_str::*,
+    pg_numeric::*,
 };
 
 /**
@@ -203,6 +216,103 @@ pub(crate) fn decimal_from_parsed<D: BinaryBuf, T: TextBuf>(
     }
 }
 
+/**
+Convert a decimal parsed from text into its binary form, rounding it to fit the given limits.
+
+Finite numbers with more digits than the precision are rounded, and numbers with exponents outside
+of the range of the decimal overflow or underflow, instead of returning an error.
+*/
+pub(crate) fn decimal_from_parsed_rounded<D: BinaryBuf, C: DigitBuf, T: TextBuf>(
+    parsed: ParsedDecimal<T>,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Result<D, OverflowError> {
+    match parsed {
+        // ±1.234e±5
+        ParsedDecimal::Finite(ParsedFinite {
+            finite_buf,
+            finite_significand:
+                ParsedSignificand {
+                    significand_is_negative,
+                    significand_range,
+                    decimal_point,
+                },
+            finite_exponent,
+        }) => {
+            let buf = finite_buf.get_ascii();
+
+            let (integer_digits, fractional_digits) = match decimal_point {
+                Some(ParsedDecimalPoint {
+                    decimal_point_range,
+                }) => (
+                    &buf[significand_range.start..decimal_point_range.start],
+                    &buf[decimal_point_range.end..significand_range.end],
+                ),
+                None => (&buf[significand_range], &[][..]),
+            };
+
+            // Only a limited number of significant digits are kept
+            // Any digits after the first `precision + 1` only affect rounding by whether
+            // or not they're all zero, so they're replaced by a single sticky digit
+            let max_digits = limits.precision + 1;
+
+            let significant_digits = integer_digits
+                .iter()
+                .chain(fractional_digits)
+                .copied()
+                .skip_while(|digit| *digit == b'0');
+
+            let len = significant_digits.clone().count();
+            let kept = len.min(max_digits);
+            let is_sticky = significant_digits
+                .clone()
+                .skip(kept)
+                .any(|digit| digit != b'0');
+
+            let coefficient = C::from_ascii(
+                significant_digits
+                    .take(kept)
+                    .chain(is_sticky.then_some(b'1')),
+            );
+
+            let exponent = match finite_exponent {
+                Some(exponent) => match D::try_exponent_from_ascii(
+                    exponent.exponent_is_negative,
+                    buf[exponent.exponent_range].iter().copied(),
+                ) {
+                    Ok(exponent) => exponent,
+                    // If the exponent doesn't fit then the number is either far too large
+                    // or far too small, so pick an exponent that overflows or underflows
+                    Err(_) if exponent.exponent_is_negative => limits.etiny().lower(max_digits + 1),
+                    Err(_) => limits.emax.raise(1),
+                },
+                None => D::default_exponent(),
+            };
+
+            let exponent = exponent.lower(fractional_digits.len()).raise(len - kept);
+            let exponent = if is_sticky {
+                exponent.lower(1)
+            } else {
+                exponent
+            };
+
+            Ok(pack(round_finite(
+                Finite {
+                    is_negative: significand_is_negative,
+                    coefficient,
+                    exponent,
+                },
+                limits,
+                mode,
+                flags,
+            )))
+        }
+        // Infinities and NaNs are never rounded
+        parsed => decimal_from_parsed(parsed),
+    }
+}
+
 /**
 Convert a decimal in its binary form into text.
 */
@@ -553,12 +663,14 @@ fn write_all_as_scientific(
     mut out: impl fmt::Write,
 ) -> Result<(), fmt::Error> {
     // Write the first declet along with a decimal point
+    let mut written_decimal_point = false;
+
     if let LeadingZeroes {
         partial_declet: Some((declet, idx)),
         ..
     } = leading_zeroes
     {
-        write_decimal_digits(&declet[idx..], 1, written, &mut out)?;
+        written_decimal_point = write_decimal_digits(&declet[idx..], 1, written, &mut out)?;
     } else if let Some(declet) = declets.next() {
         write_content(
             str::from_utf8(&[declet[0], b'.', declet[1], declet[2]]).map_err(|_| fmt::Error)?,
@@ -566,11 +678,18 @@ fn write_all_as_scientific(
             written,
             &mut out,
         )?;
+
+        written_decimal_point = true;
     }
 
     // Write the remaining digits
+    // If the first declet only had a single digit then the decimal point goes before them
     for declet in declets {
-        write_declet(declet, written, &mut out)?;
+        if !written_decimal_point {
+            written_decimal_point = write_decimal_digits(&declet, 1, written, &mut out)?;
+        } else {
+            write_declet(declet, written, &mut out)?;
+        }
     }
 
     // If no digits were written, then write a zero
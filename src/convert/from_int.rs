@@ -11,10 +11,14 @@ use crate::{
     binary::{
         decode_combination_finite,
         decode_significand_trailing_declets,
+        is_finite,
         is_sign_negative,
         BinaryBuf,
     },
-    convert::decimal_from_parsed,
+    convert::{
+        decimal_from_parsed,
+        decimal_from_parts,
+    },
     num::Integer,
     text::{
         FiniteParser,
@@ -25,46 +29,60 @@ use crate::{
 };
 
 pub(crate) fn decimal_to_int<D: BinaryBuf, I: Integer>(decimal: &D) -> Result<I, ConvertError> {
+    decimal_to_scaled_int(decimal, 0)
+}
+
+/**
+Convert a decimal into an integer after multiplying it by `10^scale`.
+
+With a scale of `4`, the decimal `12.3456` converts to the integer `123456`.
+*/
+#[allow(clippy::manual_repeat_n)]
+pub(crate) fn decimal_to_scaled_int<D: BinaryBuf, I: Integer>(
+    decimal: &D,
+    scale: i32,
+) -> Result<I, ConvertError> {
+    if !is_finite(decimal) {
+        return Err(ConvertError::non_finite(type_name::<I>()));
+    }
+
     let (exp, msd) = decode_combination_finite(decimal);
 
-    match exp.to_i32() {
-        // ±123
-        Some(0) => {
-            let trailing_significand = decode_significand_trailing_declets(decimal);
+    let digits = || {
+        Some(msd.get_ascii())
+            .into_iter()
+            .chain(decode_significand_trailing_declets(decimal).flatten())
+    };
 
-            let digits = Some(msd.get_ascii())
-                .into_iter()
-                .chain(trailing_significand.flatten());
+    // Zeros convert to zero regardless of their exponent
+    if digits().all(|d| d == b'0') {
+        return I::try_from_ascii(is_sign_negative(decimal), iter::empty())
+            .ok_or_else(|| ConvertError::would_overflow(type_name::<I>()));
+    }
 
-            I::try_from_ascii(is_sign_negative(decimal), digits)
-                .ok_or_else(|| ConvertError::would_overflow(type_name::<I>()))
-        }
+    match exp.to_i32().and_then(|exp| exp.checked_add(scale)) {
+        // ±123
+        Some(0) => I::try_from_ascii(is_sign_negative(decimal), digits())
+            .ok_or_else(|| ConvertError::would_overflow(type_name::<I>())),
         // ±123e1
         Some(exponent) if exponent > 0 => {
-            let trailing_significand = decode_significand_trailing_declets(decimal);
-
-            let digits = Some(msd.get_ascii())
-                .into_iter()
-                .chain(trailing_significand.flatten())
-                .chain(iter::repeat(b'0').take(exponent as usize));
+            let digits = digits().chain(iter::repeat(b'0').take(exponent as usize));
 
             I::try_from_ascii(is_sign_negative(decimal), digits)
                 .ok_or_else(|| ConvertError::would_overflow(type_name::<I>()))
         }
         // ±1230e-1
-        Some(exponent) if (exponent.unsigned_abs() as usize) < decimal.precision_digits() => {
-            let trailing_significand = decode_significand_trailing_declets(decimal);
-
-            let mut digits = Some(msd.get_ascii())
-                .into_iter()
-                .chain(trailing_significand.flatten());
+        Some(exponent) => {
+            let mut digits = digits();
 
             // First, try get the integer part of the decimal
             let i = I::try_from_ascii(
                 is_sign_negative(decimal),
-                digits
-                    .by_ref()
-                    .take(decimal.precision_digits() - (exponent.unsigned_abs() as usize)),
+                digits.by_ref().take(
+                    decimal
+                        .precision_digits()
+                        .saturating_sub(exponent.unsigned_abs() as usize),
+                ),
             )
             .ok_or_else(|| ConvertError::would_overflow(type_name::<I>()))?;
 
@@ -78,7 +96,7 @@ pub(crate) fn decimal_to_int<D: BinaryBuf, I: Integer>(decimal: &D) -> Result<I,
             }
         }
         // If the exponent is very large or small then it can't be represented as an integer
-        _ => Err(ConvertError::would_overflow(type_name::<I>())),
+        None => Err(ConvertError::would_overflow(type_name::<I>())),
     }
 }
 
@@ -90,3 +108,27 @@ pub(crate) fn decimal_from_int<D: BinaryBuf, I: itoa::Integer>(int: I) -> Result
         FiniteParser::parse_str(int).expect("primitive integers can always be parsed"),
     ))
 }
+
+/**
+Convert an integer into a decimal after dividing it by `10^scale`.
+
+With a scale of `4`, the integer `123456` converts to the decimal `12.3456`. The exponent of the
+decimal is always `-scale`, so trailing zeros are kept. If the integer has more digits than the
+precision of the decimal, or the exponent is out of range, then this function returns `None`.
+*/
+pub(crate) fn decimal_from_scaled_int<D: BinaryBuf, I: itoa::Integer>(
+    int: I,
+    scale: i32,
+) -> Option<D> {
+    let mut buf = itoa::Buffer::new();
+    let int = buf.format(int);
+
+    let (is_negative, digits) = match int.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, int),
+    };
+
+    let exponent = D::Exponent::from_i32(scale.checked_neg()?);
+
+    decimal_from_parts(is_negative, digits.as_bytes(), exponent).ok()
+}
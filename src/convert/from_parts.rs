@@ -0,0 +1,310 @@
+/*!
+Decompose decimals into their sign, coefficient, and exponent, and build them back up again.
+
+NaNs don't have a coefficient or exponent, but they can carry an integer payload in the trailing
+digits of their significand.
+
+The digits of the coefficient are read directly from its declets, so none of these conversions
+need to format the decimal as text.
+*/
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        decode_significand_trailing_declets,
+        encode_combination_finite,
+        encode_combination_nan,
+        encode_significand_trailing_digits,
+        is_finite,
+        is_nan,
+        is_sign_negative,
+        is_signaling_nan,
+        BinaryBuf,
+    },
+    num::Integer,
+    OverflowError,
+};
+
+/**
+Get the sign, coefficient, and exponent of a finite decimal.
+
+The value of the decimal is `coefficient * 10^exponent`. This function returns `None` if the
+decimal is infinite or NaN, or if its coefficient doesn't fit in `C`.
+*/
+pub(crate) fn decimal_to_parts<D: BinaryBuf, C: Integer>(
+    decimal: &D,
+) -> Option<(bool, C, D::Exponent)> {
+    if !is_finite(decimal) {
+        return None;
+    }
+
+    let (exponent, msd) = decode_combination_finite(decimal);
+
+    let digits = Some(msd.get_ascii())
+        .into_iter()
+        .chain(decode_significand_trailing_declets(decimal).flatten());
+
+    let coefficient = C::try_from_ascii(false, digits)?;
+
+    Some((is_sign_negative(decimal), coefficient, exponent))
+}
+
+/**
+Get the exponent of a finite decimal.
+
+This function returns `None` if the decimal is infinite or NaN.
+*/
+pub(crate) fn decimal_exponent<D: BinaryBuf>(decimal: &D) -> Option<D::Exponent> {
+    if !is_finite(decimal) {
+        return None;
+    }
+
+    let (exponent, _) = decode_combination_finite(decimal);
+
+    Some(exponent)
+}
+
+/**
+Get the digits of the coefficient of a finite decimal, from most to least significant.
+
+Leading zeros aren't included, except for a zero coefficient, which has the single digit `0`.
+Infinities and NaNs don't have any digits.
+*/
+pub(crate) fn decimal_digits<D: BinaryBuf>(decimal: &D) -> impl Iterator<Item = u8> + '_ {
+    is_finite(decimal)
+        .then(|| {
+            let (_, msd) = decode_combination_finite(decimal);
+
+            let mut digits = Some(msd.get_ascii())
+                .into_iter()
+                .chain(decode_significand_trailing_declets(decimal).flatten())
+                .skip_while(|d| *d == b'0')
+                .map(|d| d - b'0')
+                .peekable();
+
+            // If every digit is a leading zero then the coefficient is zero
+            let zero = digits.peek().is_none().then_some(0);
+
+            zero.into_iter().chain(digits)
+        })
+        .into_iter()
+        .flatten()
+}
+
+/**
+Encode a finite decimal from its sign, the ASCII digits of its coefficient, and its exponent.
+
+The decimal is encoded exactly, so this function returns an error if the coefficient has more
+digits than the precision of the decimal, or if the exponent is out of range.
+*/
+pub(crate) fn decimal_from_parts<D: BinaryBuf>(
+    is_negative: bool,
+    coefficient: &[u8],
+    exponent: D::Exponent,
+) -> Result<D, OverflowError> {
+    let mut buf = D::try_with_at_least_precision(coefficient.len(), Some(&exponent))?;
+
+    let msd = encode_significand_trailing_digits(&mut buf, [coefficient]);
+
+    encode_combination_finite(&mut buf, is_negative, exponent, msd);
+
+    Ok(buf)
+}
+
+/**
+Get the payload of a NaN.
+
+This function returns `None` if the decimal isn't a NaN, or if its payload doesn't fit in `C`.
+*/
+pub(crate) fn decimal_nan_payload<D: BinaryBuf, C: Integer>(decimal: &D) -> Option<C> {
+    if !is_nan(decimal) {
+        return None;
+    }
+
+    C::try_from_ascii(
+        false,
+        decode_significand_trailing_declets(decimal).flatten(),
+    )
+}
+
+/**
+Encode a positive NaN with the ASCII digits of its payload.
+
+This function returns an error if the payload has as many digits as the precision of the decimal.
+*/
+pub(crate) fn decimal_nan_with_payload<D: BinaryBuf>(
+    payload: &[u8],
+    is_signaling: bool,
+) -> Result<D, OverflowError> {
+    // Leading zeros don't need to be encoded
+    let payload = match payload.iter().position(|d| *d != b'0') {
+        Some(start) => &payload[start..],
+        None => &[],
+    };
+
+    let mut buf = if payload.is_empty() {
+        D::try_with_at_least_storage_width_bytes(4)
+            .expect("a NaN with no payload will always fit in the minimal sized buffer")
+    } else {
+        let mut buf = D::try_with_at_least_precision(payload.len() + 1, None)?;
+
+        encode_significand_trailing_digits(&mut buf, [payload]);
+
+        buf
+    };
+
+    encode_combination_nan(&mut buf, false, is_signaling);
+
+    Ok(buf)
+}
+
+/**
+Convert a signaling NaN into a quiet one, keeping its sign and payload.
+
+Decimals that aren't signaling NaNs are returned unchanged.
+*/
+pub(crate) fn decimal_quieten<D: BinaryBuf + Clone>(decimal: &D) -> D {
+    let mut buf = decimal.clone();
+
+    if is_signaling_nan(decimal) {
+        encode_combination_nan(&mut buf, is_sign_negative(decimal), false);
+    }
+
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+    };
+
+    use crate::binary::FixedBinaryBuf;
+
+    #[test]
+    fn to_parts() {
+        for (d, expected) in [
+            ("0", (false, 0, 0)),
+            ("-0.00", (true, 0, -2)),
+            ("1.23", (false, 123, -2)),
+            ("-1.2300e5", (true, 12300, 1)),
+            ("9999999e90", (false, 9999999, 90)),
+            ("1e-101", (false, 1, -101)),
+        ] {
+            assert_eq!(
+                Some(expected),
+                decimal_to_parts::<_, u32>(&parse::<4>(d)),
+                "{}",
+                d
+            );
+        }
+
+        assert_eq!(None, decimal_to_parts::<_, u32>(&parse::<4>("inf")));
+        assert_eq!(None, decimal_to_parts::<_, u32>(&parse::<4>("nan(1)")));
+        assert_eq!(None, decimal_to_parts::<_, u8>(&parse::<4>("1234")));
+    }
+
+    #[test]
+    fn digits() {
+        for (d, expected) in [
+            ("0", &[0][..]),
+            ("-0.000", &[0]),
+            ("1.20", &[1, 2, 0]),
+            ("0.0012", &[1, 2]),
+            ("9999999e90", &[9, 9, 9, 9, 9, 9, 9]),
+            ("inf", &[]),
+            ("nan(12)", &[]),
+        ] {
+            assert_eq!(
+                expected,
+                decimal_digits(&parse::<4>(d)).collect::<Vec<_>>(),
+                "{}",
+                d
+            );
+        }
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(Some(-2), decimal_exponent(&parse::<4>("1.20")));
+        assert_eq!(Some(90), decimal_exponent(&parse::<4>("-0e90")));
+        assert_eq!(None, decimal_exponent(&parse::<4>("-inf")));
+        assert_eq!(None, decimal_exponent(&parse::<4>("snan")));
+    }
+
+    #[test]
+    fn nan_payload() {
+        assert_eq!(Some(0), decimal_nan_payload::<_, u32>(&parse::<4>("nan")));
+        assert_eq!(
+            Some(123),
+            decimal_nan_payload::<_, u32>(&parse::<4>("-snan(123)"))
+        );
+        assert_eq!(None, decimal_nan_payload::<_, u32>(&parse::<4>("123")));
+        assert_eq!(None, decimal_nan_payload::<_, u32>(&parse::<4>("inf")));
+        assert_eq!(
+            None,
+            decimal_nan_payload::<_, u8>(&parse::<4>("nan(999999)"))
+        );
+    }
+
+    #[test]
+    fn nan_with_payload() {
+        for (payload, is_signaling, expected) in [
+            ("0", false, "nan"),
+            ("000", true, "snan"),
+            ("0123", false, "nan(123)"),
+            ("999999", true, "snan(999999)"),
+        ] {
+            let decimal = decimal_nan_with_payload::<FixedBinaryBuf<4, i32>>(
+                payload.as_bytes(),
+                is_signaling,
+            )
+            .expect("failed to encode decimal");
+
+            assert_eq!(expected, to_string(&decimal));
+        }
+
+        assert!(decimal_nan_with_payload::<FixedBinaryBuf<4, i32>>(b"1234567", false).is_err());
+    }
+
+    #[test]
+    fn quieten() {
+        for (d, expected) in [
+            ("snan", "nan"),
+            ("-snan(12)", "-nan(12)"),
+            ("nan(12)", "nan(12)"),
+            ("-1.20", "-1.20"),
+            ("-inf", "-inf"),
+        ] {
+            assert_eq!(expected, to_string(&decimal_quieten(&parse::<4>(d))));
+        }
+    }
+
+    #[test]
+    fn from_parts() {
+        for (d, (is_negative, coefficient, exponent)) in [
+            ("0", (false, "0", 0)),
+            ("-0.00", (true, "0", -2)),
+            ("1.23", (false, "123", -2)),
+            ("-1.2300e5", (true, "12300", 1)),
+            ("9.999999e96", (false, "9999999", 90)),
+            ("1e-101", (false, "1", -101)),
+        ] {
+            let decimal = decimal_from_parts::<FixedBinaryBuf<4, i32>>(
+                is_negative,
+                coefficient.as_bytes(),
+                exponent,
+            )
+            .expect("failed to encode decimal");
+
+            assert_eq!(d, to_string(&decimal));
+        }
+
+        assert!(decimal_from_parts::<FixedBinaryBuf<4, i32>>(false, b"12345678", 0).is_err());
+        assert!(decimal_from_parts::<FixedBinaryBuf<4, i32>>(false, b"1", 91).is_err());
+        assert!(decimal_from_parts::<FixedBinaryBuf<4, i32>>(false, b"1", -102).is_err());
+    }
+}
@@ -6,7 +6,16 @@ use core::fmt;
 
 use crate::{
     binary::BinaryBuf,
-    convert::decimal_from_parsed,
+    convert::{
+        decimal_from_parsed,
+        decimal_from_parsed_rounded,
+    },
+    math::{
+        DigitBuf,
+        Flags,
+        Limits,
+        RoundingMode,
+    },
     text::{
         DecimalParser,
         TextBuf,
@@ -22,6 +31,23 @@ pub(crate) fn decimal_from_str<D: BinaryBuf>(f: &str) -> Result<D, Error> {
     Ok(decimal_from_parsed(DecimalParser::parse_str(f)?)?)
 }
 
+/**
+Parse and encode a decimal from its text representation, rounding it to fit the given limits.
+*/
+pub(crate) fn decimal_from_str_rounded<D: BinaryBuf, C: DigitBuf>(
+    f: &str,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Result<D, Error> {
+    Ok(decimal_from_parsed_rounded::<D, C, _>(
+        DecimalParser::parse_str(f)?,
+        limits,
+        mode,
+        flags,
+    )?)
+}
+
 /**
 Parse and encode a decimal from a formattable value.
 */
@@ -0,0 +1,299 @@
+/*!
+Conversions between decimals and the binary format of Postgres' `NUMERIC`.
+
+In its binary protocol, Postgres sends a `NUMERIC` as a header of four big-endian 16bit integers,
+followed by an array of base-10000 digits:
+
+- `ndigits`: the number of base-10000 digits that follow the header.
+- `weight`: the power of 10000 of the first digit.
+- `sign`: `0x0000` if the number is positive, `0x4000` if it's negative, `0xc000` if it's NaN,
+  and `0xd000` or `0xf000` if it's positive or negative infinity. Infinities need Postgres 14+.
+- `dscale`: the number of decimal digits after the decimal point.
+
+Leading and trailing zero digits aren't sent, so `1.20` is sent as the digits `[1, 2000]` with a
+`weight` of `0` and a `dscale` of `2`.
+
+`NUMERIC` can't represent everything a decimal can. It doesn't have negative zero or NaN payloads,
+and its `dscale` can't be negative, so the quantum of `1.2e3` is lost and it's sent as `1200`.
+*/
+
+use crate::{
+    binary::{
+        encode_combination_finite,
+        encode_significand_trailing_digits,
+        BinaryBuf,
+    },
+    math::{
+        pack,
+        unpack,
+        DigitBuf,
+        Finite,
+        Nan,
+        Unpacked,
+    },
+    num::Integer,
+    ConvertError,
+    Error,
+};
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xc000;
+const NUMERIC_PINF: u16 = 0xd000;
+const NUMERIC_NINF: u16 = 0xf000;
+
+// The largest `dscale` Postgres will accept.
+const NUMERIC_DSCALE_MAX: u16 = 0x3fff;
+
+// The `dscale` Postgres sends for infinities, which it reads from the bits of their header.
+const NUMERIC_INF_DSCALE: u16 = 0x20;
+
+/**
+A decimal that's ready to be written in the binary format of a `NUMERIC`.
+*/
+pub(crate) struct PgNumeric<C> {
+    ndigits: i16,
+    weight: i16,
+    sign: u16,
+    dscale: u16,
+    coefficient: C,
+    exponent: i64,
+}
+
+impl<C: DigitBuf> PgNumeric<C> {
+    fn special(sign: u16, dscale: u16) -> Self {
+        PgNumeric {
+            ndigits: 0,
+            weight: 0,
+            sign,
+            dscale,
+            coefficient: C::default(),
+            exponent: 0,
+        }
+    }
+
+    /**
+    Get the base-10000 digit with the given weight.
+    */
+    fn digit(&self, weight: i64) -> u16 {
+        (0..4).rev().fold(0, |digit, power| {
+            let i = 4 * weight + power - self.exponent;
+
+            digit * 10
+                + if i >= 0 {
+                    self.coefficient.digit(i as usize) as u16
+                } else {
+                    0
+                }
+        })
+    }
+
+    /**
+    Get the bytes of the header, followed by the bytes of each digit.
+    */
+    pub(crate) fn into_bytes(self) -> impl Iterator<Item = u8> {
+        let mut header = [0; 8];
+
+        header[0..2].copy_from_slice(&self.ndigits.to_be_bytes());
+        header[2..4].copy_from_slice(&self.weight.to_be_bytes());
+        header[4..6].copy_from_slice(&self.sign.to_be_bytes());
+        header[6..8].copy_from_slice(&self.dscale.to_be_bytes());
+
+        header.into_iter().chain(
+            (0..self.ndigits as i64)
+                .flat_map(move |i| self.digit(self.weight as i64 - i).to_be_bytes()),
+        )
+    }
+}
+
+/**
+Convert a decimal into the binary format of a `NUMERIC`.
+
+This function returns an error if the exponent of the decimal is outside the range of a `NUMERIC`.
+*/
+pub(crate) fn decimal_to_pg_numeric<D: BinaryBuf, C: DigitBuf>(
+    decimal: &D,
+) -> Result<PgNumeric<C>, Error> {
+    let Finite {
+        is_negative,
+        mut coefficient,
+        exponent,
+    } = match unpack::<D, C>(decimal) {
+        Unpacked::Finite(finite) => finite,
+        Unpacked::Infinity { is_negative: false } => {
+            return Ok(PgNumeric::special(NUMERIC_PINF, NUMERIC_INF_DSCALE))
+        }
+        Unpacked::Infinity { is_negative: true } => {
+            return Ok(PgNumeric::special(NUMERIC_NINF, NUMERIC_INF_DSCALE))
+        }
+        Unpacked::Nan(_) => return Ok(PgNumeric::special(NUMERIC_NAN, 0)),
+    };
+
+    coefficient.trim();
+
+    let Some(exponent) = exponent.to_i32().map(i64::from) else {
+        Err(ConvertError::would_overflow("NUMERIC"))?
+    };
+
+    // A positive exponent is written as trailing zeros, so it doesn't need any scale
+    let Some(dscale) = u16::try_from(-exponent.min(0))
+        .ok()
+        .filter(|dscale| *dscale <= NUMERIC_DSCALE_MAX)
+    else {
+        Err(ConvertError::would_overflow("NUMERIC"))?
+    };
+
+    // Postgres doesn't have negative zero, and doesn't send any digits for zero
+    if coefficient.is_zero() {
+        return Ok(PgNumeric::special(NUMERIC_POS, dscale));
+    }
+
+    let trailing_zeros = coefficient
+        .digits()
+        .iter()
+        .position(|d| *d != 0)
+        .expect("the coefficient is non-zero") as i64;
+
+    // The weights of the first and last non-zero base-10000 digits
+    let first = (exponent + coefficient.len() as i64 - 1).div_euclid(4);
+    let last = (exponent + trailing_zeros).div_euclid(4);
+
+    let (Ok(ndigits), Ok(weight)) = (i16::try_from(first - last + 1), i16::try_from(first)) else {
+        Err(ConvertError::would_overflow("NUMERIC"))?
+    };
+
+    Ok(PgNumeric {
+        ndigits,
+        weight,
+        sign: if is_negative {
+            NUMERIC_NEG
+        } else {
+            NUMERIC_POS
+        },
+        dscale,
+        coefficient,
+        exponent,
+    })
+}
+
+/**
+Convert the binary format of a `NUMERIC` into a decimal.
+
+The decimal is encoded exactly, with an exponent of `-dscale`. A negative zero is read as a
+positive one, since Postgres doesn't have negative zero. This function returns an error if the
+bytes aren't a valid `NUMERIC`, or if the number doesn't fit in the decimal.
+*/
+pub(crate) fn decimal_from_pg_numeric<D: BinaryBuf, C: DigitBuf>(
+    bytes: &[u8],
+    target: &'static str,
+) -> Result<D, Error> {
+    if bytes.len() < 8 {
+        Err(ConvertError::malformed(target))?;
+    }
+
+    let (header, digits) = bytes.split_at(8);
+
+    let ndigits = u16::from_be_bytes([header[0], header[1]]) as usize;
+    let weight = i16::from_be_bytes([header[2], header[3]]) as i64;
+    let sign = u16::from_be_bytes([header[4], header[5]]);
+    let dscale = u16::from_be_bytes([header[6], header[7]]);
+
+    let is_negative = match sign {
+        NUMERIC_POS => false,
+        NUMERIC_NEG => true,
+        NUMERIC_PINF | NUMERIC_NINF => {
+            return Ok(pack::<D, C>(Unpacked::Infinity {
+                is_negative: sign == NUMERIC_NINF,
+            }))
+        }
+        NUMERIC_NAN => {
+            return Ok(pack::<D, C>(Unpacked::Nan(Nan {
+                is_negative: false,
+                is_signaling: false,
+                payload: C::default(),
+            })))
+        }
+        _ => Err(ConvertError::malformed(target))?,
+    };
+
+    let digits = digits
+        .chunks_exact(2)
+        .map(|digit| u16::from_be_bytes([digit[0], digit[1]]));
+
+    if ndigits * 2 != bytes.len() - 8
+        || dscale > NUMERIC_DSCALE_MAX
+        || digits.clone().any(|digit| digit > 9999)
+    {
+        Err(ConvertError::malformed(target))?;
+    }
+
+    // Find the powers of ten of the most and least significant non-zero decimal digits
+    let mut nonzero = digits
+        .clone()
+        .enumerate()
+        .filter(|(_, digit)| *digit != 0)
+        .map(|(i, digit)| (4 * (weight - i as i64), digit));
+
+    let (exponent, len, is_negative) = match (nonzero.next(), nonzero.next_back()) {
+        (Some((first_power, first)), last) => {
+            let (last_power, last) = last.unwrap_or((first_power, first));
+
+            let msd = first_power + first.ilog10() as i64;
+            let lsd = last_power + trailing_zeros(last);
+
+            // Keep the scale of the number, unless it has digits beyond it
+            let exponent = lsd.min(-(dscale as i64));
+
+            (exponent, (msd - exponent + 1) as usize, is_negative)
+        }
+        // Postgres doesn't have negative zero, so zero is always positive
+        (None, _) => (-(dscale as i64), 1, false),
+    };
+
+    // Make sure the number fits before unpacking its digits
+    let mut buf =
+        D::try_with_at_least_precision(len, Some(&D::Exponent::from_i32(exponent as i32)))?;
+
+    let mut coefficient = C::default();
+    coefficient.resize(len);
+
+    for (i, mut digit) in digits.enumerate() {
+        for power in 0..4 {
+            let i = 4 * (weight - i as i64) + power - exponent;
+
+            if (0..len as i64).contains(&i) {
+                coefficient.digits_mut()[i as usize] = (digit % 10) as u8;
+            }
+
+            digit /= 10;
+        }
+    }
+
+    let ascii = coefficient.to_ascii();
+
+    let msd = encode_significand_trailing_digits(&mut buf, [ascii.digits()]);
+
+    encode_combination_finite(
+        &mut buf,
+        is_negative,
+        D::Exponent::from_i32(exponent as i32),
+        msd,
+    );
+
+    Ok(buf)
+}
+
+/**
+Count the number of trailing decimal zeros in a non-zero base-10000 digit.
+*/
+#[allow(clippy::manual_is_multiple_of)]
+fn trailing_zeros(mut digit: u16) -> i64 {
+    let mut zeros = 0;
+
+    while digit % 10 == 0 {
+        digit /= 10;
+        zeros += 1;
+    }
+
+    zeros
+}
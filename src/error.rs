@@ -1,5 +1,7 @@
 use core::fmt;
 
+use crate::Flags;
+
 /**
 An error encountered while working with decimals.
 */
@@ -32,12 +34,21 @@ impl From<ConvertError> for Error {
     }
 }
 
+impl From<ArithmeticError> for Error {
+    fn from(err: ArithmeticError) -> Self {
+        Error {
+            kind: ErrorKind::Arithmetic(err),
+        }
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self.kind {
             ErrorKind::Parse(ref err) => fmt::Display::fmt(err, f),
             ErrorKind::Overflow(ref err) => fmt::Display::fmt(err, f),
             ErrorKind::Convert(ref err) => fmt::Display::fmt(err, f),
+            ErrorKind::Arithmetic(ref err) => fmt::Display::fmt(err, f),
         }
     }
 }
@@ -48,6 +59,7 @@ enum ErrorKind {
     Parse(ParseError),
     Overflow(OverflowError),
     Convert(ConvertError),
+    Arithmetic(ArithmeticError),
 }
 
 /**
@@ -210,6 +222,20 @@ impl OverflowError {
         }
     }
 
+    /**
+    A number of digits couldn't fit in a buffer of the given width.
+    */
+    pub(crate) fn precision_out_of_range(
+        max_width_bytes: usize,
+        note: &'static str,
+    ) -> OverflowError {
+        OverflowError {
+            max_width_bytes,
+            required_width_bytes: None,
+            note,
+        }
+    }
+
     /**
     The maximum width supported by the given buffer.
     */
@@ -245,6 +271,36 @@ impl ConvertError {
         }
     }
 
+    /**
+    Converting an infinity or NaN into the given integer type isn't possible.
+    */
+    pub(crate) fn non_finite(target: &'static str) -> Self {
+        ConvertError {
+            target,
+            reason: "isn't possible for infinities or NaNs",
+        }
+    }
+
+    /**
+    Converting bytes into the given decimal type isn't possible because they're not canonical.
+    */
+    pub(crate) fn non_canonical(target: &'static str) -> Self {
+        ConvertError {
+            target,
+            reason: "isn't possible for non-canonical encodings",
+        }
+    }
+
+    /**
+    Converting bytes into the given decimal type isn't possible because they're malformed.
+    */
+    pub(crate) fn malformed(target: &'static str) -> Self {
+        ConvertError {
+            target,
+            reason: "isn't possible for malformed bytes",
+        }
+    }
+
     /**
     Converting into the given integer type would require rounding.
     */
@@ -262,6 +318,66 @@ impl fmt::Display for ConvertError {
     }
 }
 
+/**
+An error encountered when a calculation sets a flag that its context traps, or when the context
+itself is invalid.
+*/
+#[derive(Debug)]
+pub struct ArithmeticError {
+    kind: ArithmeticErrorKind,
+}
+
+#[derive(Debug)]
+enum ArithmeticErrorKind {
+    Trapped(Flags),
+    InvalidContext { reason: &'static str },
+}
+
+impl ArithmeticError {
+    /**
+    A calculation set the given trapped flags.
+    */
+    pub(crate) fn trapped(trapped: Flags) -> Self {
+        ArithmeticError {
+            kind: ArithmeticErrorKind::Trapped(trapped),
+        }
+    }
+
+    /**
+    A calculation used a context whose limits can't be applied to its format.
+    */
+    pub(crate) fn invalid_context(reason: &'static str) -> Self {
+        ArithmeticError {
+            kind: ArithmeticErrorKind::InvalidContext { reason },
+        }
+    }
+
+    /**
+    The trapped flags that were set by the calculation.
+
+    If the context was invalid then no flags were set.
+    */
+    pub fn flags(&self) -> Flags {
+        match self.kind {
+            ArithmeticErrorKind::Trapped(trapped) => trapped,
+            ArithmeticErrorKind::InvalidContext { .. } => Flags::empty(),
+        }
+    }
+}
+
+impl fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ArithmeticErrorKind::Trapped(trapped) => {
+                write!(f, "the calculation raised trapped conditions `{}`", trapped)
+            }
+            ArithmeticErrorKind::InvalidContext { reason } => {
+                write!(f, "the context is invalid because {}", reason)
+            }
+        }
+    }
+}
+
 #[cfg(feature = "std")]
 mod std_support {
     use super::*;
@@ -275,4 +391,6 @@ mod std_support {
     impl error::Error for OverflowError {}
 
     impl error::Error for ConvertError {}
+
+    impl error::Error for ArithmeticError {}
 }
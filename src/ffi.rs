@@ -0,0 +1,421 @@
+/*!
+A C ABI for the fixed-width decimals.
+
+This module is only available when the `ffi` feature is enabled. A C header for it is generated
+with `cbindgen` and checked in at `include/decstr.h`.
+
+Decimals cross the boundary by pointer, using the same in-memory representation as GCC's
+`_Decimal32`, `_Decimal64`, and `_Decimal128` types on x86-64 Linux. That's the
+binary-integer-decimal (BID) encoding in little endian, so C code can pass pointers to its own
+decimal values directly:
+
+```c
+#include "decstr.h"
+
+_Decimal64 d;
+
+if (decstr_d64_parse("1.25", 4, &d) == DECSTR_OK) {
+    d = d * 2;
+}
+```
+
+Functions that can fail return [`DECSTR_OK`] on success and [`DECSTR_ERR`] on failure, in which
+case their output isn't written. A null pointer is always a failure. The `decstr_*_format`
+functions return a length instead, so they return [`DECSTR_FORMAT_ERR`] on failure.
+*/
+
+use crate::{
+    Bitstring128,
+    Bitstring32,
+    Bitstring64,
+};
+use core::{
+    ffi::{
+        c_char,
+        c_int,
+    },
+    fmt::{
+        self,
+        Write,
+    },
+    slice,
+    str,
+};
+
+/**
+The value returned by functions that succeed.
+*/
+pub const DECSTR_OK: c_int = 0;
+
+/**
+The value returned by functions that fail.
+*/
+pub const DECSTR_ERR: c_int = -1;
+
+/**
+The value returned by `decstr_*_format` functions that fail.
+
+This is `SIZE_MAX` in C, which can't be the length of any formatted decimal.
+*/
+pub const DECSTR_FORMAT_ERR: usize = usize::MAX;
+
+/**
+The in-memory representation of a GCC `_Decimal32`.
+*/
+#[repr(C, align(4))]
+#[derive(Clone, Copy)]
+pub struct Decimal32 {
+    bid: [u8; 4],
+}
+
+/**
+The in-memory representation of a GCC `_Decimal64`.
+*/
+#[repr(C, align(8))]
+#[derive(Clone, Copy)]
+pub struct Decimal64 {
+    bid: [u8; 8],
+}
+
+/**
+The in-memory representation of a GCC `_Decimal128`.
+*/
+#[repr(C, align(16))]
+#[derive(Clone, Copy)]
+pub struct Decimal128 {
+    bid: [u8; 16],
+}
+
+macro_rules! ffi {
+    ($d:ident => $c:ident {
+        $parse:ident,
+        $format:ident,
+        $from_i64:ident,
+        $to_i64:ident,
+        $from_u64:ident,
+        $to_u64:ident,
+        $from_double:ident,
+        $to_double:ident,
+    }) => {
+        impl From<$d> for $c {
+            fn from(d: $d) -> $c {
+                $c {
+                    bid: d.to_bid_bytes(),
+                }
+            }
+        }
+
+        impl From<$c> for $d {
+            fn from(c: $c) -> $d {
+                $d::from_bid_bytes(c.bid)
+            }
+        }
+
+        /**
+        Parse a decimal from `len` bytes of UTF8 text.
+
+        The text doesn't need to be null-terminated.
+
+        # Safety
+
+        `str` must point to `len` readable bytes, and `out` must point to a writable decimal.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $parse(str: *const c_char, len: usize, out: *mut $c) -> c_int {
+            if str.is_null() {
+                return DECSTR_ERR;
+            }
+
+            let Ok(str) = str::from_utf8(slice::from_raw_parts(str as *const u8, len)) else {
+                return DECSTR_ERR;
+            };
+
+            write(out, $d::try_parse_str(str).ok().map($c::from))
+        }
+
+        /**
+        Format a decimal as null-terminated text into `buf`.
+
+        At most `len` bytes are written, including the null terminator. The length of the full
+        text is returned, not including the null terminator, so if it's greater than or equal to
+        `len` then the text was truncated. This is the same convention as `snprintf`. If `value`
+        is null then nothing is written and [`DECSTR_FORMAT_ERR`] is returned.
+
+        # Safety
+
+        `value` must point to a readable decimal, and `buf` must point to `len` writable bytes.
+        `buf` may be null if `len` is `0`.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $format(value: *const $c, buf: *mut c_char, len: usize) -> usize {
+            let Some(value) = read(value) else {
+                return DECSTR_FORMAT_ERR;
+            };
+
+            let buf = if buf.is_null() || len == 0 {
+                &mut []
+            } else {
+                slice::from_raw_parts_mut(buf as *mut u8, len)
+            };
+
+            format(buf, $d::from(value))
+        }
+
+        /**
+        Convert a signed integer into a decimal.
+
+        This function fails if the integer can't be encoded exactly.
+
+        # Safety
+
+        `out` must point to a writable decimal.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $from_i64(value: i64, out: *mut $c) -> c_int {
+            write(out, $d::try_from(value).ok().map($c::from))
+        }
+
+        /**
+        Convert a decimal into a signed integer.
+
+        This function fails if the decimal isn't an integer, or is out of range.
+
+        # Safety
+
+        `value` must point to a readable decimal, and `out` must point to a writable integer.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $to_i64(value: *const $c, out: *mut i64) -> c_int {
+            write(
+                out,
+                read(value).and_then(|value| i64::try_from($d::from(value)).ok()),
+            )
+        }
+
+        /**
+        Convert an unsigned integer into a decimal.
+
+        This function fails if the integer can't be encoded exactly.
+
+        # Safety
+
+        `out` must point to a writable decimal.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $from_u64(value: u64, out: *mut $c) -> c_int {
+            write(out, $d::try_from(value).ok().map($c::from))
+        }
+
+        /**
+        Convert a decimal into an unsigned integer.
+
+        This function fails if the decimal isn't an integer, or is out of range.
+
+        # Safety
+
+        `value` must point to a readable decimal, and `out` must point to a writable integer.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $to_u64(value: *const $c, out: *mut u64) -> c_int {
+            write(
+                out,
+                read(value).and_then(|value| u64::try_from($d::from(value)).ok()),
+            )
+        }
+
+        /**
+        Convert a binary floating point into a decimal.
+
+        The shortest decimal that roundtrips to the same binary floating point is used. This
+        function fails if it can't be encoded exactly.
+
+        # Safety
+
+        `out` must point to a writable decimal.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $from_double(value: f64, out: *mut $c) -> c_int {
+            write(out, $d::try_from(value).ok().map($c::from))
+        }
+
+        /**
+        Convert a decimal into a binary floating point.
+
+        This function fails if the decimal can't be converted exactly.
+
+        # Safety
+
+        `value` must point to a readable decimal, and `out` must point to a writable double.
+        */
+        #[no_mangle]
+        pub unsafe extern "C" fn $to_double(value: *const $c, out: *mut f64) -> c_int {
+            write(
+                out,
+                read(value).and_then(|value| f64::try_from($d::from(value)).ok()),
+            )
+        }
+    };
+}
+
+ffi!(Bitstring32 => Decimal32 {
+    decstr_d32_parse,
+    decstr_d32_format,
+    decstr_d32_from_i64,
+    decstr_d32_to_i64,
+    decstr_d32_from_u64,
+    decstr_d32_to_u64,
+    decstr_d32_from_double,
+    decstr_d32_to_double,
+});
+
+ffi!(Bitstring64 => Decimal64 {
+    decstr_d64_parse,
+    decstr_d64_format,
+    decstr_d64_from_i64,
+    decstr_d64_to_i64,
+    decstr_d64_from_u64,
+    decstr_d64_to_u64,
+    decstr_d64_from_double,
+    decstr_d64_to_double,
+});
+
+ffi!(Bitstring128 => Decimal128 {
+    decstr_d128_parse,
+    decstr_d128_format,
+    decstr_d128_from_i64,
+    decstr_d128_to_i64,
+    decstr_d128_from_u64,
+    decstr_d128_to_u64,
+    decstr_d128_from_double,
+    decstr_d128_to_double,
+});
+
+/**
+Read a value through a pointer that may be null.
+*/
+unsafe fn read<T: Copy>(value: *const T) -> Option<T> {
+    if value.is_null() {
+        None
+    } else {
+        Some(*value)
+    }
+}
+
+/**
+Write the result of a conversion through a pointer that may be null.
+*/
+unsafe fn write<T>(out: *mut T, value: Option<T>) -> c_int {
+    match value {
+        Some(value) if !out.is_null() => {
+            *out = value;
+
+            DECSTR_OK
+        }
+        _ => DECSTR_ERR,
+    }
+}
+
+/**
+Format a value as null-terminated text into a buffer, returning the length of the full text.
+*/
+fn format(buf: &mut [u8], value: impl fmt::Display) -> usize {
+    struct Writer<'a> {
+        buf: &'a mut [u8],
+        len: usize,
+    }
+
+    impl<'a> Write for Writer<'a> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            // Always leave space for the null terminator
+            let capacity = self.buf.len().saturating_sub(1);
+
+            let start = self.len.min(capacity);
+            let copy = (capacity - start).min(s.len());
+
+            self.buf[start..start + copy].copy_from_slice(&s.as_bytes()[..copy]);
+            self.len += s.len();
+
+            Ok(())
+        }
+    }
+
+    let mut writer = Writer { buf, len: 0 };
+
+    let _ = write!(writer, "{}", value);
+
+    if let Some(last) = writer.buf.len().checked_sub(1) {
+        writer.buf[writer.len.min(last)] = 0;
+    }
+
+    writer.len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_format() {
+        let mut d = Decimal64 { bid: [0; 8] };
+
+        assert_eq!(DECSTR_OK, unsafe {
+            decstr_d64_parse("-1.25".as_ptr() as *const c_char, 5, &mut d)
+        });
+        assert_eq!(0xb180_0000_0000_007d, u64::from_le_bytes(d.bid));
+
+        let mut buf = [0x7f as c_char; 8];
+
+        assert_eq!(5, unsafe { decstr_d64_format(&d, buf.as_mut_ptr(), 8) });
+        assert_eq!(b"-1.25\0", &buf.map(|b| b as u8)[..6]);
+
+        // Text that doesn't fit is truncated
+        assert_eq!(5, unsafe { decstr_d64_format(&d, buf.as_mut_ptr(), 3) });
+        assert_eq!(b"-1\0", &buf.map(|b| b as u8)[..3]);
+
+        assert_eq!(5, unsafe {
+            decstr_d64_format(&d, core::ptr::null_mut(), 0)
+        });
+
+        // A null value is a failure, and the buffer isn't written
+        assert_eq!(DECSTR_FORMAT_ERR, unsafe {
+            decstr_d64_format(core::ptr::null(), buf.as_mut_ptr(), 8)
+        });
+        assert_eq!(b"-1\0", &buf.map(|b| b as u8)[..3]);
+
+        assert_eq!(DECSTR_ERR, unsafe {
+            decstr_d64_parse("x".as_ptr() as *const c_char, 1, &mut d)
+        });
+        assert_eq!(DECSTR_ERR, unsafe {
+            decstr_d64_parse(core::ptr::null(), 0, &mut d)
+        });
+        assert_eq!(DECSTR_ERR, unsafe {
+            decstr_d64_parse("1".as_ptr() as *const c_char, 1, core::ptr::null_mut())
+        });
+    }
+
+    #[test]
+    fn int_double() {
+        let mut d = Decimal32 { bid: [0; 4] };
+        let mut i = 0i64;
+        let mut u = 0u64;
+        let mut f = 0f64;
+
+        assert_eq!(DECSTR_OK, unsafe { decstr_d32_from_i64(-42, &mut d) });
+        assert_eq!(DECSTR_OK, unsafe { decstr_d32_to_i64(&d, &mut i) });
+        assert_eq!(-42, i);
+        assert_eq!(DECSTR_ERR, unsafe { decstr_d32_to_u64(&d, &mut u) });
+
+        assert_eq!(DECSTR_ERR, unsafe { decstr_d32_from_u64(u64::MAX, &mut d) });
+
+        assert_eq!(DECSTR_OK, unsafe { decstr_d32_from_double(0.5, &mut d) });
+        assert_eq!(DECSTR_OK, unsafe { decstr_d32_to_double(&d, &mut f) });
+        assert_eq!(0.5, f);
+        assert_eq!(DECSTR_ERR, unsafe { decstr_d32_to_i64(&d, &mut i) });
+
+        let mut d = Decimal128 { bid: [0; 16] };
+
+        assert_eq!(DECSTR_OK, unsafe { decstr_d128_from_u64(u64::MAX, &mut d) });
+        assert_eq!(DECSTR_OK, unsafe { decstr_d128_to_u64(&d, &mut u) });
+        assert_eq!(u64::MAX, u);
+    }
+}
@@ -55,26 +55,154 @@ Compared with text, decimal bitstrings are:
 
 - Compact. Instead of encoding 1 digit per byte (8 bits), you get 3 digits per 10 bits.
 - Cheap to classify. You can tell from a single byte whether or not a number is positive, negative, whole, infinity, or
-NaN. You don't need to reparse the number.
+  NaN. You don't need to reparse the number.
 
 Compared with binary (base-2) bitstrings, decimal bitstrings are:
 
 - Easy to convert between text. You don't need arbitrary-precision arithmetic to encode a human-readable number into a decimal bitstring.
 - Precise. You can exactly encode base-10 numbers, which is the base most modern number systems use.
 - Consistent. They're a newer standard, so they avoid some ambiguities around NaN payloads and signaling that affect the
-portability of binary bitstrings.
+  portability of binary bitstrings.
 
 # Features and limitations
 
-This library _only_ does conversions between Rust's primitive number types, numbers encoded as text, and decimal bitstrings.
-It's not an implementation of decimal arithmetic. It also doesn't do rounding. If a number can't be encoded in a decimal
-bitstring of a given width then you'll get `None`s instead of infinities or rounded values.
+This library is mostly focused on conversions between Rust's primitive number types, numbers encoded as text, and decimal bitstrings.
+Conversions don't do rounding by default. If a number can't be encoded in a decimal bitstring of a given width then you'll get `None`s
+instead of infinities or rounded values. Conversions that end in `_with`, like `Bitstring32::from_i64_with`, round using a [`Context`]
+instead. Text can be rounded to fit a fixed-width decimal using `parse_rounded`:
+
+```
+use decstr::{Bitstring64, Flags, RoundingMode};
+
+let (pi, flags) = Bitstring64::parse_rounded("3.14159265358979323846", RoundingMode::HalfEven)?;
+
+assert_eq!("3.141592653589793", pi.to_string());
+assert!(flags.contains(Flags::INEXACT));
+# Ok::<(), decstr::Error>(())
+```
+
+Arithmetic is supported, and does round its results. See the [Arithmetic](#arithmetic) section for details.
 
 Decimal numbers in IEEE 754 are non-normalized by-design. The number `1.00` will encode differently to `1` or `1.0`.
+The exponent of a decimal can be changed with `quantize` or `rescale`, and compared with `same_quantum`.
+Trailing zeros can be removed with `reduce`, which gives decimals that are numerically equal the same encoding:
+
+```
+use decstr::{Bitstring64, RoundingMode};
+
+let amount = Bitstring64::try_parse_str("12.345")?;
+let cents = Bitstring64::try_parse_str("0.01")?;
+
+assert_eq!("12.34", amount.quantize(&cents).to_string());
+assert_eq!("12.35", amount.rescale(-2, RoundingMode::HalfUp).to_string());
+assert!(amount.quantize(&cents).same_quantum(&cents));
+
+let a = Bitstring64::try_parse_str("1.200e3")?;
+let b = Bitstring64::try_parse_str("1200")?;
+
+assert_eq!(a.reduce().as_le_bytes(), b.reduce().as_le_bytes());
+# Ok::<(), decstr::Error>(())
+```
 
 This library does support very high precision in no-std, and can work with arbitrary precision when the
 `arbitrary-precision` feature is enabled.
 
+# Comparison
+
+Decimals are compared by their numeric value rather than their encoding. The numbers `1`, `1.0`, and `1.00`
+are all equal, even though they encode differently. So are `0` and `-0`. NaNs are unordered, so they're
+never equal to anything, including themselves. Decimals of different widths can be compared with each other.
+
+Each decimal also has a `total_cmp` method that implements the IEEE 754 `totalOrder` predicate. It can be
+used to sort decimals that mix NaNs, signed zeros, and different representations of the same number.
+
+Hashing is consistent with numeric equality, so `1`, `1.0`, and `1.00` all produce the same hash. If you need
+to distinguish between them, each decimal also has a `hash_repr` method that hashes its encoded bytes instead.
+Since NaNs aren't equal to themselves, decimals don't implement `Eq`. To use them as keys in a `HashMap`, wrap
+them in either [`NumericEq`], which compares by numeric value, or [`ReprEq`], which compares by encoding.
+
+# Arithmetic
+
+Decimals support addition and subtraction through their `add` and `sub` methods. The fixed-width decimals also
+support multiplication through `mul`, and fused multiply-add through `fma`, which calculates `a * b + c` with
+a single rounding step. The fixed-width decimals and `BigBitstring` support division through `div`, `div_integer`,
+`rem`, and the IEEE 754 `remainder`.
+
+The fixed-width decimals and `BigBitstring` also have `sqrt`, `exp`, `ln`, `log10`, and `pow`. Their results are
+correctly rounded, so they can be used to calculate constants like `ln(10)` to the full precision of a decimal. The `BigBitstring` versions
+accept the number of digits to calculate:
+
+```
+# #[cfg(feature = "arbitrary-precision")]
+# {
+use decstr::BigBitstring;
+
+let e = BigBitstring::from(1u8).exp(50);
+
+assert_eq!("2.7182818284590452353602874713526624977572470937000", e.to_string());
+# }
+```
+
+The fixed-width decimals also implement the standard operator traits, like `Add` and `Neg`, along with
+`Sum` and `Product`, so they can be used like other Rust numbers:
+
+```
+use decstr::Bitstring64;
+
+let amounts = ["10.00", "5.50", "0.25"]
+    .iter()
+    .map(|amount| amount.parse::<Bitstring64>())
+    .collect::<Result<Vec<_>, _>>()?;
+
+let total: Bitstring64 = amounts.iter().sum();
+
+assert_eq!("15.75", total.to_string());
+# Ok::<(), decstr::Error>(())
+```
+
+Arithmetic works directly on the encoded decimals and follows IEEE 754 and
+the [General Decimal Arithmetic](https://speleotrove.com/decimal/daops.html) specification:
+
+- The result is calculated exactly and then rounded to the precision of the decimal, with ties rounding to even.
+  `Bitstring` rounds to the precision of its widest width, which is 43 digits. `BigBitstring` rounds the
+  results of `div`, `div_integer`, `rem`, and `remainder` to the precision of its wider operand.
+- Exact sums and differences use the smallest exponent of the operands, so `1.0 + 1.00` is `2.00`.
+  Exact products use the sum of the exponents of the operands, so `1.0 * 1.00` is `1.000`, and exact
+  quotients use the exponent closest to their difference, so `8.00 / 2` is `4.00`.
+- Results that are too large to encode overflow to infinity.
+- Dividing a non-zero number by zero returns an infinity with the sign of the result.
+- If either operand is a NaN then the result is that NaN, quietened. Invalid operations, like adding
+  infinities with opposite signs or dividing zero by zero, return a NaN.
+
+## Contexts
+
+Each arithmetic method also has a variant that ends in `_with`, like `add_with`, that accepts a [`Context`].
+A context can change how results are rounded:
+
+- Its [`RoundingMode`] determines which way inexact results are rounded. The default is half-even.
+- Its precision and exponent range can narrow the limits of the decimal. `BigBitstring` also grows
+  to fit the precision of the context.
+
+A context also records the conditions encountered while calculating results, like whether they were
+inexact, as sticky [`Flags`]. Flags that are trapped by the context return an error instead of a result:
+
+```
+use decstr::{Bitstring32, Context, Flags, RoundingMode};
+
+let mut ctx = Context::new()
+    .with_rounding(RoundingMode::Ceiling)
+    .with_traps(Flags::DIVISION_BY_ZERO);
+
+let a = Bitstring32::try_parse_str("1234567")?;
+let b = Bitstring32::try_parse_str("0.1")?;
+
+assert_eq!("1234568", a.add_with(&b, &mut ctx)?.to_string());
+assert!(ctx.flags().contains(Flags::INEXACT));
+
+assert!(a.div_with(&Bitstring32::ZERO, &mut ctx).is_err());
+# Ok::<(), decstr::Error>(())
+```
+
 # Conversions
 
 ## Binary floating point
@@ -87,6 +215,229 @@ The following cases are worth calling out:
 - A signaling NaN is encoded as a quiet NaN.
 - NaN payloads are discarded.
 
+## Integers
+
+Decimals only convert into integers when they're exactly integral, so `17e-1` doesn't convert into an `i32`.
+Round them first with `round_to_integral`, or one of `trunc`, `floor`, `ceil`, or `round`:
+
+```
+use decstr::{Bitstring64, RoundingMode};
+
+let d = Bitstring64::try_parse_str("17e-1")?;
+
+assert_eq!(None, d.to_i32());
+assert_eq!(Some(2), d.round().to_i32());
+assert_eq!(Some(1), d.trunc().to_i32());
+assert_eq!(Some(2), d.round_to_integral(RoundingMode::HalfEven).to_i32());
+# Ok::<(), decstr::Error>(())
+```
+
+Each integer conversion also has `_rounded`, `_truncated`, and `_saturating` variants, like `to_i64_rounded`.
+The saturating variants truncate and then clamp to the range of the integer, like casting a binary floating point with `as`:
+
+```
+use decstr::{Bitstring64, RoundingMode};
+
+assert_eq!(Some(13), Bitstring64::try_parse_str("12.7")?.to_i64_rounded(RoundingMode::HalfEven));
+assert_eq!(Some(12), Bitstring64::try_parse_str("12.7")?.to_i64_truncated());
+assert_eq!(i64::MAX, Bitstring64::try_parse_str("1e40")?.to_i64_saturating());
+# Ok::<(), decstr::Error>(())
+```
+
+Decimals can also convert to and from scaled integers, where the integer is the decimal multiplied by `10^scale`.
+This is a common way to store fixed-point numbers, like a `DECIMAL(18, 4)` column stored in an `i64` with a scale of `4`:
+
+```
+use decstr::Bitstring64;
+
+let d = Bitstring64::from_scaled_i64(123450, 4)?;
+
+assert_eq!("12.3450", d.to_string());
+assert_eq!(123450, d.to_scaled_i64(4)?);
+assert_eq!(1234500, d.to_scaled_i64(5)?);
+
+// Converting with a scale that would need rounding is an error
+assert!(d.to_scaled_i64(2).is_err());
+# Ok::<(), decstr::Error>(())
+```
+
+## Parts
+
+Finite decimals can be decomposed into their sign, coefficient, and exponent with `to_parts`, and built back up with `from_parts`.
+The value of a decimal is its coefficient multiplied by `10^exponent`:
+
+```
+use decstr::Bitstring64;
+
+let d = Bitstring64::try_parse_str("-1.20")?;
+
+assert_eq!(Some((true, 120, -2)), d.to_parts());
+assert_eq!("-1.20", Bitstring64::from_parts(true, 120, -2)?.to_string());
+# Ok::<(), decstr::Error>(())
+```
+
+The digits of the coefficient can also be inspected directly with `digits`, `significant_digits`, `exponent`, `scale`, and `adjusted_exponent`.
+This is enough to check whether a decimal fits a constraint like `NUMERIC(5, 2)` without formatting it:
+
+```
+use decstr::Bitstring64;
+
+let d = Bitstring64::try_parse_str("123.40")?;
+
+assert_eq!(vec![1, 2, 3, 4, 0], d.digits().collect::<Vec<_>>());
+assert_eq!(5, d.significant_digits());
+assert_eq!(Some(2), d.scale());
+assert_eq!(Some(2), d.adjusted_exponent());
+
+// The integer part needs 3 digits and the fractional part needs 2
+let fits = d.adjusted_exponent().unwrap() < 5 - 2 && d.scale().unwrap() <= 2;
+assert!(fits);
+# Ok::<(), decstr::Error>(())
+```
+
+## Classification
+
+Decimals can be classified into one of the ten classes defined by IEEE754-2019 with `classify`, which can be converted into a `core::num::FpCategory`.
+Zeros, integers, and subnormals are identified by their value rather than their encoding, so `0e5` is a zero and `1.20e2` is an integer:
+
+```
+use decstr::{Bitstring32, DecimalCategory};
+
+assert_eq!(DecimalCategory::NegativeZero, Bitstring32::try_parse_str("-0e5")?.classify());
+assert_eq!(DecimalCategory::PositiveSubnormal, Bitstring32::try_parse_str("1e-96")?.classify());
+assert!(Bitstring32::try_parse_str("1.20e2")?.is_integer());
+# Ok::<(), decstr::Error>(())
+```
+
+DPD can encode some values in more than one way, so decimals read from bytes may not be canonical.
+Decimals produced by this library are always canonical.
+Use `try_from_le_bytes_canonical` to reject non-canonical bytes from untrusted sources, or `canonicalize` to re-encode them:
+
+```
+use decstr::Bitstring32;
+
+// `999` encoded using a redundant declet
+let bytes = [0xff, 0x03, 0x50, 0x22];
+
+assert!(Bitstring32::try_from_le_bytes_canonical(bytes).is_err());
+
+let d = Bitstring32::from_le_bytes(bytes);
+
+assert!(!d.is_canonical());
+assert_eq!("999", d.to_string());
+assert_eq!(Bitstring32::try_parse_str("999")?.as_le_bytes(), d.canonicalize().as_le_bytes());
+# Ok::<(), decstr::Error>(())
+```
+
+## NaN payloads
+
+NaNs can carry an integer payload, like `nan(123)`, which is a useful way to tag why a value is missing.
+Payloads are kept through arithmetic and conversions between decimal encodings, and can be read with `nan_payload`.
+There are a few exceptions:
+
+- Arithmetic, and conversions that round into a narrower decimal, truncate payloads that are too long
+  for the precision of the result.
+- Conversions to and from binary floating point discard payloads.
+- Conversions to Postgres' `NUMERIC` discard payloads, along with the sign of the NaN and whether it's signaling.
+
+Signaling NaNs can be converted into quiet ones with `quieten`:
+
+```
+use decstr::Bitstring64;
+
+let d = Bitstring64::nan_with_payload(42, true)?;
+
+assert_eq!("snan(42)", d.to_string());
+assert_eq!(Some(42), d.nan_payload());
+assert_eq!("nan(42)", d.quieten().to_string());
+# Ok::<(), decstr::Error>(())
+```
+
+## Binary integer decimal
+
+This library encodes decimals using densely-packed-decimal (DPD), but IEEE754-2019 also defines a binary-integer-decimal (BID)
+encoding, used by Intel's decimal library, GCC's decimal types on x86, and MongoDB. The fixed-width decimals can be converted
+to and from BID without going through text:
+
+```
+use decstr::Bitstring64;
+
+let d = Bitstring64::try_parse_str("-7.50")?;
+
+assert_eq!(0xb180_0000_0000_02ee, u64::from_le_bytes(d.to_bid_bytes()));
+assert_eq!("-7.50", Bitstring64::from_bid_bytes(d.to_bid_bytes()).to_string());
+# Ok::<(), decstr::Error>(())
+```
+
+## IBM decimal floating point
+
+IBM z/Architecture, POWER, and DB2's `DECFLOAT` store decimals using the same densely-packed-decimal encoding as this library,
+but in big-endian byte-order. Decimals of any width can be converted to and from IBM's format. The fixed-width
+decimals use `from_ibm_dfp_bytes` and return arrays. `Bitstring` and `BigBitstring` use `try_from_ibm_dfp_bytes`,
+since the bytes might not be a valid width, and return an iterator over the bytes:
+
+```
+use decstr::{Bitstring, Bitstring64};
+
+// DB2 `DECFLOAT(16)` for `123.45`
+let bytes = [0x22, 0x30, 0x00, 0x00, 0x00, 0x00, 0x49, 0xc5];
+
+assert_eq!("123.45", Bitstring64::from_ibm_dfp_bytes(bytes).to_string());
+assert_eq!("123.45", Bitstring::try_from_ibm_dfp_bytes(&bytes)?.to_string());
+
+assert_eq!(bytes, Bitstring64::try_parse_str("123.45")?.to_ibm_dfp_bytes());
+assert!(Bitstring::try_from_ibm_dfp_bytes(&bytes)?.to_ibm_dfp_bytes().eq(bytes));
+# Ok::<(), decstr::Error>(())
+```
+
+## C
+
+When the `ffi` feature is enabled, the fixed-width decimals can be parsed, formatted, and converted from C through the functions
+in the `ffi` module. They use the same representation as GCC's `_Decimal32`, `_Decimal64`, and `_Decimal128` types on x86-64,
+and are declared in the `include/decstr.h` header.
+
+## BSON
+
+When the `bson` feature is enabled, decimals can be converted to and from MongoDB's `Decimal128`. `Bitstring128`
+converts losslessly in both directions. `Bitstring` and `BigBitstring` can be converted with `TryFrom`, which fails if
+the decimal can't be encoded exactly, or rounded to fit with `to_bson_decimal128_with`:
+
+```
+# #[cfg(feature = "bson")]
+# {
+use decstr::{Bitstring, Context};
+
+let d = Bitstring::try_parse_str("1.2345678901234567890123456789012345")?;
+
+assert!(bson::Decimal128::try_from(d).is_err());
+
+let rounded = d.to_bson_decimal128_with(&mut Context::new())?;
+
+assert_eq!("1.234567890123456789012345678901234", rounded.to_string());
+# }
+# Ok::<(), decstr::Error>(())
+```
+
+## Postgres
+
+Postgres' `NUMERIC` uses its own base-10000 format in its binary protocol. `Bitstring` and `BigBitstring` can be converted
+to and from it exactly, keeping the scale of the number:
+
+```
+use decstr::Bitstring;
+
+let bytes = [0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x07, 0xd0];
+
+let d = Bitstring::try_from_pg_numeric_bytes(&bytes)?;
+
+assert_eq!("1.20", d.to_string());
+assert_eq!(bytes, *d.to_pg_numeric_bytes()?.collect::<Vec<_>>());
+# Ok::<(), decstr::Error>(())
+```
+
+When the `postgres` feature is enabled, they also implement `ToSql` and `FromSql` from `postgres-types`, so they can be
+used with the `postgres` and `tokio-postgres` clients.
+
 ## Exponents
 
 The exponent range of a decimal depends on its width in bits.
@@ -116,6 +467,8 @@ exponent. There's some bit-twiddling involved to encode the bits for a number ac
 but it's all explained along the way.
 - `convert`: Combines the `text` and `binary` modules to convert between strings and Rust primitive
 numbers and encoded bitstrings.
+- `math`: Arithmetic and comparison on encoded bitstrings.
+- `context`: The rounding, limits, and flags that arithmetic is performed with.
 - `bitstring`: The user-facing types.
 - `num`: Some generic infrastructure for working with integers and floating points that support
 conversion and arithmetic.
@@ -126,13 +479,24 @@ simplicity and performance. The same implementation handles encoding decimal32 u
 
 mod binary;
 mod bitstring;
+#[cfg(feature = "bson")]
+mod bson;
+mod category;
+mod context;
 mod convert;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod math;
 mod num;
+#[cfg(feature = "postgres")]
+mod postgres;
 mod text;
 
 pub use self::{
     bitstring::*,
+    category::*,
+    context::*,
     error::*,
 };
 
@@ -140,6 +504,11 @@ pub use self::{
 mod tests {
     use super::*;
 
+    use core::{
+        fmt,
+        str::FromStr,
+    };
+
     pub(crate) fn bitstr(b: &[u8]) -> String {
         use core::fmt::Write;
 
@@ -282,6 +651,1660 @@ mod tests {
         }
     }
 
+    #[test]
+    fn eq() {
+        for (a, b, is_eq) in [
+            ("1", "1", true),
+            ("1.0", "1.00", true),
+            ("1", "1.000", true),
+            ("10e-1", "1", true),
+            ("1.2e3", "1200", true),
+            ("0", "-0", true),
+            ("0", "0e-10", true),
+            ("-0.00", "0e5", true),
+            ("inf", "inf", true),
+            ("-inf", "-inf", true),
+            ("1", "-1", false),
+            ("1", "1.01", false),
+            ("inf", "-inf", false),
+            ("nan", "nan", false),
+            ("snan", "snan", false),
+            ("nan", "1", false),
+        ] {
+            let da = Bitstring::try_parse_str(a).expect("failed to parse decimal");
+            let db = Bitstring::try_parse_str(b).expect("failed to parse decimal");
+
+            assert_eq!(is_eq, da == db, "{} == {}", a, b);
+            assert_eq!(is_eq, db == da, "{} == {}", b, a);
+        }
+    }
+
+    #[test]
+    fn partial_cmp() {
+        use core::cmp::Ordering;
+
+        for (a, b, cmp) in [
+            ("1", "2", Some(Ordering::Less)),
+            ("1.0", "1.00", Some(Ordering::Equal)),
+            ("0.09", "0.1", Some(Ordering::Less)),
+            ("-1", "0", Some(Ordering::Less)),
+            ("-0", "0", Some(Ordering::Equal)),
+            ("-2", "-1", Some(Ordering::Less)),
+            ("1e5", "99999", Some(Ordering::Greater)),
+            ("-inf", "-1e100", Some(Ordering::Less)),
+            ("inf", "1e100", Some(Ordering::Greater)),
+            ("nan", "1", None),
+            ("1", "nan", None),
+            ("nan", "nan", None),
+        ] {
+            let da = Bitstring::try_parse_str(a).expect("failed to parse decimal");
+            let db = Bitstring::try_parse_str(b).expect("failed to parse decimal");
+
+            assert_eq!(cmp, da.partial_cmp(&db), "{} cmp {}", a, b);
+            assert_eq!(
+                cmp.map(Ordering::reverse),
+                db.partial_cmp(&da),
+                "{} cmp {}",
+                b,
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn cmp_different_widths() {
+        let d32 = Bitstring32::try_parse_str("1.5").unwrap();
+        let d64 = Bitstring64::try_parse_str("1.500").unwrap();
+        let d128 = Bitstring128::try_parse_str("1.5000000000000000000000000001").unwrap();
+        let d = Bitstring::try_parse_str("-1.5").unwrap();
+
+        assert_eq!(d32, d64);
+        assert_eq!(d64, d32);
+        assert!(d32 < d128);
+        assert!(d128 > d64);
+        assert!(d < d32);
+        assert!(d128 > d);
+        assert_ne!(Bitstring32::NAN, Bitstring64::NAN);
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn cmp_big() {
+        let big =
+            BigBitstring::try_parse_str("1.00000000000000000000000000000000000000000000000000001")
+                .unwrap();
+
+        assert!(big > Bitstring64::ONE);
+        assert!(Bitstring::try_parse_str("1.0").unwrap() < big);
+        assert_eq!(
+            BigBitstring::try_parse_str("1.000").unwrap(),
+            Bitstring32::try_parse_str("1").unwrap()
+        );
+
+        let large = BigBitstring::try_parse_str("1e9999999999").unwrap();
+        let larger = BigBitstring::try_parse_str("1e99999999999").unwrap();
+        let small = BigBitstring::try_parse_str("1e-99999999999").unwrap();
+
+        assert!(large > Bitstring128::MAX);
+        assert!(large < larger);
+        assert!(small < Bitstring128::MIN_POSITIVE);
+        assert!(small > Bitstring128::ZERO);
+        assert_eq!(large, BigBitstring::try_parse_str("10e9999999998").unwrap());
+    }
+
+    #[test]
+    fn total_cmp() {
+        let mut decimals = [
+            "nan", "1", "-0", "1.0", "-inf", "snan", "0", "-1", "inf", "-nan", "-1.0", "0.5",
+        ]
+        .map(|d| Bitstring::try_parse_str(d).expect("failed to parse decimal"));
+
+        decimals.sort_by(|a, b| a.total_cmp(b));
+
+        assert_eq!(
+            ["-nan", "-inf", "-1", "-1.0", "-0", "0", "0.5", "1.0", "1", "inf", "snan", "nan"],
+            decimals.map(|d| d.to_string())
+        );
+    }
+
+    #[test]
+    fn hash_map_key() {
+        use std::collections::{
+            HashMap,
+            HashSet,
+        };
+
+        let key = |d: &str| NumericEq(Bitstring::try_parse_str(d).unwrap());
+
+        let mut map = HashMap::new();
+
+        map.insert(key("1.0"), "a");
+        map.insert(key("-0"), "b");
+        map.insert(key("nan"), "c");
+
+        assert_eq!(Some(&"a"), map.get(&key("1")));
+        assert_eq!(Some(&"a"), map.get(&key("1.00")));
+        assert_eq!(Some(&"b"), map.get(&key("0e4")));
+        assert_eq!(Some(&"c"), map.get(&key("-snan")));
+        assert_eq!(None, map.get(&key("1.01")));
+
+        let numeric = ["1", "1.0", "1.00", "10e-1", "2"]
+            .into_iter()
+            .map(|d| NumericEq(Bitstring64::try_parse_str(d).unwrap()))
+            .collect::<HashSet<_>>();
+
+        assert_eq!(2, numeric.len());
+
+        let repr = ["1", "1.0", "1.00", "10e-1", "2", "1.0"]
+            .into_iter()
+            .map(|d| ReprEq(Bitstring64::try_parse_str(d).unwrap()))
+            .collect::<HashSet<_>>();
+
+        // `10e-1` and `1.0` have the same encoding
+        assert_eq!(4, repr.len());
+    }
+
+    #[test]
+    fn hash_repr() {
+        use std::hash::{
+            BuildHasher,
+            RandomState,
+        };
+
+        let state = RandomState::new();
+        let hash = |d: &str| {
+            let mut hasher = state.build_hasher();
+            Bitstring128::try_parse_str(d)
+                .unwrap()
+                .hash_repr(&mut hasher);
+            std::hash::Hasher::finish(&hasher)
+        };
+
+        assert_eq!(hash("1.0"), hash("1.0"));
+        assert_ne!(hash("1.0"), hash("1.00"));
+        assert_ne!(hash("0"), hash("-0"));
+    }
+
+    #[test]
+    fn add_sub() {
+        fn check<D: FromStr<Err = Error> + fmt::Display>(
+            op: impl Fn(&D, &D) -> D,
+            a: &str,
+            b: &str,
+            expected: &str,
+        ) {
+            let a: D = a.parse().expect("failed to parse decimal");
+            let b: D = b.parse().expect("failed to parse decimal");
+
+            assert_eq!(expected, op(&a, &b).to_string());
+        }
+
+        check(Bitstring32::add, "1.0", "1.00", "2.00");
+        check(Bitstring32::sub, "1", "1.5", "-0.5");
+        check(Bitstring32::add, "9999999", "1", "1.000000e7");
+        check(Bitstring32::add, "9.999999e96", "1e90", "inf");
+        check(Bitstring32::sub, "inf", "inf", "nan");
+
+        check(Bitstring64::add, "0.1", "0.2", "0.3");
+        check(Bitstring64::add, "1", "1e-20", "1.000000000000000");
+        check(Bitstring64::sub, "nan(42)", "1", "nan(42)");
+
+        check(
+            Bitstring128::add,
+            "9999999999999999999999999999999999",
+            "1",
+            "1.000000000000000000000000000000000e34",
+        );
+        check(Bitstring128::sub, "-0", "0", "-0");
+
+        // Bitstring grows to fit its result, and rounds to the precision of its widest width
+        check(Bitstring::add, "9999999999999999", "1", "10000000000000000");
+        check(
+            Bitstring::add,
+            "1",
+            "1e-50",
+            "1.000000000000000000000000000000000000000000",
+        );
+
+        let d = Bitstring::try_parse_str("9999999")
+            .unwrap()
+            .add(&Bitstring::try_parse_str("1").unwrap());
+        assert_eq!(8, d.as_le_bytes().len());
+    }
+
+    #[test]
+    fn mul_fma() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("3.60", d("1.20").mul(&d("3")).to_string());
+        assert_eq!("-0.0", d("0.9").mul(&d("-0")).to_string());
+        assert_eq!("inf", d("1e300").mul(&d("1e300")).to_string());
+        assert_eq!("nan", d("inf").mul(&d("0")).to_string());
+
+        // qty * price + fee
+        assert_eq!(
+            "37.4850",
+            d("3").fma(&d("12.4950"), &d("0.0000")).to_string()
+        );
+
+        // Rounding once gives a different result to rounding twice
+        let (a, b, c) = (d("99999999.5"), d("100000000.5"), d("-1e16"));
+
+        assert_eq!("-0.25", a.fma(&b, &c).to_string());
+        assert_eq!("0e1", a.mul(&b).add(&c).to_string());
+
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("1.111110e7", d("1234567").mul(&d("9")).to_string());
+
+        let d = |s: &str| Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!(
+            "1.219326311370217943225118122210029e35",
+            d("123456789012345678")
+                .mul(&d("987654321098765432"))
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn div_rem() {
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("4.00", d("8.00").div(&d("2")).to_string());
+        assert_eq!("0.3333333", d("1").div(&d("3")).to_string());
+        assert_eq!("-inf", d("-1").div(&d("0")).to_string());
+        assert_eq!("inf", d("-1").div(&d("-0")).to_string());
+        assert_eq!("nan", d("0").div(&d("0")).to_string());
+
+        assert_eq!("3", d("10").div_integer(&d("3")).to_string());
+        assert_eq!("nan", d("1e10").div_integer(&d("1")).to_string());
+
+        assert_eq!("1.0", d("3.6").rem(&d("1.3")).to_string());
+        assert_eq!("-0.3", d("3.6").remainder(&d("1.3")).to_string());
+        assert_eq!("nan", d("1").rem(&d("0")).to_string());
+
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("0.3333333333333333", d("1").div(&d("3")).to_string());
+
+        let d = |s: &str| Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!(
+            "0.6666666666666666666666666666666667",
+            d("2").div(&d("3")).to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn div_rem_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        // The precision of the result is the precision of the wider operand
+        assert_eq!("0.3333333", d("1").div(&d("3")).to_string());
+        assert_eq!(
+            "0.3333333333333333333333333333333333333333333",
+            d("1.00000000000000000000000000000000000000000")
+                .div(&d("3"))
+                .to_string()
+        );
+
+        assert_eq!("4.00", d("8.00").div(&d("2")).to_string());
+        assert_eq!("3", d("10").div_integer(&d("3")).to_string());
+        assert_eq!("1", d("10").rem(&d("3")).to_string());
+        assert_eq!("-2", d("10").remainder(&d("6")).to_string());
+        assert_eq!("-inf", d("1").div(&d("-0")).to_string());
+
+        // Large exponents are supported
+        assert_eq!("5e9999999999", d("1e10000000000").div(&d("2")).to_string());
+        assert_eq!(
+            "1e199999999998",
+            d("1e99999999999").div(&d("1e-99999999999")).to_string()
+        );
+        assert_eq!(
+            "-1e-199999999998",
+            d("-1e-99999999999").div(&d("1e99999999999")).to_string()
+        );
+        assert_eq!(
+            "1e800000000000",
+            d("1e400000000000").div(&d("1e-400000000000")).to_string()
+        );
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ops() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("3.5", (d("1.5") + d("2")).to_string());
+        assert_eq!("-0.5", (d("1.5") - &d("2")).to_string());
+        assert_eq!("3.0", (&d("1.5") * d("2")).to_string());
+        assert_eq!("0.75", (&d("1.5") / &d("2")).to_string());
+        assert_eq!("1.5", (d("1.5") % d("2")).to_string());
+        assert_eq!("-1.5", (-d("1.5")).to_string());
+        assert_eq!("0", (-&d("-0")).to_string());
+        assert_eq!("-inf", (-d("inf")).to_string());
+        assert_eq!("-nan(3)", (-d("nan(3)")).to_string());
+
+        let mut a = d("1");
+        a += d("2");
+        a -= &d("0.5");
+        a *= d("4");
+        a /= &d("5");
+        a %= d("1.5");
+
+        assert_eq!("0.5", a.to_string());
+
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("1.111110e7", (d("1234567") * d("9")).to_string());
+
+        let d = |s: &str| Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("0.3", (d("0.1") + d("0.2")).to_string());
+    }
+
+    #[test]
+    fn sum_product() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        let lines = [d("10.00"), d("5.50"), d("0.25")];
+
+        assert_eq!("15.75", lines.iter().sum::<Bitstring64>().to_string());
+        assert_eq!(
+            "13.750000",
+            lines.iter().product::<Bitstring64>().to_string()
+        );
+        assert_eq!("15.75", lines.into_iter().sum::<Bitstring64>().to_string());
+
+        // The exponent of the sum isn't affected by a starting zero
+        assert_eq!("1e5", [d("1e5")].iter().sum::<Bitstring64>().to_string());
+        assert_eq!("-0", [d("-0")].iter().sum::<Bitstring64>().to_string());
+
+        assert_eq!(
+            "0",
+            core::iter::empty::<Bitstring32>()
+                .sum::<Bitstring32>()
+                .to_string()
+        );
+        assert_eq!(
+            "1",
+            core::iter::empty::<Bitstring128>()
+                .product::<Bitstring128>()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn context_rounding() {
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+
+        for (mode, tie, neg_tie, zero_five) in [
+            (
+                RoundingMode::HalfEven,
+                "1.234566e7",
+                "-1.234566e7",
+                "1.234565e7",
+            ),
+            (
+                RoundingMode::HalfUp,
+                "1.234567e7",
+                "-1.234567e7",
+                "1.234565e7",
+            ),
+            (
+                RoundingMode::HalfDown,
+                "1.234566e7",
+                "-1.234566e7",
+                "1.234565e7",
+            ),
+            (RoundingMode::Up, "1.234567e7", "-1.234567e7", "1.234566e7"),
+            (
+                RoundingMode::Down,
+                "1.234566e7",
+                "-1.234566e7",
+                "1.234565e7",
+            ),
+            (
+                RoundingMode::Ceiling,
+                "1.234567e7",
+                "-1.234566e7",
+                "1.234566e7",
+            ),
+            (
+                RoundingMode::Floor,
+                "1.234566e7",
+                "-1.234567e7",
+                "1.234565e7",
+            ),
+            (
+                RoundingMode::ZeroFiveUp,
+                "1.234566e7",
+                "-1.234566e7",
+                "1.234566e7",
+            ),
+        ] {
+            let mut ctx = Context::new().with_rounding(mode);
+
+            let r = d("1234566e1").add_with(&d("5"), &mut ctx).unwrap();
+            assert_eq!(tie, r.to_string(), "{:?}", mode);
+
+            let r = d("-1234566e1").sub_with(&d("5"), &mut ctx).unwrap();
+            assert_eq!(neg_tie, r.to_string(), "{:?}", mode);
+
+            let r = d("1234565e1").add_with(&d("1"), &mut ctx).unwrap();
+            assert_eq!(zero_five, r.to_string(), "{:?}", mode);
+
+            assert_eq!(Flags::INEXACT | Flags::ROUNDED, ctx.flags());
+        }
+
+        // Overflow depends on the rounding mode
+        let mut ctx = Context::new().with_rounding(RoundingMode::Down);
+        let r = d("9000000e90").mul_with(&d("10"), &mut ctx).unwrap();
+
+        assert_eq!("9.999999e96", r.to_string());
+        assert!(ctx.flags().contains(Flags::OVERFLOW | Flags::INEXACT));
+    }
+
+    #[test]
+    fn context_flags_traps() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        let mut ctx = Context::new();
+
+        // Exact results don't set any flags
+        d("1.5").add_with(&d("2.25"), &mut ctx).unwrap();
+        assert!(ctx.flags().is_empty());
+
+        // Flags are sticky
+        d("1").div_with(&d("3"), &mut ctx).unwrap();
+        d("1").div_with(&d("0"), &mut ctx).unwrap();
+        d("1").add_with(&d("1"), &mut ctx).unwrap();
+        assert_eq!(
+            Flags::INEXACT | Flags::ROUNDED | Flags::DIVISION_BY_ZERO,
+            ctx.flags()
+        );
+
+        ctx.clear_flags();
+        assert!(ctx.flags().is_empty());
+
+        d("inf").sub_with(&d("inf"), &mut ctx).unwrap();
+        assert_eq!(Flags::INVALID, ctx.flags());
+
+        // Trapped flags return errors, but are still recorded
+        let mut ctx = Context::new().with_traps(Flags::DIVISION_BY_ZERO | Flags::INVALID);
+
+        assert!(d("1").div_with(&d("3"), &mut ctx).is_ok());
+        assert!(d("1").div_with(&d("0"), &mut ctx).is_err());
+        assert!(d("0").fma_with(&d("inf"), &d("1"), &mut ctx).is_err());
+        assert!(d("1").rem_with(&d("0"), &mut ctx).is_err());
+        assert_eq!(
+            Flags::INEXACT | Flags::ROUNDED | Flags::DIVISION_BY_ZERO | Flags::INVALID,
+            ctx.flags()
+        );
+
+        let err = d("1").div_with(&d("0"), &mut ctx).unwrap_err();
+        assert_eq!(
+            "the calculation raised trapped conditions `DIVISION_BY_ZERO`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn context_limits() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        let mut ctx = Context::new().with_precision(5);
+        assert_eq!(
+            "0.33333",
+            d("1").div_with(&d("3"), &mut ctx).unwrap().to_string()
+        );
+        assert_eq!(
+            "1.2346e6",
+            d("1234567")
+                .mul_with(&d("1"), &mut ctx)
+                .unwrap()
+                .to_string()
+        );
+
+        let mut ctx = Context::new().with_emax(10);
+        assert_eq!(
+            "inf",
+            d("1e10").mul_with(&d("10"), &mut ctx).unwrap().to_string()
+        );
+        assert!(ctx.flags().contains(Flags::OVERFLOW));
+
+        let mut ctx = Context::new().with_emin(-10).with_precision(3);
+        assert_eq!(
+            "1e-12",
+            d("1.23e-12")
+                .add_with(&d("0"), &mut ctx)
+                .unwrap()
+                .to_string()
+        );
+        assert!(ctx.flags().contains(Flags::UNDERFLOW));
+
+        // A context can't widen the limits of a format
+        let mut ctx = Context::new().with_precision(50);
+        assert_eq!(
+            "0.3333333333333333",
+            d("1").div_with(&d("3"), &mut ctx).unwrap().to_string()
+        );
+
+        let mut ctx = Context::new().with_precision(usize::MAX);
+        assert_eq!("3", d("1").add_with(&d("2"), &mut ctx).unwrap().to_string());
+
+        // The exponent range of a context must overlap the exponent range of the format
+        let mut ctx = Context::new().with_emin(500);
+        assert!(d("1").add_with(&d("2"), &mut ctx).is_err());
+
+        let mut ctx = Context::new().with_emin(10).with_emax(-10);
+        assert!(d("1").add_with(&d("2"), &mut ctx).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn context_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        // The precision of the context can widen big decimals
+        let mut ctx = Context::new().with_precision(50);
+        assert_eq!(
+            "0.33333333333333333333333333333333333333333333333333",
+            d("1").div_with(&d("3"), &mut ctx).unwrap().to_string()
+        );
+
+        let mut ctx = Context::new()
+            .with_precision(3)
+            .with_rounding(RoundingMode::Up);
+        assert_eq!(
+            "0.334",
+            d("1").div_with(&d("3"), &mut ctx).unwrap().to_string()
+        );
+
+        let mut ctx = Context::new().with_precision(usize::MAX);
+        assert_eq!(
+            "0.3333333",
+            d("1").div_with(&d("3"), &mut ctx).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn context_conversions() {
+        let mut ctx = Context::new();
+
+        let d = Bitstring32::from_i64_with(123456789, &mut ctx).unwrap();
+        assert_eq!("1.234568e8", d.to_string());
+        assert_eq!(Flags::INEXACT | Flags::ROUNDED, ctx.flags());
+
+        let mut ctx = Context::new().with_rounding(RoundingMode::Floor);
+
+        let d = Bitstring32::from_i64_with(-123456789, &mut ctx).unwrap();
+        assert_eq!("-1.234568e8", d.to_string());
+
+        let d = Bitstring32::from_u32_with(1234567, &mut ctx).unwrap();
+        assert_eq!("1234567", d.to_string());
+
+        let d = Bitstring64::from_f64_with(0.1 + 0.2, &mut ctx).unwrap();
+        assert_eq!("0.3000000000000000", d.to_string());
+
+        let d = Bitstring32::from_f64_with(1e200, &mut ctx).unwrap();
+        assert_eq!("9.999999e96", d.to_string());
+        assert!(ctx.flags().contains(Flags::OVERFLOW));
+
+        let d = Bitstring32::from_f64_with(f64::NAN, &mut ctx).unwrap();
+        assert!(d.is_nan());
+
+        let mut ctx = Context::new().with_traps(Flags::INEXACT);
+        assert!(Bitstring64::from_i128_with(i128::MAX, &mut ctx).is_err());
+        assert!(Bitstring128::from_i128_with(i64::MAX as i128, &mut ctx).is_ok());
+    }
+
+    #[test]
+    fn functions_regenerate_constants() {
+        // Reference values from https://doc.rust-lang.org/stable/core/f64/consts/index.html
+        const E: &str = "2.71828182845904523536028747135266250";
+        const SQRT_2: &str = "1.41421356237309504880168872420969808";
+        const LOG10_2: &str = "0.301029995663981195213738894724493027";
+        const LOG10_E: &str = "0.434294481903251827651128918916605082";
+        const LN_2: &str = "0.693147180559945309417232121458176568";
+        const LN_10: &str = "2.30258509299404568401799145468436421";
+
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+        let r = |s: &str| {
+            Bitstring64::parse_rounded(s, RoundingMode::HalfEven)
+                .expect("failed to parse decimal")
+                .0
+        };
+
+        assert_eq!(Bitstring64::E.as_le_bytes(), d("1").exp().as_le_bytes());
+        assert_eq!(
+            Bitstring64::SQRT_2.as_le_bytes(),
+            d("2").sqrt().as_le_bytes()
+        );
+
+        assert_eq!(r(E).as_le_bytes(), d("1").exp().as_le_bytes());
+        assert_eq!(r(SQRT_2).as_le_bytes(), d("2").sqrt().as_le_bytes());
+        assert_eq!(r(LOG10_2).as_le_bytes(), d("2").log10().as_le_bytes());
+        assert_eq!(r(LOG10_E).as_le_bytes(), d("1").exp().log10().as_le_bytes());
+        assert_eq!(r(LN_2).as_le_bytes(), d("2").ln().as_le_bytes());
+        assert_eq!(r(LN_10).as_le_bytes(), d("10").ln().as_le_bytes());
+
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+        let r = |s: &str| {
+            Bitstring32::parse_rounded(s, RoundingMode::HalfEven)
+                .expect("failed to parse decimal")
+                .0
+        };
+
+        assert_eq!(r(E).as_le_bytes(), d("1").exp().as_le_bytes());
+        assert_eq!(r(SQRT_2).as_le_bytes(), d("2").sqrt().as_le_bytes());
+        assert_eq!(r(LN_2).as_le_bytes(), d("2").ln().as_le_bytes());
+        assert_eq!(r(LN_10).as_le_bytes(), d("10").ln().as_le_bytes());
+
+        let d = |s: &str| Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+        let r = |s: &str| {
+            Bitstring128::parse_rounded(s, RoundingMode::HalfEven)
+                .expect("failed to parse decimal")
+                .0
+        };
+
+        assert_eq!(r(E).as_le_bytes(), d("1").exp().as_le_bytes());
+        assert_eq!(r(SQRT_2).as_le_bytes(), d("2").sqrt().as_le_bytes());
+        assert_eq!(r(LOG10_2).as_le_bytes(), d("2").log10().as_le_bytes());
+        assert_eq!(r(LN_2).as_le_bytes(), d("2").ln().as_le_bytes());
+        assert_eq!(r(LN_10).as_le_bytes(), d("10").ln().as_le_bytes());
+    }
+
+    #[test]
+    fn functions() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("1.0", d("1.00").sqrt().to_string());
+        assert_eq!("1024", d("2").pow(&d("10")).to_string());
+        assert_eq!("0.25", d("2").pow(&d("-2")).to_string());
+        assert_eq!("1.414213562373095", d("2").pow(&d("0.5")).to_string());
+        assert_eq!("3", d("1000").log10().to_string());
+        assert_eq!("-inf", d("0").ln().to_string());
+        assert!(d("-1").sqrt().is_nan());
+        assert!(d("-1").ln().is_nan());
+        assert!(d("0").pow(&d("0")).is_nan());
+
+        let mut ctx = Context::new().with_rounding(RoundingMode::Floor);
+        assert_eq!(
+            "2.718281828459045",
+            d("1").exp_with(&mut ctx).unwrap().to_string()
+        );
+
+        let mut ctx = Context::new().with_rounding(RoundingMode::Ceiling);
+        assert_eq!(
+            "2.718281828459046",
+            d("1").exp_with(&mut ctx).unwrap().to_string()
+        );
+        assert_eq!(Flags::INEXACT | Flags::ROUNDED, ctx.flags());
+
+        let mut ctx = Context::new().with_precision(5);
+        assert_eq!("1.4142", d("2").sqrt_with(&mut ctx).unwrap().to_string());
+
+        let mut ctx = Context::new().with_traps(Flags::OVERFLOW);
+        assert!(d("1000").exp_with(&mut ctx).is_err());
+        assert!(d("10").pow_with(&d("400"), &mut ctx).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn functions_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        let sqrt_2 = d("2").sqrt(60);
+        assert_eq!(
+            "1.41421356237309504880168872420969807856967187537694807317668",
+            sqrt_2.to_string()
+        );
+
+        let ln_10 = d("10").ln(60);
+        assert_eq!(
+            "2.30258509299404568401799145468436420760110148862877297603333",
+            ln_10.to_string()
+        );
+
+        assert_eq!(
+            "0.301029995663981195213738894724493026768189881462108541310427",
+            d("2").log10(60).to_string()
+        );
+        assert_eq!(
+            "1.23456789012345678901234567890",
+            d("1.23456789012345678901234567890")
+                .exp(60)
+                .ln(30)
+                .to_string()
+        );
+        assert_eq!(
+            "1267650600228229401496703205376",
+            d("2").pow(&d("100"), 60).to_string()
+        );
+
+        // Functions of big decimals agree with fixed-width decimals at the same precision
+        let big = d("2").sqrt(34);
+        let fixed = Bitstring128::SQRT_2;
+        assert_eq!(fixed.to_string(), big.to_string());
+    }
+
+    #[test]
+    fn quantize() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("1.00", d("1").quantize(&d("0.01")).to_string());
+        assert_eq!("1.24", d("1.235").quantize(&d("0.01")).to_string());
+        assert_eq!("-0.00", d("-0.001").quantize(&d("0.01")).to_string());
+        assert_eq!("1e2", d("123").rescale(2, RoundingMode::Down).to_string());
+        assert_eq!("2e2", d("123").rescale(2, RoundingMode::Up).to_string());
+        assert!(d("1").rescale(-16, RoundingMode::HalfEven).is_nan());
+        assert!(d("inf").quantize(&d("1")).is_nan());
+
+        assert!(d("1.00").same_quantum(&d("-9.99")));
+        assert!(!d("1.0").same_quantum(&d("1.00")));
+
+        let mut ctx = Context::new().with_traps(Flags::INEXACT);
+        assert!(d("1.23").rescale_with(-1, &mut ctx).is_err());
+        assert!(d("1.20").rescale_with(-1, &mut ctx).is_ok());
+
+        let mut ctx = Context::new().with_traps(Flags::INVALID);
+        assert!(d("1").quantize_with(&d("1e-20"), &mut ctx).is_err());
+
+        let d = |s: &str| Bitstring::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!(
+            "1.20",
+            d("1.2").rescale(-2, RoundingMode::HalfEven).to_string()
+        );
+        assert!(d("1e3").same_quantum(&d("5e3")));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn quantize_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("1.00", d("1").quantize(&d("0.01")).to_string());
+        assert!(d("1").rescale(-20, RoundingMode::HalfEven).is_nan());
+
+        // The precision of the context can widen big decimals to fit the new exponent
+        let mut ctx = Context::new().with_precision(21);
+        assert_eq!(
+            "1.00000000000000000000",
+            d("1").rescale_with(-20, &mut ctx).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn reduce() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!("1.2e3", d("1.200e3").reduce().to_string());
+        assert_eq!(
+            d("1.200e3").reduce().as_le_bytes(),
+            d("1200.0000").reduce().as_le_bytes()
+        );
+        assert_eq!("-0", d("-0.000").reduce().to_string());
+        assert_eq!("inf", d("inf").reduce().to_string());
+        assert!(d("snan").reduce().is_nan());
+
+        assert_eq!("1.20", d("1.2000").trim_to_scale(2).to_string());
+        assert_eq!("1.2", d("1.2").trim_to_scale(2).to_string());
+        assert_eq!("1.2e3", d("1200.00").trim_to_scale(-2).to_string());
+        assert_eq!("1200", d("1200.00").trim_to_scale(0).to_string());
+
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("1.0e91", d("1000000e85").reduce().to_string());
+
+        let d = |s: &str| Bitstring::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!(
+            "1.5",
+            d("1.50000000000000000000000000").reduce().to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn reduce_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!(
+            "1.5",
+            d("1.5000000000000000000000000000000000000000000000000000")
+                .reduce()
+                .to_string()
+        );
+        assert_eq!("1.50", d("1.5000").trim_to_scale(2).to_string());
+    }
+
+    #[test]
+    fn round_to_integral() {
+        let d = |s: &str| Bitstring32::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!(Some(2), d("17e-1").round().to_i32());
+        assert_eq!(Some(1), d("17e-1").trunc().to_i32());
+        assert_eq!(
+            "-2",
+            d("-1.5")
+                .round_to_integral(RoundingMode::HalfEven)
+                .to_string()
+        );
+        assert_eq!("-2", d("-1.5").round().to_string());
+        assert_eq!("-3", d("-2.5").round().to_string());
+        assert_eq!(
+            "-2",
+            d("-2.5")
+                .round_to_integral(RoundingMode::HalfEven)
+                .to_string()
+        );
+        assert_eq!("-1", d("-1.5").trunc().to_string());
+        assert_eq!("-2", d("-1.5").floor().to_string());
+        assert_eq!("-1", d("-1.5").ceil().to_string());
+        assert_eq!("-0", d("-0.5").ceil().to_string());
+        assert_eq!("1.2e3", d("1.2e3").floor().to_string());
+        assert_eq!("inf", d("inf").floor().to_string());
+
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("123457", d("123456.789").round().to_string());
+
+        let d = |s: &str| Bitstring128::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!("-1", d("-1e-34").floor().to_string());
+
+        let d = |s: &str| Bitstring::try_parse_str(s).expect("failed to parse decimal");
+        assert_eq!(
+            "12345678901234567890123456789",
+            d("12345678901234567890123456788.5").ceil().to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn round_to_integral_big() {
+        let d = |s: &str| BigBitstring::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!(
+            "123456789012345678901234567890123456789012345678901",
+            d("123456789012345678901234567890123456789012345678900.5")
+                .round()
+                .to_string()
+        );
+        assert_eq!(Some(2), d("17e-1").round().to_i32());
+    }
+
+    #[test]
+    fn to_int_rounded_saturating_truncated() {
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert_eq!(Some(13), d("12.7").to_i64_rounded(RoundingMode::HalfEven));
+        assert_eq!(Some(12), d("12.5").to_i64_rounded(RoundingMode::HalfEven));
+        assert_eq!(Some(-13), d("-12.5").to_i64_rounded(RoundingMode::HalfUp));
+        assert_eq!(Some(0), d("-0.4").to_u8_rounded(RoundingMode::HalfEven));
+        assert_eq!(None, d("-0.6").to_u8_rounded(RoundingMode::HalfEven));
+        assert_eq!(None, d("255.5").to_u8_rounded(RoundingMode::HalfEven));
+        assert_eq!(Some(255), d("255.5").to_u8_rounded(RoundingMode::Down));
+
+        assert_eq!(Some(12), d("12.7").to_i64_truncated());
+        assert_eq!(Some(-12), d("-12.7").to_i64_truncated());
+        assert_eq!(None, d("1e40").to_i64_truncated());
+        assert_eq!(None, d("inf").to_i64_truncated());
+
+        assert_eq!(i64::MAX, d("1e40").to_i64_saturating());
+        assert_eq!(i64::MIN, d("-1e40").to_i64_saturating());
+        assert_eq!(i64::MAX, d("inf").to_i64_saturating());
+        assert_eq!(i64::MIN, d("-inf").to_i64_saturating());
+        assert_eq!(0, d("nan").to_i64_saturating());
+        assert_eq!(0, d("-12.7").to_u32_saturating());
+        assert_eq!(-12, d("-12.7").to_i8_saturating());
+        assert_eq!(u128::MAX, d("1e300").to_u128_saturating());
+
+        // Converting infinities and NaNs is an error rather than a panic
+        assert!(d("nan").to_i64().is_none());
+        assert!(i64::try_from(d("-inf")).is_err());
+    }
+
+    #[test]
+    fn scaled_int() {
+        let d = Bitstring64::from_scaled_i64(-1234500, 4).expect("failed to convert decimal");
+        assert_eq!("-123.4500", d.to_string());
+        assert_eq!(-1234500, d.to_scaled_i64(4).unwrap());
+        assert_eq!(-12345, d.to_scaled_i64(2).unwrap());
+        assert_eq!(-123450000000, d.to_scaled_i128(9).unwrap());
+        assert!(d.to_scaled_i64(1).is_err());
+
+        // Negative scales work with positive exponents
+        let d = Bitstring32::from_scaled_i64(12, -3).expect("failed to convert decimal");
+        assert_eq!("1.2e4", d.to_string());
+        assert_eq!(12000, d.to_scaled_i64(0).unwrap());
+        assert_eq!(12, d.to_scaled_i64(-3).unwrap());
+
+        // Zeros convert regardless of scale
+        assert_eq!(0, Bitstring64::ZERO.to_scaled_i64(400).unwrap());
+        assert_eq!(
+            0,
+            Bitstring64::try_parse_str("0e-300")
+                .unwrap()
+                .to_scaled_i64(0)
+                .unwrap()
+        );
+
+        // Integers with more digits than the precision aren't rounded
+        assert!(Bitstring32::from_scaled_i64(12345678, 4).is_err());
+        assert!(Bitstring64::from_scaled_i64(i64::MAX, 4).is_err());
+        assert!(Bitstring128::from_scaled_i64(i64::MAX, 4).is_ok());
+
+        // The exponent must be in range
+        assert!(Bitstring32::from_scaled_i64(1, 102).is_err());
+        assert!(Bitstring32::from_scaled_i64(1, 101).is_ok());
+
+        // Overflowing the integer is an error
+        assert!(Bitstring64::try_parse_str("1e10")
+            .unwrap()
+            .to_scaled_i64(10)
+            .is_err());
+        assert_eq!(
+            10i128.pow(20),
+            Bitstring64::try_parse_str("1e10")
+                .unwrap()
+                .to_scaled_i128(10)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn parts() {
+        let d = Bitstring128::try_parse_str("9.999999999999999999999999999999999e6144").unwrap();
+        assert_eq!(
+            Some((false, 9999999999999999999999999999999999, 6111)),
+            d.to_parts()
+        );
+
+        assert_eq!(
+            Some((true, 0, -101)),
+            Bitstring32::try_parse_str("-0e-101").unwrap().to_parts()
+        );
+        assert_eq!(None, Bitstring32::try_parse_str("inf").unwrap().to_parts());
+        assert_eq!(
+            None,
+            Bitstring32::try_parse_str("nan(1)").unwrap().to_parts()
+        );
+
+        // Coefficients are never rounded
+        assert!(Bitstring32::from_parts(false, 12345678, 0).is_err());
+        assert!(Bitstring128::from_parts(false, u128::MAX, 0).is_err());
+
+        // Exponents are never clamped
+        assert!(Bitstring32::from_parts(false, 1, 91).is_err());
+        assert!(Bitstring64::from_parts(true, 1, i32::MIN).is_err());
+        assert!(Bitstring64::from_parts(true, 1, i32::MAX).is_err());
+        assert!(Bitstring::from_parts(true, u128::MAX, i32::MIN).is_err());
+        assert!(Bitstring::from_parts(true, u128::MAX, i32::MAX).is_err());
+
+        // Dynamic decimals can have coefficients too big for a `u128`
+        let d = Bitstring::from_parts(false, u128::MAX, 0).unwrap();
+        assert_eq!(u128::MAX.to_string(), d.to_string());
+
+        let d = Bitstring::try_parse_str(&format!("{}0", u128::MAX)).unwrap();
+        assert_eq!(None, d.to_parts());
+    }
+
+    #[test]
+    fn digits() {
+        let d = Bitstring128::try_parse_str("-1.234000e-6100").unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 0, 0, 0], d.digits().collect::<Vec<_>>());
+        assert_eq!(7, d.significant_digits());
+        assert_eq!(Some(-6106), d.exponent());
+        assert_eq!(Some(6106), d.scale());
+        assert_eq!(Some(-6100), d.adjusted_exponent());
+
+        // Leading zeros aren't significant
+        let d = Bitstring32::try_parse_str("0.00120").unwrap();
+        assert_eq!(3, d.significant_digits());
+        assert_eq!(Some(-3), d.adjusted_exponent());
+
+        // Zeros have a single digit
+        let d = Bitstring32::try_parse_str("0e5").unwrap();
+        assert_eq!(vec![0], d.digits().collect::<Vec<_>>());
+        assert_eq!(Some(5), d.adjusted_exponent());
+        assert_eq!(Some(-5), d.scale());
+
+        let d = Bitstring32::try_parse_str("nan(123)").unwrap();
+        assert_eq!(0, d.digits().count());
+        assert_eq!(None, d.scale());
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn digits_big() {
+        use num_bigint::BigInt;
+
+        let d = BigBitstring::try_parse_str("1.5e-100000").unwrap();
+        assert_eq!(vec![1, 5], d.digits().collect::<Vec<_>>());
+        assert_eq!(Some(BigInt::from(100001)), d.scale());
+        assert_eq!(Some(BigInt::from(-100000)), d.adjusted_exponent());
+    }
+
+    #[test]
+    fn classify() {
+        use core::num::FpCategory;
+
+        let d = |s: &str| Bitstring64::try_parse_str(s).expect("failed to parse decimal");
+
+        assert!(d("-0.00").is_zero());
+        assert!(!d("1e-398").is_zero());
+        assert!(!d("nan").is_zero());
+
+        assert!(d("100e-2").is_integer());
+        assert!(d("-1.2e5").is_integer());
+        assert!(!d("1.01").is_integer());
+        assert!(!d("inf").is_integer());
+
+        assert!(d("1e-398").is_subnormal());
+        assert!(!d("1e-398").is_normal());
+        assert!(d("1e-383").is_normal());
+        assert!(d("9.999999999999999e384").is_normal());
+        assert!(!d("0").is_normal());
+        assert!(!d("0").is_subnormal());
+
+        assert_eq!(DecimalCategory::SignalingNan, d("-snan").classify());
+        assert_eq!(DecimalCategory::QuietNan, d("nan(1)").classify());
+        assert_eq!(DecimalCategory::NegativeInfinity, d("-inf").classify());
+        assert_eq!(DecimalCategory::NegativeNormal, d("-1").classify());
+        assert_eq!(DecimalCategory::NegativeSubnormal, d("-1e-384").classify());
+        assert_eq!(DecimalCategory::PositiveZero, d("0e100").classify());
+
+        assert_eq!(
+            FpCategory::Subnormal,
+            FpCategory::from(d("1e-384").classify())
+        );
+        assert_eq!(FpCategory::Nan, FpCategory::from(d("snan").classify()));
+
+        // The normal range depends on the width of the decimal
+        let d = Bitstring128::try_parse_str("1e-384").unwrap();
+        assert_eq!(DecimalCategory::PositiveNormal, d.classify());
+        assert!(Bitstring128::try_parse_str("1e-6144")
+            .unwrap()
+            .is_subnormal());
+
+        // Decimals produced by this library are always canonical
+        assert!(Bitstring64::PI.is_canonical());
+
+        let mut bytes = *Bitstring32::try_parse_str("999").unwrap().as_le_bytes();
+        bytes[1] |= 0b0000_0011;
+        let d = Bitstring32::from_le_bytes(bytes);
+        assert!(!d.is_canonical());
+        assert_eq!("999", d.to_string());
+    }
+
+    #[test]
+    fn canonical() {
+        let canonical = *Bitstring64::try_parse_str("-9.99e-300")
+            .unwrap()
+            .as_le_bytes();
+        assert!(Bitstring64::try_from_le_bytes_canonical(canonical).is_ok());
+
+        let mut bytes = canonical;
+        bytes[1] |= 0b0000_0011;
+
+        let d = Bitstring64::from_le_bytes(bytes);
+        assert!(!d.is_canonical());
+        assert!(Bitstring64::try_from_le_bytes_canonical(bytes).is_err());
+        assert_eq!(&canonical, d.canonicalize().as_le_bytes());
+
+        // Dynamic decimals check their width as well as their encoding
+        assert!(Bitstring::try_from_le_bytes_canonical(&canonical).is_ok());
+        assert!(Bitstring::try_from_le_bytes_canonical(&bytes).is_err());
+        assert!(Bitstring::try_from_le_bytes_canonical(&bytes[..7]).is_err());
+
+        // Infinities and NaNs ignore some of their bits
+        let d = Bitstring128::from_le_bytes([0xff; 16]);
+        assert!(!d.is_canonical());
+        assert_eq!(
+            "-snan",
+            d.canonicalize().to_string().split('(').next().unwrap()
+        );
+
+        let d = Bitstring32::from_le_bytes([1, 0, 0, 0x78]);
+        assert!(!d.is_canonical());
+        assert_eq!(
+            Bitstring32::INFINITY.as_le_bytes(),
+            d.canonicalize().as_le_bytes()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn canonical_big() {
+        let canonical = Bitstring64::try_parse_str("999").unwrap();
+        let mut bytes = *canonical.as_le_bytes();
+        bytes[1] |= 0b0000_0011;
+
+        let d = BigBitstring::try_from_le_bytes(&bytes).unwrap();
+        assert!(!d.is_canonical());
+        assert!(BigBitstring::try_from_le_bytes_canonical(&bytes).is_err());
+        assert_eq!(canonical.as_le_bytes(), d.canonicalize().as_le_bytes());
+    }
+
+    #[test]
+    fn bid() {
+        // Vectors from Intel's decimal library
+        assert_eq!(
+            0x3280_0001u32.to_le_bytes(),
+            Bitstring32::ONE.to_bid_bytes()
+        );
+        assert_eq!(
+            0x31c0_0000_0000_0001u64.to_le_bytes(),
+            Bitstring64::ONE.to_bid_bytes()
+        );
+        assert_eq!(
+            0x3040_0000_0000_0000_0000_0000_0000_0001u128.to_le_bytes(),
+            Bitstring128::ONE.to_bid_bytes()
+        );
+
+        assert_eq!(
+            Bitstring64::MAX.as_le_bytes(),
+            Bitstring64::from_bid_bytes(0x77f3_86f2_6fc0_ffffu64.to_le_bytes()).as_le_bytes()
+        );
+        assert_eq!(
+            Bitstring64::MIN_POSITIVE.as_le_bytes(),
+            Bitstring64::from_bid_bytes(1u64.to_le_bytes()).as_le_bytes()
+        );
+
+        // Non-canonical significands are zero
+        let d = Bitstring64::from_bid_bytes(0x6fff_ffff_ffff_ffffu64.to_le_bytes());
+        assert!(d.is_zero());
+        assert!(d.is_canonical());
+
+        // Non-canonical DPD declets are canonicalized
+        let mut bytes = *Bitstring32::try_parse_str("999").unwrap().as_le_bytes();
+        bytes[1] |= 0b0000_0011;
+
+        assert_eq!(
+            0x3280_03e7u32.to_le_bytes(),
+            Bitstring32::from_le_bytes(bytes).to_bid_bytes()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bson")]
+    fn bson() {
+        use std::str::FromStr;
+
+        // bson formats decimals following the General Decimal Arithmetic specification
+        for (d, expected) in [
+            ("0", "0"),
+            ("-0.00", "-0.00"),
+            ("1.20", "1.20"),
+            ("1.05e3", "1.05E+3"),
+            ("-1e-6176", "-1E-6176"),
+            (
+                "9.999999999999999999999999999999999e6144",
+                "9.999999999999999999999999999999999E+6144",
+            ),
+            ("inf", "Infinity"),
+            ("-inf", "-Infinity"),
+            ("nan", "NaN"),
+        ] {
+            let d = Bitstring128::try_parse_str(d).unwrap();
+
+            let bson = ::bson::Decimal128::from(d);
+            assert_eq!(expected, bson.to_string());
+
+            let bson = ::bson::Decimal128::from_str(expected).unwrap();
+            assert_eq!(d.as_le_bytes(), Bitstring128::from(bson).as_le_bytes());
+
+            let dynamic = Bitstring::from(bson);
+            assert_eq!(d.to_string(), dynamic.to_string());
+            assert_eq!(
+                bson.bytes(),
+                ::bson::Decimal128::try_from(dynamic).unwrap().bytes()
+            );
+
+            #[cfg(feature = "arbitrary-precision")]
+            {
+                let big = BigBitstring::from(bson);
+                assert_eq!(d.to_string(), big.to_string());
+                assert_eq!(
+                    bson.bytes(),
+                    ::bson::Decimal128::try_from(big).unwrap().bytes()
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "bson", feature = "arbitrary-precision"))]
+    fn bson_exponent_limits() {
+        // Exponents near the limits of an `i32` underflow or overflow without overflowing
+        // while they're rounded
+        for (s, expected, expected_flags) in [
+            (
+                "1234567890e2147483640",
+                "inf",
+                Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "-12345e2147483645",
+                "-inf",
+                Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "1e2147483647",
+                "inf",
+                Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED,
+            ),
+            (
+                "1234567890e-2147483640",
+                "0e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED | Flags::CLAMPED,
+            ),
+            (
+                "-12345e-2147483645",
+                "-0e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED | Flags::CLAMPED,
+            ),
+            (
+                "1e-2147483648",
+                "0e-6176",
+                Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED | Flags::CLAMPED,
+            ),
+        ] {
+            let d = BigBitstring::try_parse_str(s).unwrap();
+
+            let mut ctx = Context::new();
+
+            let bson = d.to_bson_decimal128_with(&mut ctx).unwrap();
+
+            assert_eq!(expected, Bitstring128::from(bson).to_string(), "{}", s);
+            assert_eq!(expected_flags, ctx.flags(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn pg_numeric() {
+        // Vectors captured from `numeric_send` in Postgres 15
+        for (d, bytes) in [
+            ("0", &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00][..]),
+            ("0.00", &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02]),
+            ("0.00000", &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05]),
+            (
+                "1",
+                &[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+            ),
+            (
+                "10000",
+                &[0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01],
+            ),
+            (
+                "-0.0001",
+                &[0x00, 0x01, 0xff, 0xff, 0x40, 0x00, 0x00, 0x04, 0x00, 0x01],
+            ),
+            (
+                "0.0012",
+                &[0x00, 0x01, 0xff, 0xff, 0x00, 0x00, 0x00, 0x04, 0x00, 0x0c],
+            ),
+            (
+                "0.00001234",
+                &[0x00, 0x01, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x08, 0x04, 0xd2],
+            ),
+            (
+                "1.20",
+                &[
+                    0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x07, 0xd0,
+                ],
+            ),
+            (
+                "3950.123456",
+                &[
+                    0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x06, 0x0f, 0x6e, 0x04, 0xd2, 0x15,
+                    0xe0,
+                ],
+            ),
+            (
+                "12345.6789",
+                &[
+                    0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04, 0x00, 0x01, 0x09, 0x29, 0x1a,
+                    0x85,
+                ],
+            ),
+            (
+                "-100000000",
+                &[0x00, 0x01, 0x00, 0x02, 0x40, 0x00, 0x00, 0x00, 0x00, 0x01],
+            ),
+            ("nan", &[0x00, 0x00, 0x00, 0x00, 0xc0, 0x00, 0x00, 0x00]),
+            ("inf", &[0x00, 0x00, 0x00, 0x00, 0xd0, 0x00, 0x00, 0x20]),
+            ("-inf", &[0x00, 0x00, 0x00, 0x00, 0xf0, 0x00, 0x00, 0x20]),
+        ] {
+            let dynamic = Bitstring::try_from_pg_numeric_bytes(bytes).unwrap();
+            assert_eq!(d, dynamic.to_string());
+            assert_eq!(
+                bytes,
+                dynamic.to_pg_numeric_bytes().unwrap().collect::<Vec<_>>()
+            );
+
+            #[cfg(feature = "arbitrary-precision")]
+            {
+                let big = BigBitstring::try_from_pg_numeric_bytes(bytes).unwrap();
+                assert_eq!(d, big.to_string());
+                assert_eq!(
+                    bytes,
+                    big.to_pg_numeric_bytes().unwrap().collect::<Vec<_>>()
+                );
+            }
+        }
+
+        // Postgres doesn't have negative zero, so a zero with a negative sign is positive
+        for (d, bytes) in [
+            ("0", &[0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00][..]),
+            ("0.00", &[0x00, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x02]),
+            (
+                "0",
+                &[0x00, 0x01, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00],
+            ),
+        ] {
+            assert_eq!(
+                d,
+                Bitstring::try_from_pg_numeric_bytes(bytes)
+                    .unwrap()
+                    .to_string()
+            );
+
+            #[cfg(feature = "arbitrary-precision")]
+            assert_eq!(
+                d,
+                BigBitstring::try_from_pg_numeric_bytes(bytes)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+
+        // Values that `NUMERIC` can't represent exactly
+        for (d, expected) in [("-0.0", "0.0"), ("1.2e3", "1200"), ("-snan(42)", "nan")] {
+            let dynamic = Bitstring::try_parse_str(d).unwrap();
+
+            let bytes = dynamic.to_pg_numeric_bytes().unwrap().collect::<Vec<_>>();
+            assert_eq!(
+                expected,
+                Bitstring::try_from_pg_numeric_bytes(&bytes)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+
+        // The exponent is out of range of `NUMERIC`
+        assert!(Bitstring::try_parse_str("1e-16384")
+            .unwrap()
+            .to_pg_numeric_bytes()
+            .is_err());
+
+        // The number has too many digits for a `Bitstring`
+        let mut bytes = vec![0x00, 0x0c, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x00];
+        bytes.extend([0x00, 0x01].repeat(12));
+        assert!(Bitstring::try_from_pg_numeric_bytes(&bytes).is_err());
+
+        #[cfg(feature = "arbitrary-precision")]
+        {
+            let big = BigBitstring::try_from_pg_numeric_bytes(&bytes).unwrap();
+            assert_eq!(45, big.significant_digits());
+            assert_eq!(
+                bytes,
+                big.to_pg_numeric_bytes().unwrap().collect::<Vec<_>>()
+            );
+        }
+
+        // Malformed bytes
+        for bytes in [
+            &[0x00, 0x00, 0x00][..],
+            &[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+            &[0x00, 0x00, 0x00, 0x00, 0x12, 0x34, 0x00, 0x00],
+            &[0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x27, 0x10],
+            &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0x00],
+        ] {
+            assert!(
+                Bitstring::try_from_pg_numeric_bytes(bytes).is_err(),
+                "{:?}",
+                bytes
+            );
+        }
+    }
+
+    #[test]
+    fn ibm_dfp() {
+        for (d, bytes) in [
+            ("1", &[0x22, 0x50, 0x00, 0x01][..]),
+            ("-0", &[0xa2, 0x50, 0x00, 0x00]),
+            ("1", &[0x22, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            ("123.45", &[0x22, 0x30, 0x00, 0x00, 0x00, 0x00, 0x49, 0xc5]),
+            ("-0", &[0xa2, 0x38, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            ("inf", &[0x78, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            ("nan", &[0x7c, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+            (
+                "1",
+                &[
+                    0x22, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                    0x00, 0x00, 0x01,
+                ],
+            ),
+        ] {
+            match bytes.len() {
+                4 => {
+                    let d32 = Bitstring32::from_ibm_dfp_bytes(bytes.try_into().unwrap());
+                    assert_eq!(d, d32.to_string());
+                    assert_eq!(bytes, d32.to_ibm_dfp_bytes());
+                }
+                8 => {
+                    let d64 = Bitstring64::from_ibm_dfp_bytes(bytes.try_into().unwrap());
+                    assert_eq!(d, d64.to_string());
+                    assert_eq!(bytes, d64.to_ibm_dfp_bytes());
+                }
+                16 => {
+                    let d128 = Bitstring128::from_ibm_dfp_bytes(bytes.try_into().unwrap());
+                    assert_eq!(d, d128.to_string());
+                    assert_eq!(bytes, d128.to_ibm_dfp_bytes());
+                }
+                _ => unreachable!(),
+            }
+
+            let dynamic = Bitstring::try_from_ibm_dfp_bytes(bytes).unwrap();
+            assert_eq!(d, dynamic.to_string());
+            assert_eq!(bytes, dynamic.to_ibm_dfp_bytes().collect::<Vec<_>>());
+
+            #[cfg(feature = "arbitrary-precision")]
+            {
+                let big = BigBitstring::try_from_ibm_dfp_bytes(bytes).unwrap();
+                assert_eq!(d, big.to_string());
+                assert_eq!(bytes, big.to_ibm_dfp_bytes().collect::<Vec<_>>());
+            }
+        }
+
+        assert!(Bitstring::try_from_ibm_dfp_bytes(&[0x22, 0x50, 0x00]).is_err());
+    }
+
+    #[test]
+    fn nan_payload() {
+        let d = Bitstring128::nan_with_payload(10u128.pow(33) - 1, false).unwrap();
+        assert_eq!(Some(10u128.pow(33) - 1), d.nan_payload());
+        assert!(Bitstring128::nan_with_payload(10u128.pow(33), false).is_err());
+        assert!(Bitstring32::nan_with_payload(1_000_000, false).is_err());
+
+        assert_eq!(
+            Some(0),
+            Bitstring64::try_parse_str("-nan").unwrap().nan_payload()
+        );
+        assert_eq!(
+            None,
+            Bitstring64::try_parse_str("-inf").unwrap().nan_payload()
+        );
+
+        // Payloads survive arithmetic
+        let d = Bitstring64::nan_with_payload(7, false).unwrap() + Bitstring64::ONE;
+        assert_eq!(Some(7), d.nan_payload());
+
+        // The sign is kept when quietening
+        let d = Bitstring64::try_parse_str("-snan(3)").unwrap().quieten();
+        assert_eq!("-nan(3)", d.to_string());
+
+        // Decimals that aren't signaling NaNs are unchanged
+        assert_eq!(
+            "1.20",
+            Bitstring32::try_parse_str("1.20")
+                .unwrap()
+                .quieten()
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn parts_big() {
+        use num_bigint::{
+            BigInt,
+            BigUint,
+        };
+
+        let d = BigBitstring::try_parse_str("-1.20e-10000").unwrap();
+        assert_eq!(
+            Some((true, BigUint::from(120u8), BigInt::from(-10002))),
+            d.to_parts()
+        );
+
+        let coefficient = BigUint::from(u128::MAX) * BigUint::from(u128::MAX);
+        let d = BigBitstring::from_parts(false, &coefficient, &BigInt::from(-5));
+        assert_eq!(Some((false, coefficient, BigInt::from(-5))), d.to_parts());
+    }
+
+    #[test]
+    fn parse_rounded() {
+        let (d, flags) =
+            Bitstring64::parse_rounded("3.14159265358979323846", RoundingMode::HalfEven).unwrap();
+        assert_eq!("3.141592653589793", d.to_string());
+        assert_eq!(Flags::INEXACT | Flags::ROUNDED, flags);
+
+        let (d, flags) =
+            Bitstring64::parse_rounded("3.14159265358979323846", RoundingMode::Up).unwrap();
+        assert_eq!("3.141592653589794", d.to_string());
+        assert!(flags.contains(Flags::INEXACT));
+
+        // Numbers that fit aren't rounded
+        let (d, flags) = Bitstring32::parse_rounded("123.4567", RoundingMode::Down).unwrap();
+        assert_eq!("123.4567", d.to_string());
+        assert!(flags.is_empty());
+
+        // Trailing zeros are rounded exactly
+        let (d, flags) = Bitstring32::parse_rounded("123456700", RoundingMode::Down).unwrap();
+        assert_eq!("1.234567e8", d.to_string());
+        assert_eq!(Flags::ROUNDED, flags);
+
+        // Digits far past the precision still affect rounding
+        let (d, _) = Bitstring32::parse_rounded(
+            "1.000000500000000000000000000000000000000000000000000000000001",
+            RoundingMode::HalfEven,
+        )
+        .unwrap();
+        assert_eq!("1.000001", d.to_string());
+
+        let (d, _) = Bitstring32::parse_rounded(
+            "0.0000000000000000000000000000000000000000000000000012345675",
+            RoundingMode::HalfEven,
+        )
+        .unwrap();
+        assert_eq!("1.234568e-51", d.to_string());
+
+        // Exponents are clamped, or overflow and underflow
+        let (d, flags) = Bitstring32::parse_rounded("1e96", RoundingMode::HalfEven).unwrap();
+        assert_eq!("1.000000e96", d.to_string());
+        assert_eq!(Flags::CLAMPED, flags);
+
+        let (d, flags) = Bitstring32::parse_rounded("1e97", RoundingMode::HalfEven).unwrap();
+        assert_eq!("inf", d.to_string());
+        assert!(flags.contains(Flags::OVERFLOW | Flags::INEXACT));
+
+        let (d, _) = Bitstring32::parse_rounded("-1e97", RoundingMode::Down).unwrap();
+        assert_eq!("-9.999999e96", d.to_string());
+
+        let (d, flags) = Bitstring32::parse_rounded("1.5e-101", RoundingMode::HalfEven).unwrap();
+        assert_eq!("2e-101", d.to_string());
+        assert!(flags.contains(Flags::UNDERFLOW | Flags::INEXACT));
+
+        let (d, flags) =
+            Bitstring128::parse_rounded("-1e-99999999999999", RoundingMode::HalfEven).unwrap();
+        assert_eq!("-0e-6176", d.to_string());
+        assert!(flags.contains(Flags::UNDERFLOW | Flags::CLAMPED));
+
+        let (d, _) = Bitstring128::parse_rounded("1e99999999999999", RoundingMode::Floor).unwrap();
+        assert_eq!("9.999999999999999999999999999999999e6144", d.to_string());
+
+        let (d, flags) =
+            Bitstring64::parse_rounded("0e99999999999999", RoundingMode::Floor).unwrap();
+        assert_eq!("0e369", d.to_string());
+        assert_eq!(Flags::CLAMPED, flags);
+
+        // Infinities and NaNs aren't rounded
+        let (d, flags) = Bitstring64::parse_rounded("-inf", RoundingMode::HalfEven).unwrap();
+        assert_eq!("-inf", d.to_string());
+        assert!(flags.is_empty());
+
+        let (d, _) = Bitstring64::parse_rounded("snan(12)", RoundingMode::HalfEven).unwrap();
+        assert_eq!("snan(12)", d.to_string());
+
+        assert!(Bitstring64::parse_rounded("1.2.3", RoundingMode::HalfEven).is_err());
+
+        // Contexts can trap rounding
+        let mut ctx = Context::new().with_traps(Flags::INEXACT);
+        assert!(Bitstring32::try_parse_str_with("1.5", &mut ctx).is_ok());
+        assert!(Bitstring32::try_parse_str_with("1.00000001", &mut ctx).is_err());
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn parse_rounded_matches_decnumber() {
+        let mut rng = crate::math::tests::Rng(0x2545f4914f6cdd1d);
+        let mut ctx = dec::Context::<dec::Decimal64>::default();
+
+        let modes = [
+            (RoundingMode::HalfEven, dec::Rounding::HalfEven),
+            (RoundingMode::HalfUp, dec::Rounding::HalfUp),
+            (RoundingMode::HalfDown, dec::Rounding::HalfDown),
+            (RoundingMode::Up, dec::Rounding::Up),
+            (RoundingMode::Down, dec::Rounding::Down),
+            (RoundingMode::Ceiling, dec::Rounding::Ceiling),
+            (RoundingMode::Floor, dec::Rounding::Floor),
+            (RoundingMode::ZeroFiveUp, dec::Rounding::ZeroFiveUp),
+        ];
+
+        for _ in 0..10_000 {
+            let exponent = if rng.next(4) == 0 {
+                rng.next(900) as i32 - 450
+            } else {
+                rng.next(40) as i32 - 20
+            };
+            let s = rng.decimal(40, exponent);
+
+            // NaN payloads aren't rounded
+            if s.contains("nan") {
+                continue;
+            }
+
+            let (mode, rounding) = modes[rng.next(modes.len() as u64) as usize];
+
+            ctx.set_rounding(rounding);
+            ctx.set_status(Default::default());
+
+            let expected = ctx.parse(s.as_str()).unwrap();
+            let (actual, flags) = Bitstring64::parse_rounded(&s, mode).unwrap();
+
+            assert_eq!(
+                expected.to_ne_bytes(),
+                *actual.as_le_bytes(),
+                "{} {:?}",
+                s,
+                mode
+            );
+            assert_eq!(
+                ctx.status().inexact(),
+                flags.contains(Flags::INEXACT),
+                "{} {:?}",
+                s,
+                mode
+            );
+        }
+    }
+
     #[test]
     fn decimal_roundtrip_i128() {
         for i in [0i128, 42i128, i128::MIN, i128::MAX] {
@@ -442,6 +2465,8 @@ mod tests {
             "-123e-3",
             "1.2354e-7",
             "-1.2354e-7",
+            "1.000000e96",
+            "1.234567e10",
             "1e96",
             "1e-95",
             "1e384",
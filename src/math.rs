@@ -0,0 +1,101 @@
+/*!
+Arithmetic and comparison on binary-encoded decimal numbers.
+
+Arithmetic follows the same steps for every operation:
+
+1. Unpack each operand into an [`Unpacked`] value, which is either a NaN, an infinity, or a
+   [`Finite`] number with its coefficient decoded into a [`DigitBuf`] and its exponent decoded
+   into a binary integer.
+2. Compute the exact result on the unpacked digits, or enough of its digits to round it correctly.
+3. Round the result to the precision and exponent range of the [`Limits`] of the format,
+   recording any conditions encountered as [`Flags`].
+4. Pack the rounded result back into an encoded decimal.
+
+Classification, comparison, and hashing don't need to do any arithmetic, so they work directly
+on the encoded bitstrings instead. They decode the combination field and stream the significand
+digits out of their declets without unpacking them first.
+*/
+
+mod add;
+mod classify;
+mod cmp;
+mod decimal;
+mod digits;
+mod div;
+mod exp;
+mod hash;
+mod mul;
+mod quantize;
+mod reduce;
+mod round;
+mod sqrt;
+
+pub(crate) use crate::context::{
+    Flags,
+    RoundingMode,
+};
+
+pub(crate) use self::{
+    add::*,
+    classify::*,
+    cmp::*,
+    decimal::*,
+    digits::*,
+    div::*,
+    exp::*,
+    hash::*,
+    mul::*,
+    quantize::*,
+    reduce::*,
+    round::*,
+    sqrt::*,
+};
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::binary::{
+        BinaryBuf,
+        FixedBinaryBuf,
+    };
+
+    pub(crate) fn parse<const N: usize>(s: &str) -> FixedBinaryBuf<N, i32> {
+        crate::convert::decimal_from_str(s).expect("failed to parse decimal")
+    }
+
+    pub(crate) fn to_string<D: BinaryBuf>(d: &D) -> String {
+        let mut s = String::new();
+        crate::convert::decimal_to_fmt(d, &mut s).expect("failed to format decimal");
+        s
+    }
+
+    pub(crate) struct Rng(pub(crate) u64);
+
+    impl Rng {
+        pub(crate) fn next(&mut self, n: u64) -> u64 {
+            // xorshift64
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+
+            self.0 % n
+        }
+
+        pub(crate) fn decimal(&mut self, precision: usize, exponent: i32) -> String {
+            let sign = if self.next(2) == 0 { "" } else { "-" };
+
+            match self.next(20) {
+                0 => return format!("{}inf", sign),
+                1 => return format!("{}nan({})", sign, self.next(1000)),
+                2 => return format!("{}0e{}", sign, exponent),
+                _ => (),
+            }
+
+            let digits = 1 + self.next(precision as u64) as usize;
+            let coefficient: String = (0..digits)
+                .map(|_| char::from(b'0' + self.next(10) as u8))
+                .collect();
+
+            format!("{}{}e{}", sign, coefficient, exponent)
+        }
+    }
+}
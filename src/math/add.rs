@@ -0,0 +1,564 @@
+/*!
+Addition and subtraction of decimals.
+
+The operands are aligned so their exponents match, and then their coefficients are added or
+subtracted exactly. The result is then rounded to fit its format.
+
+If the exponents of the operands are very different then aligning them could need an enormous
+number of digits. When one operand is entirely below the digits that will be kept after rounding,
+it can only affect the result through rounding. In that case we replace it with a smaller
+number that affects rounding in the same way, but is closer to the other operand.
+*/
+
+use core::cmp::Ordering;
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        pack,
+        propagate_nan,
+        round_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+};
+
+/**
+Add two decimals.
+*/
+pub(crate) fn decimal_add<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(add(unpack::<D, C>(a), unpack(b), limits, mode, flags))
+}
+
+/**
+Subtract one decimal from another.
+*/
+pub(crate) fn decimal_sub<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    let mut b = unpack(b);
+    b.negate();
+
+    pack(add(unpack::<D, C>(a), b, limits, mode, flags))
+}
+
+/**
+Add two unpacked decimals.
+*/
+pub(crate) fn add<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a, &b], limits, flags) {
+        return nan;
+    }
+
+    match (a, b) {
+        // ±inf + ±inf
+        (
+            Unpacked::Infinity {
+                is_negative: a_is_negative,
+            },
+            Unpacked::Infinity {
+                is_negative: b_is_negative,
+            },
+        ) => {
+            if a_is_negative == b_is_negative {
+                Unpacked::Infinity {
+                    is_negative: a_is_negative,
+                }
+            } else {
+                Unpacked::invalid(flags)
+            }
+        }
+        // ±inf + ±123
+        (inf @ Unpacked::Infinity { .. }, _) | (_, inf @ Unpacked::Infinity { .. }) => inf,
+        // ±123 + ±123
+        (Unpacked::Finite(a), Unpacked::Finite(b)) => {
+            round_finite(add_finite(a, b, limits, mode), limits, mode, flags)
+        }
+        _ => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Add two finite numbers.
+
+The result is exact, unless one of the operands is so much smaller than the other that it can
+only affect rounding. In that case the result has enough digits to be rounded correctly.
+*/
+pub(crate) fn add_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut a: Finite<C, E>,
+    mut b: Finite<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+) -> Finite<C, E> {
+    a.coefficient.trim();
+    b.coefficient.trim();
+
+    // The ideal exponent of the result is the smaller exponent of the operands
+    let ideal_exponent = a.exponent.clone().min(b.exponent.clone());
+
+    match (a.coefficient.is_zero(), b.coefficient.is_zero()) {
+        // ±0 + ±0
+        (true, true) => {
+            return Finite {
+                is_negative: zero_sign(a.is_negative, b.is_negative, mode),
+                coefficient: C::default(),
+                exponent: ideal_exponent,
+            };
+        }
+        // ±0 + ±123
+        (true, false) => return pad_towards(b, ideal_exponent, limits),
+        // ±123 + ±0
+        (false, true) => return pad_towards(a, ideal_exponent, limits),
+        // ±123 + ±123
+        (false, false) => (),
+    }
+
+    // Make `a` the operand with the larger magnitude digit
+    if adjusted_exponent(&a) < adjusted_exponent(&b) {
+        core::mem::swap(&mut a, &mut b);
+    }
+
+    // If `b` is entirely below the digits of `a`, and below the digits that will be kept after rounding,
+    // then it can only affect rounding. Replace it with a smaller number that's closer to `a`.
+    //
+    // The rounding position of the result is somewhere below the most significant digit of `a`.
+    // It can't be further than `precision + 1` digits away. If `b` is entirely below that position,
+    // and entirely below the least significant digit of `a`, then it's equivalent to any number
+    // between zero and the next power of ten up.
+    let sticky = a
+        .exponent
+        .clone()
+        .min(adjusted_exponent(&a).lower(limits.precision + 1))
+        .lower(1);
+
+    if adjusted_exponent(&b) < sticky {
+        b = Finite {
+            is_negative: b.is_negative,
+            coefficient: C::from_u128(1),
+            exponent: sticky.lower(1),
+        };
+    }
+
+    // Align the coefficients so they have the same exponent
+    let exponent = a.exponent.clone().min(b.exponent.clone());
+
+    a.coefficient.shift_left(distance(&exponent, &a.exponent));
+    b.coefficient.shift_left(distance(&exponent, &b.exponent));
+
+    if a.is_negative == b.is_negative {
+        a.coefficient.add_assign(&b.coefficient);
+
+        Finite {
+            is_negative: a.is_negative,
+            coefficient: a.coefficient,
+            exponent,
+        }
+    } else {
+        match a.coefficient.cmp_magnitude(&b.coefficient) {
+            Ordering::Greater => {
+                a.coefficient.sub_assign(&b.coefficient);
+
+                Finite {
+                    is_negative: a.is_negative,
+                    coefficient: a.coefficient,
+                    exponent,
+                }
+            }
+            Ordering::Less => {
+                b.coefficient.sub_assign(&a.coefficient);
+
+                Finite {
+                    is_negative: b.is_negative,
+                    coefficient: b.coefficient,
+                    exponent,
+                }
+            }
+            Ordering::Equal => Finite {
+                is_negative: zero_sign(a.is_negative, b.is_negative, mode),
+                coefficient: C::default(),
+                exponent,
+            },
+        }
+    }
+}
+
+/**
+Get the sign of a zero that's the sum of numbers with the given signs.
+
+The sum of zeros with the same sign keeps that sign. Otherwise the sum is positive, unless
+rounding towards negative infinity.
+*/
+pub(crate) fn zero_sign(a_is_negative: bool, b_is_negative: bool, mode: RoundingMode) -> bool {
+    if a_is_negative == b_is_negative {
+        a_is_negative
+    } else {
+        mode == RoundingMode::Floor
+    }
+}
+
+/**
+Pad the coefficient of a number with zeros to bring its exponent closer to an ideal one.
+
+The coefficient won't be padded beyond the precision of the result.
+*/
+fn pad_towards<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut finite: Finite<C, E>,
+    ideal_exponent: E,
+    limits: &Limits<E>,
+) -> Finite<C, E> {
+    if ideal_exponent >= finite.exponent {
+        return finite;
+    }
+
+    let ideal_exponent = ideal_exponent.max(limits.etiny());
+
+    let pad = (finite.exponent.clone() - ideal_exponent)
+        .to_i32()
+        .map(|pad| pad as usize)
+        .unwrap_or(usize::MAX)
+        .min(limits.precision.saturating_sub(finite.coefficient.len()));
+
+    finite.coefficient.shift_left(pad);
+    finite.exponent = finite.exponent.lower(pad);
+
+    finite
+}
+
+/**
+Get the exponent of the most significant digit of a number.
+*/
+pub(crate) fn adjusted_exponent<C: DigitBuf, E: BinaryExponent>(finite: &Finite<C, E>) -> E {
+    finite
+        .exponent
+        .raise(finite.coefficient.len().saturating_sub(1))
+}
+
+/**
+Get the distance between two exponents, where `from` is not larger than `to`.
+
+The distance is expected to be small enough to fit in the digits of a coefficient.
+*/
+pub(crate) fn distance<E: BinaryExponentMath>(from: &E, to: &E) -> usize {
+    (to.clone() - from.clone())
+        .to_i32()
+        .expect("the distance between exponents is small") as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::tests::{
+            parse,
+            to_string,
+        },
+    };
+
+    fn check<const N: usize>(
+        a: &str,
+        op: &str,
+        b: &str,
+        mode: RoundingMode,
+        expected: &str,
+        expected_flags: Flags,
+    ) {
+        let da = parse::<N>(a);
+        let db = parse::<N>(b);
+
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result: FixedBinaryBuf<N, i32> = match op {
+            "+" => decimal_add::<_, crate::math::ArrayDigitBuf<128>>(
+                &da, &db, &limits, mode, &mut flags,
+            ),
+            "-" => decimal_sub::<_, crate::math::ArrayDigitBuf<128>>(
+                &da, &db, &limits, mode, &mut flags,
+            ),
+            _ => unreachable!(),
+        };
+
+        assert_eq!(expected, to_string(&result), "{} {} {}", a, op, b);
+        assert_eq!(expected_flags, flags, "{} {} {}", a, op, b);
+    }
+
+    #[test]
+    fn add_exact() {
+        for (a, b, expected) in [
+            ("1", "1", "2"),
+            ("1", "-1", "0"),
+            ("-1", "1", "0"),
+            ("1.0", "1", "2.0"),
+            ("1.00", "1.0", "2.00"),
+            ("12", "7.00", "19.00"),
+            ("1.3", "-1.07", "0.23"),
+            ("1.3", "-1.30", "0.00"),
+            ("1.3", "-2.07", "-0.77"),
+            ("0", "0", "0"),
+            ("-0", "-0", "-0"),
+            ("0", "-0", "0"),
+            ("0.00", "0", "0.00"),
+            ("0", "1.5", "1.5"),
+            ("0e-3", "1", "1.000"),
+            ("1e2", "0", "100"),
+            ("1e2", "1", "101"),
+            ("999999", "1", "1000000"),
+        ] {
+            check::<4>(
+                a,
+                "+",
+                b,
+                RoundingMode::HalfEven,
+                expected,
+                Flags::default(),
+            );
+        }
+
+        // Dropping trailing zeros is rounded, but exact
+        check::<4>(
+            "9999999",
+            "+",
+            "1",
+            RoundingMode::HalfEven,
+            "1.000000e7",
+            Flags::ROUNDED,
+        );
+    }
+
+    #[test]
+    fn sub_exact() {
+        for (a, b, expected) in [
+            ("1", "1", "0"),
+            ("1", "-1", "2"),
+            ("1.3", "1.07", "0.23"),
+            ("1.07", "1.3", "-0.23"),
+            ("0", "0", "0"),
+            ("-0", "0", "-0"),
+            ("0", "-0", "0"),
+        ] {
+            check::<4>(
+                a,
+                "-",
+                b,
+                RoundingMode::HalfEven,
+                expected,
+                Flags::default(),
+            );
+        }
+    }
+
+    #[test]
+    fn add_rounded() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        for (a, b, mode, expected) in [
+            ("1234567", "0.5", RoundingMode::HalfEven, "1234568"),
+            ("1234566", "0.5", RoundingMode::HalfEven, "1234566"),
+            ("1234567", "0.5", RoundingMode::Down, "1234567"),
+            ("1", "1e-50", RoundingMode::HalfEven, "1.000000"),
+            ("1", "1e-50", RoundingMode::Up, "1.000001"),
+            ("1", "-1e-50", RoundingMode::HalfEven, "1.000000"),
+            ("1", "-1e-50", RoundingMode::Down, "0.9999999"),
+            ("1e50", "1", RoundingMode::HalfEven, "1.000000e50"),
+            ("1e50", "1", RoundingMode::Ceiling, "1.000001e50"),
+            ("-1e50", "-1", RoundingMode::Floor, "-1.000001e50"),
+            ("1.000000e50", "5e43", RoundingMode::HalfEven, "1.000000e50"),
+            ("1.000001e50", "5e43", RoundingMode::HalfEven, "1.000002e50"),
+            (
+                "1.000000e50",
+                "5.000001e43",
+                RoundingMode::HalfEven,
+                "1.000001e50",
+            ),
+        ] {
+            check::<4>(a, "+", b, mode, expected, rounded);
+        }
+    }
+
+    #[test]
+    fn add_overflow() {
+        let overflow = Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED;
+
+        check::<4>(
+            "9.999999e96",
+            "+",
+            "1e90",
+            RoundingMode::HalfEven,
+            "inf",
+            overflow,
+        );
+        check::<4>(
+            "-9.999999e96",
+            "-",
+            "1e90",
+            RoundingMode::HalfEven,
+            "-inf",
+            overflow,
+        );
+        check::<4>(
+            "9.999999e96",
+            "+",
+            "1e90",
+            RoundingMode::Down,
+            "9.999999e96",
+            overflow,
+        );
+    }
+
+    #[test]
+    fn add_large_exponent() {
+        check::<4>(
+            "1e90",
+            "+",
+            "1e90",
+            RoundingMode::HalfEven,
+            "2e90",
+            Flags::default(),
+        );
+        check::<4>(
+            "1000000e90",
+            "+",
+            "0e90",
+            RoundingMode::HalfEven,
+            "1.000000e96",
+            Flags::default(),
+        );
+    }
+
+    #[test]
+    fn add_subnormal() {
+        check::<4>(
+            "1e-101",
+            "+",
+            "1e-101",
+            RoundingMode::HalfEven,
+            "2e-101",
+            Flags::default(),
+        );
+        check::<4>(
+            "1e-101",
+            "-",
+            "1e-101",
+            RoundingMode::HalfEven,
+            "0e-101",
+            Flags::default(),
+        );
+    }
+
+    #[test]
+    fn add_special() {
+        for (a, op, b, expected, expected_flags) in [
+            ("inf", "+", "1", "inf", Flags::default()),
+            ("1", "+", "-inf", "-inf", Flags::default()),
+            ("inf", "+", "inf", "inf", Flags::default()),
+            ("inf", "-", "inf", "nan", Flags::INVALID),
+            ("inf", "+", "-inf", "nan", Flags::INVALID),
+            ("-inf", "-", "inf", "-inf", Flags::default()),
+            ("nan", "+", "1", "nan", Flags::default()),
+            ("1", "+", "nan(12)", "nan(12)", Flags::default()),
+            ("1", "-", "-nan(12)", "-nan(12)", Flags::default()),
+            ("snan(3)", "+", "nan(12)", "nan(3)", Flags::INVALID),
+            ("inf", "+", "snan", "nan", Flags::INVALID),
+        ] {
+            check::<8>(a, op, b, RoundingMode::HalfEven, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn add_zero_sign_floor() {
+        check::<8>("1", "-", "1", RoundingMode::Floor, "-0", Flags::default());
+        check::<8>("-0", "+", "0", RoundingMode::Floor, "-0", Flags::default());
+    }
+
+    #[test]
+    fn add_decimal128() {
+        check::<16>(
+            "1234567890123456789012345678901234",
+            "+",
+            "0.5",
+            RoundingMode::HalfEven,
+            "1234567890123456789012345678901234",
+            Flags::INEXACT | Flags::ROUNDED,
+        );
+        check::<16>(
+            "0.1",
+            "+",
+            "0.2",
+            RoundingMode::HalfEven,
+            "0.3",
+            Flags::default(),
+        );
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn add_matches_decnumber_decimal64() {
+        let mut rng = crate::math::tests::Rng(0x2545f4914f6cdd1d);
+        let mut ctx = dec::Context::<dec::Decimal64>::default();
+        let limits = Limits::from_storage_width_bits(64);
+
+        for _ in 0..20_000 {
+            // Mostly pick exponents close together so the operands overlap
+            let ea = rng.next(60) as i32 - 30;
+            let eb = if rng.next(4) == 0 {
+                rng.next(767) as i32 - 398
+            } else {
+                ea + rng.next(40) as i32 - 20
+            };
+
+            let a = rng.decimal(16, ea);
+            let b = rng.decimal(16, eb);
+
+            let da: FixedBinaryBuf<8, i32> = crate::convert::decimal_from_str(&a).unwrap();
+            let db: FixedBinaryBuf<8, i32> = crate::convert::decimal_from_str(&b).unwrap();
+
+            let ra = dec::Decimal64::from_ne_bytes(da.bytes().try_into().unwrap());
+            let rb = dec::Decimal64::from_ne_bytes(db.bytes().try_into().unwrap());
+
+            let sum: FixedBinaryBuf<8, i32> = decimal_add::<_, crate::math::ArrayDigitBuf<64>>(
+                &da,
+                &db,
+                &limits,
+                RoundingMode::HalfEven,
+                &mut Flags::default(),
+            );
+            let diff: FixedBinaryBuf<8, i32> = decimal_sub::<_, crate::math::ArrayDigitBuf<64>>(
+                &da,
+                &db,
+                &limits,
+                RoundingMode::HalfEven,
+                &mut Flags::default(),
+            );
+
+            assert_eq!(ctx.add(ra, rb).to_ne_bytes(), sum.bytes(), "{} + {}", a, b);
+            assert_eq!(ctx.sub(ra, rb).to_ne_bytes(), diff.bytes(), "{} - {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,234 @@
+/*!
+Classifying decimals by their value.
+
+These work directly on the exponent and digits of a decimal. Zeros, integers, and subnormals can
+all be encoded with many different exponents, so they're identified by their value rather than
+their encoding.
+*/
+
+use crate::{
+    binary::{
+        is_finite,
+        is_infinite,
+        is_sign_negative,
+        is_signaling_nan,
+        BinaryBuf,
+        BinaryExponent,
+    },
+    convert::{
+        decimal_digits,
+        decimal_exponent,
+    },
+    num::Integer,
+    DecimalCategory,
+};
+
+/**
+Whether a decimal is a positive or negative zero, with any exponent.
+*/
+pub(crate) fn decimal_is_zero<D: BinaryBuf>(decimal: &D) -> bool {
+    is_finite(decimal) && decimal_digits(decimal).all(|digit| digit == 0)
+}
+
+/**
+Whether a decimal is finite and has no non-zero fractional digits.
+*/
+pub(crate) fn decimal_is_integer<D: BinaryBuf>(decimal: &D) -> bool {
+    let Some(exponent) = decimal_exponent(decimal) else {
+        return false;
+    };
+
+    if exponent >= D::Exponent::zero() {
+        return true;
+    }
+
+    // Trailing zeros can cancel out a negative exponent, so `1.00` is an integer
+    let trailing_zeros =
+        decimal_digits(decimal).fold(0, |zeros, digit| if digit == 0 { zeros + 1 } else { 0 });
+
+    decimal_is_zero(decimal) || exponent.raise(trailing_zeros) >= D::Exponent::zero()
+}
+
+/**
+Whether a decimal is non-zero and has an adjusted exponent below the minimum for its format.
+*/
+pub(crate) fn decimal_is_subnormal<D: BinaryBuf>(decimal: &D) -> bool {
+    let Some(exponent) = decimal_exponent(decimal) else {
+        return false;
+    };
+
+    if decimal_is_zero(decimal) {
+        return false;
+    }
+
+    let adjusted_exponent = exponent.raise(decimal_digits(decimal).count() - 1);
+
+    adjusted_exponent < D::Exponent::emin(decimal)
+}
+
+/**
+Get the class of a decimal.
+*/
+pub(crate) fn decimal_classify<D: BinaryBuf>(decimal: &D) -> DecimalCategory {
+    let is_negative = is_sign_negative(decimal);
+
+    if is_signaling_nan(decimal) {
+        DecimalCategory::SignalingNan
+    } else if !is_finite(decimal) && !is_infinite(decimal) {
+        DecimalCategory::QuietNan
+    } else if is_infinite(decimal) {
+        if is_negative {
+            DecimalCategory::NegativeInfinity
+        } else {
+            DecimalCategory::PositiveInfinity
+        }
+    } else if decimal_is_zero(decimal) {
+        if is_negative {
+            DecimalCategory::NegativeZero
+        } else {
+            DecimalCategory::PositiveZero
+        }
+    } else if decimal_is_subnormal(decimal) {
+        if is_negative {
+            DecimalCategory::NegativeSubnormal
+        } else {
+            DecimalCategory::PositiveSubnormal
+        }
+    } else if is_negative {
+        DecimalCategory::NegativeNormal
+    } else {
+        DecimalCategory::PositiveNormal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::tests::parse,
+    };
+
+    #[test]
+    fn is_zero() {
+        for (d, expected) in [
+            ("0", true),
+            ("-0.000", true),
+            ("0e90", true),
+            ("1e-101", false),
+            ("inf", false),
+            ("nan", false),
+        ] {
+            assert_eq!(expected, decimal_is_zero(&parse::<4>(d)), "{}", d);
+        }
+    }
+
+    #[test]
+    fn is_integer() {
+        for (d, expected) in [
+            ("0", true),
+            ("-0.000", true),
+            ("12", true),
+            ("1.2e3", true),
+            ("1.200", false),
+            ("1200e-2", true),
+            ("1230e-2", false),
+            ("1e-101", false),
+            ("-inf", false),
+            ("snan", false),
+        ] {
+            assert_eq!(expected, decimal_is_integer(&parse::<4>(d)), "{}", d);
+        }
+    }
+
+    #[test]
+    fn classify() {
+        for (d, expected) in [
+            ("snan(1)", DecimalCategory::SignalingNan),
+            ("-nan", DecimalCategory::QuietNan),
+            ("-inf", DecimalCategory::NegativeInfinity),
+            ("-1", DecimalCategory::NegativeNormal),
+            ("-1e-96", DecimalCategory::NegativeSubnormal),
+            ("-0e-101", DecimalCategory::NegativeZero),
+            ("0e90", DecimalCategory::PositiveZero),
+            ("9.99999e-96", DecimalCategory::PositiveSubnormal),
+            ("1e-95", DecimalCategory::PositiveNormal),
+            ("1000000e-101", DecimalCategory::PositiveNormal),
+            ("9999999e90", DecimalCategory::PositiveNormal),
+            ("inf", DecimalCategory::PositiveInfinity),
+        ] {
+            assert_eq!(expected, decimal_classify(&parse::<4>(d)), "{}", d);
+        }
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn matches_decnumber() {
+        let mut rng = crate::math::tests::Rng(0x2545f4914f6cdd1d);
+
+        for _ in 0..10_000 {
+            let mut bytes = [0; 8];
+            for b in &mut bytes {
+                *b = rng.next(256) as u8;
+            }
+
+            // Bias towards specials and small exponents so every class is covered
+            match rng.next(4) {
+                0 => bytes[7] |= 0b0111_1000,
+                1 => {
+                    bytes[7] &= 0b1000_0011;
+                    bytes[6] = 0
+                }
+                _ => (),
+            }
+
+            // Infinities with the bit after their combination field set aren't classified
+            // as infinities by this library
+            if bytes[7] & 0b0111_1110 == 0b0111_1010 {
+                continue;
+            }
+
+            let decimal = FixedBinaryBuf::<8, i32>::from(bytes);
+            let expected = dec::Decimal64::from_ne_bytes(bytes);
+
+            let category = match expected.class() {
+                dec::Class::SignalingNan => DecimalCategory::SignalingNan,
+                dec::Class::QuietNan => DecimalCategory::QuietNan,
+                dec::Class::NegInfinity => DecimalCategory::NegativeInfinity,
+                dec::Class::NegNormal => DecimalCategory::NegativeNormal,
+                dec::Class::NegSubnormal => DecimalCategory::NegativeSubnormal,
+                dec::Class::NegZero => DecimalCategory::NegativeZero,
+                dec::Class::PosZero => DecimalCategory::PositiveZero,
+                dec::Class::PosSubnormal => DecimalCategory::PositiveSubnormal,
+                dec::Class::PosNormal => DecimalCategory::PositiveNormal,
+                dec::Class::PosInfinity => DecimalCategory::PositiveInfinity,
+            };
+
+            assert_eq!(category, decimal_classify(&decimal), "{:?}", bytes);
+            assert_eq!(expected.is_zero(), decimal_is_zero(&decimal), "{:?}", bytes);
+            assert_eq!(
+                expected.is_subnormal(),
+                decimal_is_subnormal(&decimal),
+                "{:?}",
+                bytes
+            );
+            assert_eq!(
+                expected.is_canonical(),
+                crate::binary::is_canonical(&decimal),
+                "{:?}",
+                bytes
+            );
+
+            let mut canonical = decimal;
+            crate::binary::canonicalize(&mut canonical);
+
+            assert_eq!(
+                expected.canonical().to_ne_bytes(),
+                canonical.bytes(),
+                "{:?}",
+                bytes
+            );
+        }
+    }
+}
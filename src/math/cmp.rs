@@ -0,0 +1,541 @@
+/*!
+Compare binary-encoded decimals by their numeric value.
+
+Decimals aren't normalized, so the same number may have many different encodings. The numbers
+`1`, `1.0`, and `10e-1` all have different significands and exponents, but they're all equal.
+To compare two decimals we first look at their _adjusted_ exponents; the exponent of their most
+significant non-zero digit. If those are the same then we compare their significant digits
+from left to right.
+*/
+
+use core::{
+    cmp::Ordering,
+    iter,
+};
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        decode_significand_trailing_declets,
+        is_infinite,
+        is_nan,
+        is_sign_negative,
+        is_signaling_nan,
+        BinaryBuf,
+        BinaryExponent,
+    },
+    num::Integer,
+};
+
+/**
+Compare two decimals by their numeric value.
+
+This method returns `None` if either decimal is a NaN. Zeros compare equal regardless of their
+sign or exponent.
+*/
+pub(crate) fn decimal_partial_cmp<DA: BinaryBuf, DB: BinaryBuf>(
+    a: &DA,
+    b: &DB,
+) -> Option<Ordering> {
+    if is_nan(a) || is_nan(b) {
+        return None;
+    }
+
+    let a_is_negative = is_sign_negative(a);
+    let b_is_negative = is_sign_negative(b);
+
+    let cmp = match (is_infinite(a), is_infinite(b)) {
+        // ±inf, ±inf
+        (true, true) => b_is_negative.cmp(&a_is_negative),
+        // ±inf, ±123
+        (true, false) => {
+            if a_is_negative {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+        // ±123, ±inf
+        (false, true) => {
+            if b_is_negative {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }
+        // ±123, ±123
+        (false, false) => {
+            let a = FiniteMagnitude::decode(a);
+            let b = FiniteMagnitude::decode(b);
+
+            match (a, b) {
+                // Zeros are equal regardless of their sign
+                (None, None) => Ordering::Equal,
+                // Zero is less than any positive number and greater than any negative one
+                (None, Some(_)) => {
+                    if b_is_negative {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    }
+                }
+                (Some(_), None) => {
+                    if a_is_negative {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                }
+                (Some(a), Some(b)) => match (a_is_negative, b_is_negative) {
+                    (false, false) => a.cmp(&b),
+                    (true, true) => b.cmp(&a),
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                },
+            }
+        }
+    };
+
+    Some(cmp)
+}
+
+/**
+Whether two decimals have the same numeric value.
+
+NaNs are never equal to anything, including themselves.
+*/
+pub(crate) fn decimal_eq<DA: BinaryBuf, DB: BinaryBuf>(a: &DA, b: &DB) -> bool {
+    decimal_partial_cmp(a, b) == Some(Ordering::Equal)
+}
+
+/**
+Compare two decimals using the IEEE 754 `totalOrder` predicate.
+
+Unlike `decimal_partial_cmp`, this method orders all decimals, including NaNs. The order is:
+
+1. `-NaN`, ordered by payload from largest to smallest.
+2. `-sNaN`, ordered by payload from largest to smallest.
+3. `-inf`.
+4. Negative finite numbers, where the same number is ordered from largest to smallest exponent.
+5. `-0`, then `+0`.
+6. Positive finite numbers, where the same number is ordered from smallest to largest exponent.
+7. `+inf`.
+8. `+sNaN`, ordered by payload from smallest to largest.
+9. `+NaN`, ordered by payload from smallest to largest.
+
+So `-1` is ordered before `-1.0` and `1.0` is ordered before `1`.
+*/
+pub(crate) fn decimal_total_cmp<DA: BinaryBuf, DB: BinaryBuf>(a: &DA, b: &DB) -> Ordering {
+    match (is_sign_negative(a), is_sign_negative(b)) {
+        (false, false) => total_cmp_magnitude(a, b),
+        (true, true) => total_cmp_magnitude(b, a),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+    }
+}
+
+/**
+Compare the absolute values of two decimals using the IEEE 754 `totalOrder` predicate.
+*/
+fn total_cmp_magnitude<DA: BinaryBuf, DB: BinaryBuf>(a: &DA, b: &DB) -> Ordering {
+    fn rank<D: BinaryBuf>(decimal: &D) -> u8 {
+        if is_signaling_nan(decimal) {
+            2
+        } else if is_nan(decimal) {
+            3
+        } else if is_infinite(decimal) {
+            1
+        } else {
+            0
+        }
+    }
+
+    match (rank(a), rank(b)) {
+        // ±123, ±123
+        (0, 0) => {
+            let cmp = match (FiniteMagnitude::decode(a), FiniteMagnitude::decode(b)) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Less,
+                (Some(_), None) => Ordering::Greater,
+                (Some(a), Some(b)) => a.cmp(&b),
+            };
+
+            // If the numbers are the same then order by their exponent
+            cmp.then_with(|| {
+                let (a_exp, _) = decode_combination_finite(a);
+                let (b_exp, _) = decode_combination_finite(b);
+
+                cmp_integer(&a_exp, &b_exp)
+            })
+        }
+        // ±inf, ±inf
+        (1, 1) => Ordering::Equal,
+        // ±nan, ±nan
+        (a_rank, b_rank) if a_rank == b_rank => {
+            // The payload of a NaN is its trailing significand
+            let a = decode_significand_trailing_declets(a).flatten();
+            let b = decode_significand_trailing_declets(b).flatten();
+
+            cmp_digits(a, b)
+        }
+        (a_rank, b_rank) => a_rank.cmp(&b_rank),
+    }
+}
+
+/**
+Compare two streams of ASCII digits as unsigned integers.
+
+The streams may have different lengths and leading zeros.
+*/
+fn cmp_digits(a: impl Iterator<Item = u8>, b: impl Iterator<Item = u8>) -> Ordering {
+    let mut a = a.skip_while(|d| *d == b'0');
+    let mut b = b.skip_while(|d| *d == b'0');
+
+    // The first digit that differs between the streams determines the order,
+    // unless one stream is longer than the other
+    let mut cmp = Ordering::Equal;
+    loop {
+        match (a.next(), b.next()) {
+            (Some(a), Some(b)) => {
+                if cmp == Ordering::Equal {
+                    cmp = a.cmp(&b);
+                }
+            }
+            (None, None) => return cmp,
+            (Some(_), None) => return Ordering::Greater,
+            (None, Some(_)) => return Ordering::Less,
+        }
+    }
+}
+
+/**
+The absolute value of a finite non-zero decimal.
+*/
+struct FiniteMagnitude<'a, D: BinaryBuf> {
+    decimal: &'a D,
+    adjusted_exponent: D::Exponent,
+    leading_zeros: usize,
+}
+
+impl<'a, D: BinaryBuf> FiniteMagnitude<'a, D> {
+    /**
+    Decode the magnitude of a finite decimal.
+
+    If the decimal is zero then this method will return `None`.
+    */
+    fn decode(decimal: &'a D) -> Option<Self> {
+        let (exp, _) = decode_combination_finite(decimal);
+
+        let mut total_digits = 0;
+        let mut leading_zeros = None;
+        for (i, digit) in significand_digits(decimal).enumerate() {
+            total_digits += 1;
+
+            if leading_zeros.is_none() && digit != b'0' {
+                leading_zeros = Some(i);
+            }
+        }
+
+        let leading_zeros = leading_zeros?;
+
+        // The exponent applies to the least significant digit
+        // We want the exponent of the most significant non-zero digit instead
+        let adjusted_exponent = exp.raise(total_digits - leading_zeros - 1);
+
+        Some(FiniteMagnitude {
+            decimal,
+            adjusted_exponent,
+            leading_zeros,
+        })
+    }
+
+    fn cmp<DB: BinaryBuf>(&self, other: &FiniteMagnitude<DB>) -> Ordering {
+        match cmp_integer(&self.adjusted_exponent, &other.adjusted_exponent) {
+            Ordering::Equal => {
+                // The most significant digits line up, so compare the significands digit-by-digit
+                // If one significand is shorter than the other then it's padded with trailing zeros
+                let mut a = significand_digits(self.decimal).skip(self.leading_zeros);
+                let mut b = significand_digits(other.decimal).skip(other.leading_zeros);
+
+                loop {
+                    match (a.next(), b.next()) {
+                        (None, None) => return Ordering::Equal,
+                        (a, b) => match a.unwrap_or(b'0').cmp(&b.unwrap_or(b'0')) {
+                            Ordering::Equal => continue,
+                            cmp => return cmp,
+                        },
+                    }
+                }
+            }
+            cmp => cmp,
+        }
+    }
+}
+
+/**
+Stream the ASCII digits of a finite decimal's significand, from most to least significant.
+*/
+fn significand_digits<D: BinaryBuf>(decimal: &D) -> impl Iterator<Item = u8> + '_ {
+    let (_, msd) = decode_combination_finite(decimal);
+
+    iter::once(msd.get_ascii()).chain(decode_significand_trailing_declets(decimal).flatten())
+}
+
+/**
+Compare two integers that may be of different types.
+*/
+pub(crate) fn cmp_integer<A: Integer, B: Integer>(a: &A, b: &B) -> Ordering {
+    match (a.to_i32(), b.to_i32()) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        // An integer that doesn't fit in an `i32` has a larger magnitude than one that does
+        (Some(_), None) => {
+            if b.is_negative() {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }
+        (None, Some(_)) => {
+            if a.is_negative() {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+        (None, None) => cmp_integer_text(a, b),
+    }
+}
+
+/**
+Compare two large integers by their text representation.
+
+Only arbitrary precision decimals can have exponents that overflow an `i32`.
+*/
+#[cfg(feature = "arbitrary-precision")]
+fn cmp_integer_text<A: Integer, B: Integer>(a: &A, b: &B) -> Ordering {
+    let a = a.as_display().to_string();
+    let b = b.as_display().to_string();
+
+    match (a.strip_prefix('-'), b.strip_prefix('-')) {
+        (None, None) => a.len().cmp(&b.len()).then_with(|| a.cmp(&b)),
+        (Some(a), Some(b)) => b.len().cmp(&a.len()).then_with(|| b.cmp(a)),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+    }
+}
+
+#[cfg(not(feature = "arbitrary-precision"))]
+fn cmp_integer_text<A: Integer, B: Integer>(_: &A, _: &B) -> Ordering {
+    unreachable!("exponents only overflow an `i32` in arbitrary precision decimals")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::parse;
+
+    #[test]
+    fn cmp_finite() {
+        for (a, b, expected) in [
+            ("1", "2", Ordering::Less),
+            ("2", "1", Ordering::Greater),
+            ("1", "1", Ordering::Equal),
+            ("1.0", "1", Ordering::Equal),
+            ("1.00", "1.0", Ordering::Equal),
+            ("10e-1", "1", Ordering::Equal),
+            ("100", "1e2", Ordering::Equal),
+            ("0.1", "1", Ordering::Less),
+            ("0.01", "0.1", Ordering::Less),
+            ("1.01", "1.1", Ordering::Less),
+            ("9", "10", Ordering::Less),
+            ("99.9", "100", Ordering::Less),
+            ("123.456", "123.4560", Ordering::Equal),
+            ("123.456", "123.4561", Ordering::Less),
+            ("-1", "1", Ordering::Less),
+            ("1", "-1", Ordering::Greater),
+            ("-1", "-2", Ordering::Greater),
+            ("-1.0", "-1", Ordering::Equal),
+            ("-0.1", "-0.01", Ordering::Less),
+            ("0", "-0", Ordering::Equal),
+            ("0", "0e10", Ordering::Equal),
+            ("0", "0.000", Ordering::Equal),
+            ("0", "1e-50", Ordering::Less),
+            ("0", "-1e-50", Ordering::Greater),
+            ("-0", "1", Ordering::Less),
+            ("1e-50", "0", Ordering::Greater),
+        ] {
+            assert_eq!(
+                Some(expected),
+                decimal_partial_cmp(&parse::<16>(a), &parse::<16>(b)),
+                "{} cmp {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn cmp_infinity() {
+        for (a, b, expected) in [
+            ("inf", "inf", Ordering::Equal),
+            ("-inf", "-inf", Ordering::Equal),
+            ("-inf", "inf", Ordering::Less),
+            ("inf", "-inf", Ordering::Greater),
+            ("inf", "1e6000", Ordering::Greater),
+            ("-inf", "-1e6000", Ordering::Less),
+            ("1", "inf", Ordering::Less),
+            ("1", "-inf", Ordering::Greater),
+            ("0", "-inf", Ordering::Greater),
+        ] {
+            assert_eq!(
+                Some(expected),
+                decimal_partial_cmp(&parse::<16>(a), &parse::<16>(b)),
+                "{} cmp {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn cmp_nan() {
+        for (a, b) in [
+            ("nan", "nan"),
+            ("nan", "1"),
+            ("1", "nan"),
+            ("snan", "snan"),
+            ("-nan", "inf"),
+            ("inf", "snan"),
+        ] {
+            assert_eq!(
+                None,
+                decimal_partial_cmp(&parse::<16>(a), &parse::<16>(b)),
+                "{} cmp {}",
+                a,
+                b
+            );
+            assert!(!decimal_eq(&parse::<16>(a), &parse::<16>(b)));
+        }
+    }
+
+    #[test]
+    fn cmp_different_widths() {
+        for (a, b, expected) in [
+            ("1", "1.000000000", Ordering::Equal),
+            ("1.5", "1.500000001", Ordering::Less),
+            ("-1.5", "-1.500000001", Ordering::Greater),
+            ("9999999", "9999999.000000001", Ordering::Less),
+            ("1e90", "1e100", Ordering::Less),
+        ] {
+            assert_eq!(
+                Some(expected),
+                decimal_partial_cmp(&parse::<4>(a), &parse::<16>(b)),
+                "{} cmp {}",
+                a,
+                b
+            );
+            assert_eq!(
+                Some(expected.reverse()),
+                decimal_partial_cmp(&parse::<16>(b), &parse::<4>(a)),
+                "{} cmp {}",
+                b,
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn total_cmp() {
+        // Decimals in ascending order, according to the totalOrder predicate
+        let ordered = [
+            "-nan(2)",
+            "-nan(1)",
+            "-nan",
+            "-snan(10)",
+            "-snan",
+            "-inf",
+            "-1e10",
+            "-1",
+            "-1.0",
+            "-1.00",
+            "-0.5",
+            "-0e1",
+            "-0",
+            "-0.0",
+            "0.0",
+            "0",
+            "0e1",
+            "0.5",
+            "1.00",
+            "1.0",
+            "1",
+            "1e10",
+            "inf",
+            "snan",
+            "snan(10)",
+            "nan",
+            "nan(1)",
+            "nan(2)",
+        ];
+
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(
+                    i.cmp(&j),
+                    decimal_total_cmp(&parse::<16>(a), &parse::<16>(b)),
+                    "{} cmp {}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn total_cmp_different_widths() {
+        for (a, b, expected) in [
+            ("1", "1", Ordering::Equal),
+            ("1.0", "1", Ordering::Less),
+            ("-1.0", "-1", Ordering::Greater),
+            ("nan(12)", "nan(012)", Ordering::Equal),
+            ("nan(12)", "nan(120)", Ordering::Less),
+            ("snan(99)", "snan(100)", Ordering::Less),
+        ] {
+            assert_eq!(
+                expected,
+                decimal_total_cmp(&parse::<4>(a), &parse::<16>(b)),
+                "{} cmp {}",
+                a,
+                b
+            );
+            assert_eq!(
+                expected.reverse(),
+                decimal_total_cmp(&parse::<16>(b), &parse::<4>(a)),
+                "{} cmp {}",
+                b,
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn cmp_integer_mixed() {
+        assert_eq!(Ordering::Less, cmp_integer(&1i32, &2i64));
+        assert_eq!(Ordering::Less, cmp_integer(&1i32, &(i64::MAX)));
+        assert_eq!(Ordering::Greater, cmp_integer(&1i32, &(i64::MIN)));
+        assert_eq!(Ordering::Greater, cmp_integer(&(i64::MAX), &1i32));
+        assert_eq!(Ordering::Less, cmp_integer(&(i64::MIN), &1i32));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn cmp_integer_large() {
+        assert_eq!(Ordering::Equal, cmp_integer(&i64::MAX, &i64::MAX));
+        assert_eq!(Ordering::Less, cmp_integer(&(i64::MAX - 1), &i64::MAX));
+        assert_eq!(Ordering::Less, cmp_integer(&i64::MIN, &(i64::MIN + 1)));
+        assert_eq!(Ordering::Less, cmp_integer(&i64::MIN, &i64::MAX));
+        assert_eq!(Ordering::Less, cmp_integer(&(i64::MAX / 10), &i64::MAX));
+        assert_eq!(Ordering::Greater, cmp_integer(&(i64::MIN / 10), &i64::MIN));
+    }
+}
@@ -0,0 +1,332 @@
+/*!
+Unpacking encoded decimals into a form that's convenient for arithmetic.
+
+An unpacked decimal has its significand decoded into a buffer of digits, and its exponent
+decoded into a binary integer. Arithmetic is done on unpacked decimals, and then the results are
+packed back into their encoded form.
+*/
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        decode_significand_trailing_declets,
+        encode_combination_finite,
+        encode_combination_infinity,
+        encode_combination_nan,
+        encode_significand_trailing_digits,
+        is_infinite,
+        is_nan,
+        is_sign_negative,
+        is_signaling_nan,
+        BinaryBuf,
+    },
+    math::{
+        DigitBuf,
+        Flags,
+        Limits,
+    },
+};
+use core::iter;
+
+/**
+A decimal that's been decoded for arithmetic.
+*/
+#[derive(Debug, Clone)]
+pub(crate) enum Unpacked<C, E> {
+    Finite(Finite<C, E>),
+    Infinity { is_negative: bool },
+    Nan(Nan<C>),
+}
+
+/**
+A finite decimal, with the value `(-1)^sign * coefficient * 10^exponent`.
+*/
+#[derive(Debug, Clone)]
+pub(crate) struct Finite<C, E> {
+    pub(crate) is_negative: bool,
+    pub(crate) coefficient: C,
+    pub(crate) exponent: E,
+}
+
+/**
+A NaN, with an optional payload.
+*/
+#[derive(Debug, Clone)]
+pub(crate) struct Nan<C> {
+    pub(crate) is_negative: bool,
+    pub(crate) is_signaling: bool,
+    pub(crate) payload: C,
+}
+
+impl<C: DigitBuf, E> Unpacked<C, E> {
+    /**
+    A quiet NaN without a payload.
+
+    This is the result of invalid operations.
+    */
+    pub(crate) fn invalid(flags: &mut Flags) -> Self {
+        flags.set(Flags::INVALID);
+
+        Unpacked::Nan(Nan {
+            is_negative: false,
+            is_signaling: false,
+            payload: C::default(),
+        })
+    }
+
+    /**
+    Flip the sign of this decimal.
+
+    The signs of NaNs are left unchanged.
+    */
+    pub(crate) fn negate(&mut self) {
+        match self {
+            Unpacked::Finite(finite) => finite.is_negative = !finite.is_negative,
+            Unpacked::Infinity { is_negative } => *is_negative = !*is_negative,
+            Unpacked::Nan(_) => (),
+        }
+    }
+}
+
+/**
+Decode a decimal for arithmetic.
+*/
+pub(crate) fn unpack<D: BinaryBuf, C: DigitBuf>(decimal: &D) -> Unpacked<C, D::Exponent> {
+    let is_negative = is_sign_negative(decimal);
+
+    if is_nan(decimal) {
+        let payload = C::from_ascii(decode_significand_trailing_declets(decimal).flatten());
+
+        Unpacked::Nan(Nan {
+            is_negative,
+            is_signaling: is_signaling_nan(decimal),
+            payload,
+        })
+    } else if is_infinite(decimal) {
+        Unpacked::Infinity { is_negative }
+    } else {
+        let (exponent, msd) = decode_combination_finite(decimal);
+
+        let coefficient = C::from_ascii(
+            iter::once(msd.get_ascii())
+                .chain(decode_significand_trailing_declets(decimal).flatten()),
+        );
+
+        Unpacked::Finite(Finite {
+            is_negative,
+            coefficient,
+            exponent,
+        })
+    }
+}
+
+/**
+Encode the result of arithmetic into a decimal.
+
+The decimal must already fit the limits of `D`.
+*/
+pub(crate) fn pack<D: BinaryBuf, C: DigitBuf>(unpacked: Unpacked<C, D::Exponent>) -> D {
+    match unpacked {
+        Unpacked::Finite(Finite {
+            is_negative,
+            coefficient,
+            exponent,
+        }) => {
+            let ascii = coefficient.to_ascii();
+
+            let mut buf = D::try_with_at_least_precision(ascii.len(), Some(&exponent))
+                .expect("the result of arithmetic will always fit the decimal");
+
+            let msd = encode_significand_trailing_digits(&mut buf, [ascii.digits()]);
+
+            encode_combination_finite(&mut buf, is_negative, exponent, msd);
+
+            buf
+        }
+        Unpacked::Infinity { is_negative } => {
+            let mut buf = D::try_with_at_least_storage_width_bytes(4)
+                .expect("infinity will always fit in the minimal sized buffer");
+
+            encode_combination_infinity(&mut buf, is_negative);
+
+            buf
+        }
+        Unpacked::Nan(Nan {
+            is_negative,
+            is_signaling,
+            payload,
+        }) => {
+            let mut buf = if payload.is_zero() {
+                D::try_with_at_least_storage_width_bytes(4)
+                    .expect("a NaN with no payload will always fit in the minimal sized buffer")
+            } else {
+                let ascii = payload.to_ascii();
+
+                let mut buf = D::try_with_at_least_precision(ascii.len() + 1, None)
+                    .expect("the NaN payload will always fit the decimal");
+
+                encode_significand_trailing_digits(&mut buf, [ascii.digits()]);
+
+                buf
+            };
+
+            encode_combination_nan(&mut buf, is_negative, is_signaling);
+
+            buf
+        }
+    }
+}
+
+/**
+Propagate a NaN from the operands of an arithmetic operation.
+
+If either operand is a signaling NaN then the result is the first signaling NaN, quietened,
+and the operation is invalid. Otherwise, if either operand is a quiet NaN then the result is
+the first quiet NaN. If neither operand is a NaN then this method returns `None`.
+
+The payload of the NaN will be truncated to fit the given limits.
+*/
+pub(crate) fn propagate_nan<C: DigitBuf, E>(
+    operands: &[&Unpacked<C, E>],
+    limits: &Limits<E>,
+    flags: &mut Flags,
+) -> Option<Unpacked<C, E>> {
+    let signaling = operands.iter().find(|operand| {
+        matches!(
+            operand,
+            Unpacked::Nan(Nan {
+                is_signaling: true,
+                ..
+            })
+        )
+    });
+
+    let nan = match signaling {
+        Some(nan) => {
+            flags.set(Flags::INVALID);
+
+            nan
+        }
+        None => operands
+            .iter()
+            .find(|operand| matches!(operand, Unpacked::Nan(_)))?,
+    };
+
+    let Unpacked::Nan(nan) = nan else {
+        unreachable!()
+    };
+
+    let mut payload = nan.payload.clone();
+
+    // The payload of a NaN is stored in the trailing significand, which has
+    // one less digit than the precision
+    // If it doesn't fit then we keep its least significant digits
+    if payload.len() > limits.precision - 1 {
+        payload.resize(limits.precision - 1);
+        payload.trim();
+    }
+
+    Some(Unpacked::Nan(Nan {
+        is_negative: nan.is_negative,
+        is_signaling: false,
+        payload,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::ArrayDigitBuf,
+    };
+
+    type Buf = ArrayDigitBuf<64>;
+
+    fn roundtrip<D: BinaryBuf>(s: &str) {
+        let decimal: D = crate::convert::decimal_from_str(s).expect("failed to parse decimal");
+
+        let unpacked = unpack::<D, Buf>(&decimal);
+        let packed: D = pack(unpacked);
+
+        assert_eq!(decimal.bytes(), packed.bytes(), "{}", s);
+    }
+
+    #[test]
+    fn unpack_pack_roundtrip() {
+        for s in [
+            "0",
+            "-0",
+            "0e10",
+            "0.000",
+            "1",
+            "-1",
+            "1.0",
+            "123.456",
+            "-123.456e7",
+            "9999999",
+            "1e-101",
+            "inf",
+            "-inf",
+            "nan",
+            "-nan",
+            "snan",
+            "nan(123)",
+            "-snan(456)",
+        ] {
+            roundtrip::<FixedBinaryBuf<4, i32>>(s);
+            roundtrip::<FixedBinaryBuf<8, i32>>(s);
+            roundtrip::<FixedBinaryBuf<16, i32>>(s);
+        }
+    }
+
+    #[test]
+    fn unpack_finite() {
+        let decimal: FixedBinaryBuf<8, i32> =
+            crate::convert::decimal_from_str("-123.4500").expect("failed to parse decimal");
+
+        match unpack::<_, Buf>(&decimal) {
+            Unpacked::Finite(finite) => {
+                assert!(finite.is_negative);
+                assert_eq!(&[0, 0, 5, 4, 3, 2, 1], finite.coefficient.digits());
+                assert_eq!(-4, finite.exponent);
+            }
+            _ => panic!("expected a finite number"),
+        }
+    }
+
+    #[test]
+    fn propagate_nan_order() {
+        let limits = Limits::<i32>::from_storage_width_bits(32);
+
+        let nan = |s: &str| -> Unpacked<Buf, i32> {
+            let decimal: FixedBinaryBuf<4, i32> =
+                crate::convert::decimal_from_str(s).expect("failed to parse decimal");
+
+            unpack(&decimal)
+        };
+
+        for (a, b, expected, is_invalid) in [
+            ("1", "2", None, false),
+            ("nan(1)", "2", Some("nan(1)"), false),
+            ("1", "nan(2)", Some("nan(2)"), false),
+            ("nan(1)", "nan(2)", Some("nan(1)"), false),
+            ("nan(1)", "snan(2)", Some("nan(2)"), true),
+            ("-snan(1)", "nan(2)", Some("-nan(1)"), true),
+        ] {
+            let mut flags = Flags::default();
+
+            let result = propagate_nan(&[&nan(a), &nan(b)], &limits, &mut flags).map(|nan| {
+                let decimal: FixedBinaryBuf<4, i32> = pack(nan);
+
+                let mut s = String::new();
+                crate::convert::decimal_to_fmt(&decimal, &mut s).unwrap();
+                s
+            });
+
+            assert_eq!(expected.map(String::from), result, "{} {}", a, b);
+            assert_eq!(is_invalid, flags.contains(Flags::INVALID), "{} {}", a, b);
+        }
+    }
+}
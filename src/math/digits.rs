@@ -0,0 +1,567 @@
+/*!
+Base-10 digit buffers for decimal arithmetic.
+
+The significand of an encoded decimal is packed into declets, which are convenient for storage
+but not for arithmetic. Before doing any math, the significand is unpacked into a buffer of digits
+that we call the _coefficient_. Digits are stored as binary values between `0` and `9`, ordered
+from least to most significant, so `123` is stored as `[3, 2, 1]`. That way the index of a digit is
+also its power of ten, and carries propagate towards the end of the buffer.
+
+The arithmetic here is all schoolbook. It's not fast, but it's simple and works the same way for
+decimal32 up to arbitrary precision.
+*/
+
+use core::cmp::Ordering;
+
+/**
+A buffer of base-10 digits, ordered from least to most significant.
+
+A coefficient with no digits is zero. Coefficients may also have leading (most significant) zeros,
+which can be removed with `trim`.
+*/
+pub(crate) trait DigitBuf: Clone + Default {
+    /**
+    Get the digits in the buffer.
+    */
+    fn digits(&self) -> &[u8];
+
+    /**
+    Get an exclusive reference to the digits in the buffer.
+    */
+    fn digits_mut(&mut self) -> &mut [u8];
+
+    /**
+    Set the number of digits in the buffer.
+
+    If the buffer grows then any new digits will be zero.
+    */
+    fn resize(&mut self, len: usize);
+
+    /**
+    Create a coefficient from ASCII digits, ordered from most to least significant.
+    */
+    fn from_ascii<I: Iterator<Item = u8>>(ascii: I) -> Self {
+        let mut buf = Self::default();
+
+        for digit in ascii {
+            let len = buf.len();
+            buf.resize(len + 1);
+            buf.digits_mut()[len] = digit - b'0';
+        }
+
+        buf.digits_mut().reverse();
+        buf.trim();
+
+        buf
+    }
+
+    /**
+    Create a coefficient from an unsigned integer.
+    */
+    fn from_u128(mut n: u128) -> Self {
+        let mut buf = Self::default();
+
+        while n > 0 {
+            let len = buf.len();
+            buf.resize(len + 1);
+            buf.digits_mut()[len] = (n % 10) as u8;
+
+            n /= 10;
+        }
+
+        buf
+    }
+
+    /**
+    Create a coefficient with `len` nines.
+    */
+    fn nines(len: usize) -> Self {
+        let mut buf = Self::default();
+
+        buf.resize(len);
+        buf.digits_mut().fill(9);
+
+        buf
+    }
+
+    /**
+    Get the digits in the buffer as ASCII, ordered from most to least significant.
+
+    Zero is returned as the single digit `0`.
+    */
+    fn to_ascii(&self) -> Self {
+        let mut ascii = self.clone();
+        ascii.trim();
+
+        if ascii.is_zero() {
+            ascii.resize(1);
+        }
+
+        let digits = ascii.digits_mut();
+        digits.reverse();
+
+        for digit in digits {
+            *digit += b'0';
+        }
+
+        ascii
+    }
+
+    /**
+    The number of digits in the buffer, including any leading zeros.
+    */
+    fn len(&self) -> usize {
+        self.digits().len()
+    }
+
+    /**
+    Whether the coefficient is zero.
+    */
+    fn is_zero(&self) -> bool {
+        self.digits().iter().all(|d| *d == 0)
+    }
+
+    /**
+    Get the digit at the given power of ten.
+
+    If the digit is beyond the end of the buffer then it's zero.
+    */
+    fn digit(&self, i: usize) -> u8 {
+        self.digits().get(i).copied().unwrap_or(0)
+    }
+
+    /**
+    Remove any leading zeros.
+    */
+    fn trim(&mut self) {
+        let len = self
+            .digits()
+            .iter()
+            .rposition(|d| *d != 0)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        self.resize(len);
+    }
+
+    /**
+    Multiply the coefficient by `10^n`.
+    */
+    fn shift_left(&mut self, n: usize) {
+        let len = self.len();
+
+        if n == 0 || len == 0 {
+            return;
+        }
+
+        self.resize(len + n);
+
+        let digits = self.digits_mut();
+        digits.copy_within(0..len, n);
+        digits[..n].fill(0);
+    }
+
+    /**
+    Divide the coefficient by `10^n`, discarding the remainder.
+    */
+    fn shift_right(&mut self, n: usize) {
+        let len = self.len();
+
+        if n >= len {
+            self.resize(0);
+        } else if n > 0 {
+            self.digits_mut().copy_within(n..len, 0);
+            self.resize(len - n);
+        }
+    }
+
+    /**
+    Add `1` to the coefficient.
+    */
+    fn increment(&mut self) {
+        for digit in self.digits_mut() {
+            if *digit == 9 {
+                *digit = 0;
+            } else {
+                *digit += 1;
+                return;
+            }
+        }
+
+        // If we get this far then every digit carried
+        let len = self.len();
+        self.resize(len + 1);
+        self.digits_mut()[len] = 1;
+    }
+
+    /**
+    Add another coefficient to this one.
+    */
+    fn add_assign(&mut self, other: &Self) {
+        if other.len() > self.len() {
+            self.resize(other.len());
+        }
+
+        let mut carry = 0;
+        for (i, digit) in self.digits_mut().iter_mut().enumerate() {
+            let sum = *digit + other.digit(i) + carry;
+
+            *digit = sum % 10;
+            carry = sum / 10;
+        }
+
+        if carry > 0 {
+            let len = self.len();
+            self.resize(len + 1);
+            self.digits_mut()[len] = carry;
+        }
+    }
+
+    /**
+    Subtract another coefficient from this one.
+
+    The other coefficient must not be larger than this one.
+    */
+    fn sub_assign(&mut self, other: &Self) {
+        debug_assert_ne!(Ordering::Less, self.cmp_magnitude(other));
+
+        let mut borrow = 0;
+        for (i, digit) in self.digits_mut().iter_mut().enumerate() {
+            let sub = other.digit(i) + borrow;
+
+            if *digit >= sub {
+                *digit -= sub;
+                borrow = 0;
+            } else {
+                *digit = *digit + 10 - sub;
+                borrow = 1;
+            }
+        }
+
+        debug_assert_eq!(0, borrow);
+
+        self.trim();
+    }
+
+    /**
+    Multiply this coefficient by another.
+    */
+    fn mul(&self, other: &Self) -> Self {
+        let mut product = Self::default();
+
+        if self.is_zero() || other.is_zero() {
+            return product;
+        }
+
+        product.resize(self.len() + other.len());
+
+        for (i, a) in self.digits().iter().enumerate() {
+            if *a == 0 {
+                continue;
+            }
+
+            // Each step is at most `9 + 9 * 9 + 8`, so it fits in a byte
+            let mut carry = 0;
+            for (j, b) in other.digits().iter().enumerate() {
+                let digit = &mut product.digits_mut()[i + j];
+                let step = *digit + a * b + carry;
+
+                *digit = step % 10;
+                carry = step / 10;
+            }
+
+            product.digits_mut()[i + other.len()] = carry;
+        }
+
+        product.trim();
+        product
+    }
+
+    /**
+    Divide this coefficient by another, returning the quotient and remainder.
+
+    The other coefficient must not be zero.
+    */
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "attempt to divide a coefficient by zero");
+
+        let mut quotient = Self::default();
+        quotient.resize(self.len());
+
+        let mut remainder = Self::default();
+
+        // Bring down one digit at a time, working out how many times the divisor fits
+        for i in (0..self.len()).rev() {
+            let len = remainder.len();
+            remainder.resize(len + 1);
+
+            let digits = remainder.digits_mut();
+            digits.copy_within(0..len, 1);
+            digits[0] = self.digit(i);
+
+            remainder.trim();
+
+            let mut digit = 0;
+            while remainder.cmp_magnitude(other) != Ordering::Less {
+                remainder.sub_assign(other);
+                digit += 1;
+            }
+
+            quotient.digits_mut()[i] = digit;
+        }
+
+        quotient.trim();
+
+        (quotient, remainder)
+    }
+
+    /**
+    Compare the value of two coefficients.
+    */
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        let len = self.len().max(other.len());
+
+        for i in (0..len).rev() {
+            match self.digit(i).cmp(&other.digit(i)) {
+                Ordering::Equal => continue,
+                cmp => return cmp,
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+/**
+A fixed-size digit buffer.
+
+This buffer is used for decimals that need to work in no-std environments. The size of the buffer
+needs to be large enough for any intermediate results in calculations; the exact size depends on
+the precision of the decimal being calculated.
+*/
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArrayDigitBuf<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for ArrayDigitBuf<N> {
+    fn default() -> Self {
+        ArrayDigitBuf {
+            buf: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> DigitBuf for ArrayDigitBuf<N> {
+    fn digits(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    fn digits_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+
+    fn resize(&mut self, len: usize) {
+        assert!(
+            len <= N,
+            "a coefficient of {} digits overflowed its {} digit buffer",
+            len,
+            N
+        );
+
+        if len > self.len {
+            self.buf[self.len..len].fill(0);
+        }
+
+        self.len = len;
+    }
+}
+
+#[cfg(feature = "arbitrary-precision")]
+impl DigitBuf for Vec<u8> {
+    fn digits(&self) -> &[u8] {
+        self
+    }
+
+    fn digits_mut(&mut self) -> &mut [u8] {
+        self
+    }
+
+    fn resize(&mut self, len: usize) {
+        Vec::resize(self, len, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Buf = ArrayDigitBuf<32>;
+
+    fn digits(s: &str) -> Buf {
+        Buf::from_ascii(s.bytes())
+    }
+
+    fn ascii(buf: &Buf) -> String {
+        String::from_utf8(buf.to_ascii().digits().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn from_ascii() {
+        assert_eq!(&[3, 2, 1], digits("123").digits());
+        assert_eq!(&[3, 2, 1], digits("000123").digits());
+        assert!(digits("000").is_zero());
+        assert_eq!(0, digits("000").len());
+
+        assert_eq!("0", ascii(&digits("0")));
+        assert_eq!("1230", ascii(&digits("01230")));
+    }
+
+    #[test]
+    fn from_u128() {
+        assert_eq!("0", ascii(&Buf::from_u128(0)));
+        assert_eq!("1234567890", ascii(&Buf::from_u128(1234567890)));
+        assert_eq!(
+            u128::MAX.to_string(),
+            String::from_utf8(
+                ArrayDigitBuf::<40>::from_u128(u128::MAX)
+                    .to_ascii()
+                    .digits()
+                    .to_vec()
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn shift() {
+        let mut buf = digits("123");
+
+        buf.shift_left(2);
+        assert_eq!("12300", ascii(&buf));
+
+        buf.shift_right(3);
+        assert_eq!("12", ascii(&buf));
+
+        buf.shift_right(3);
+        assert_eq!("0", ascii(&buf));
+
+        buf.shift_left(3);
+        assert_eq!(0, buf.len());
+    }
+
+    #[test]
+    fn increment() {
+        for (a, b) in [
+            ("0", "1"),
+            ("8", "9"),
+            ("9", "10"),
+            ("199", "200"),
+            ("999", "1000"),
+        ] {
+            let mut buf = digits(a);
+            buf.increment();
+
+            assert_eq!(b, ascii(&buf), "{} + 1", a);
+        }
+    }
+
+    #[test]
+    fn add() {
+        for (a, b, sum) in [
+            ("0", "0", "0"),
+            ("1", "0", "1"),
+            ("0", "1", "1"),
+            ("5", "5", "10"),
+            ("999", "1", "1000"),
+            ("1", "999", "1000"),
+            ("123456789", "987654321", "1111111110"),
+        ] {
+            let mut buf = digits(a);
+            buf.add_assign(&digits(b));
+
+            assert_eq!(sum, ascii(&buf), "{} + {}", a, b);
+        }
+    }
+
+    #[test]
+    fn sub() {
+        for (a, b, diff) in [
+            ("0", "0", "0"),
+            ("1", "0", "1"),
+            ("1", "1", "0"),
+            ("10", "1", "9"),
+            ("1000", "1", "999"),
+            ("1111111110", "987654321", "123456789"),
+        ] {
+            let mut buf = digits(a);
+            buf.sub_assign(&digits(b));
+
+            assert_eq!(diff, ascii(&buf), "{} - {}", a, b);
+        }
+    }
+
+    #[test]
+    fn mul() {
+        for (a, b, product) in [
+            ("0", "0", "0"),
+            ("0", "123", "0"),
+            ("1", "123", "123"),
+            ("9", "9", "81"),
+            ("99", "99", "9801"),
+            ("1000", "1000", "1000000"),
+            ("123456789", "987654321", "121932631112635269"),
+        ] {
+            assert_eq!(product, ascii(&digits(a).mul(&digits(b))), "{} * {}", a, b);
+            assert_eq!(product, ascii(&digits(b).mul(&digits(a))), "{} * {}", b, a);
+        }
+    }
+
+    #[test]
+    fn div_rem() {
+        for (a, b, quotient, remainder) in [
+            ("0", "1", "0", "0"),
+            ("1", "1", "1", "0"),
+            ("7", "2", "3", "1"),
+            ("100", "7", "14", "2"),
+            ("1", "3", "0", "1"),
+            ("121932631112635269", "987654321", "123456789", "0"),
+            ("121932631112635270", "123456789", "987654321", "1"),
+        ] {
+            let (q, r) = digits(a).div_rem(&digits(b));
+
+            assert_eq!(quotient, ascii(&q), "{} / {}", a, b);
+            assert_eq!(remainder, ascii(&r), "{} % {}", a, b);
+        }
+    }
+
+    #[test]
+    fn cmp() {
+        for (a, b, cmp) in [
+            ("0", "0", Ordering::Equal),
+            ("1", "0", Ordering::Greater),
+            ("0", "1", Ordering::Less),
+            ("10", "9", Ordering::Greater),
+            ("123", "124", Ordering::Less),
+            ("00123", "123", Ordering::Equal),
+        ] {
+            let mut a_buf = Buf::default();
+            a_buf.resize(a.len());
+            for (i, d) in a.bytes().rev().enumerate() {
+                a_buf.digits_mut()[i] = d - b'0';
+            }
+
+            assert_eq!(cmp, a_buf.cmp_magnitude(&digits(b)), "{} cmp {}", a, b);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        let mut buf = ArrayDigitBuf::<4>::default();
+
+        buf.resize(5);
+    }
+}
@@ -0,0 +1,535 @@
+/*!
+Division and remainders of decimals.
+
+Division follows the General Decimal Arithmetic specification. The quotient is calculated to one
+more digit than the precision of the result, along with a sticky digit that records whether
+the remainder was non-zero. That's enough to round it correctly. If the division is exact then
+the quotient uses the exponent closest to the ideal one, which is the difference between the
+exponents of the operands.
+
+Integer division and remainders need the integer part of the quotient. If the integer part has
+more digits than the precision of the result then the operation is invalid.
+*/
+
+use core::cmp::Ordering;
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        adjusted_exponent,
+        distance,
+        pack,
+        propagate_nan,
+        round_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+};
+
+/**
+The kind of division to perform.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Division {
+    /**
+    Divide, rounding the quotient to the precision of the result.
+    */
+    Quotient,
+    /**
+    Divide, truncating the quotient to an integer.
+    */
+    Integer,
+    /**
+    The remainder after truncating the quotient to an integer.
+    */
+    Remainder,
+    /**
+    The remainder after rounding the quotient to the nearest integer, with ties going to even.
+    */
+    RemainderNear,
+}
+
+/**
+Divide two decimals.
+*/
+pub(crate) fn decimal_div<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    division: Division,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(div(
+        unpack::<D, C>(a),
+        unpack(b),
+        division,
+        limits,
+        mode,
+        flags,
+    ))
+}
+
+/**
+Divide two unpacked decimals.
+*/
+pub(crate) fn div<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    division: Division,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a, &b], limits, flags) {
+        return nan;
+    }
+
+    let is_remainder = matches!(division, Division::Remainder | Division::RemainderNear);
+
+    match (a, b) {
+        // ±inf / ±inf
+        (Unpacked::Infinity { .. }, Unpacked::Infinity { .. }) => Unpacked::invalid(flags),
+        // ±inf % ±123
+        (Unpacked::Infinity { .. }, _) if is_remainder => Unpacked::invalid(flags),
+        // ±inf / ±123
+        (
+            Unpacked::Infinity { is_negative: a },
+            Unpacked::Finite(Finite { is_negative: b, .. }),
+        ) => Unpacked::Infinity {
+            is_negative: a != b,
+        },
+        // ±123 % ±inf
+        (Unpacked::Finite(a), Unpacked::Infinity { .. }) if is_remainder => {
+            round_finite(a, limits, mode, flags)
+        }
+        // ±123 / ±inf
+        (Unpacked::Finite(a), Unpacked::Infinity { is_negative: b }) => {
+            let exponent = match division {
+                Division::Integer => E::from_i32(0),
+                _ => {
+                    flags.set(Flags::CLAMPED);
+
+                    limits.etiny()
+                }
+            };
+
+            Unpacked::Finite(Finite {
+                is_negative: a.is_negative != b,
+                coefficient: C::default(),
+                exponent,
+            })
+        }
+        // ±123 / ±123
+        (Unpacked::Finite(mut a), Unpacked::Finite(mut b)) => {
+            a.coefficient.trim();
+            b.coefficient.trim();
+
+            match (a.coefficient.is_zero(), b.coefficient.is_zero()) {
+                // ±0 / ±0
+                (true, true) => Unpacked::invalid(flags),
+                // ±0 % ±123 and ±123 % ±0
+                (_, true) if is_remainder => Unpacked::invalid(flags),
+                // ±123 / ±0
+                (false, true) => {
+                    flags.set(Flags::DIVISION_BY_ZERO);
+
+                    Unpacked::Infinity {
+                        is_negative: a.is_negative != b.is_negative,
+                    }
+                }
+                _ => match division {
+                    Division::Quotient => {
+                        round_finite(div_finite(a, b, limits), limits, mode, flags)
+                    }
+                    Division::Integer => div_integer_finite(a, b, limits, flags),
+                    Division::Remainder | Division::RemainderNear => {
+                        match rem_finite(a, b, division == Division::RemainderNear, limits) {
+                            Some(rem) => round_finite(rem, limits, mode, flags),
+                            None => Unpacked::invalid(flags),
+                        }
+                    }
+                },
+            }
+        }
+        _ => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Divide two finite numbers, where the divisor isn't zero.
+
+The result has enough digits to be rounded correctly.
+*/
+pub(crate) fn div_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    limits: &Limits<E>,
+) -> Finite<C, E> {
+    let is_negative = a.is_negative != b.is_negative;
+    let ideal_exponent = a.exponent.clone() - b.exponent.clone();
+
+    if a.coefficient.is_zero() {
+        return Finite {
+            is_negative,
+            coefficient: a.coefficient,
+            exponent: ideal_exponent,
+        };
+    }
+
+    // Scale the dividend so the quotient has at least one more digit than the precision
+    let scale = (limits.precision + 1 + b.coefficient.len()).saturating_sub(a.coefficient.len());
+
+    let mut dividend = a.coefficient;
+    dividend.shift_left(scale);
+
+    let (mut coefficient, remainder) = dividend.div_rem(&b.coefficient);
+    let mut exponent = ideal_exponent.lower(scale);
+
+    if remainder.is_zero() {
+        // If the division is exact then remove trailing zeros until the exponent is ideal
+        while exponent < ideal_exponent && coefficient.digit(0) == 0 {
+            coefficient.shift_right(1);
+            exponent = exponent.raise(1);
+        }
+    } else {
+        // If the division is inexact then add a sticky digit so it rounds correctly
+        coefficient.shift_left(1);
+        coefficient.digits_mut()[0] = 1;
+        exponent = exponent.lower(1);
+    }
+
+    Finite {
+        is_negative,
+        coefficient,
+        exponent,
+    }
+}
+
+/**
+Divide two finite numbers, truncating the quotient to an integer.
+*/
+fn div_integer_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    limits: &Limits<E>,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    let is_negative = a.is_negative != b.is_negative;
+
+    match div_rem_integer(&a, &b, limits) {
+        Some((coefficient, _, _)) => Unpacked::Finite(Finite {
+            is_negative,
+            coefficient,
+            exponent: E::from_i32(0),
+        }),
+        None => Unpacked::invalid(flags),
+    }
+}
+
+/**
+Get the remainder of dividing two finite numbers, where the divisor isn't zero.
+
+If `is_near` is `true` then the quotient is rounded to the nearest integer, with ties going
+to even. Otherwise the quotient is truncated.
+
+If the integer quotient has more digits than the precision then the remainder can't be
+calculated, and this method returns `None`.
+*/
+fn rem_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    is_near: bool,
+    limits: &Limits<E>,
+) -> Option<Finite<C, E>> {
+    let (quotient, mut remainder, exponent) = div_rem_integer(&a, &b, limits)?;
+
+    let mut is_negative = a.is_negative;
+
+    // If the dividend is much smaller than the divisor then it's also less than half of it
+    let is_small = adjusted_exponent(&a) < adjusted_exponent(&b).lower(1);
+
+    if is_near && !is_small && !remainder.is_zero() {
+        // If the remainder is more than half the divisor then round the quotient up
+        // That makes the remainder the distance to the next multiple of the divisor instead
+        let mut divisor = b.coefficient;
+        divisor.shift_left(distance(&exponent, &b.exponent));
+
+        let mut twice = remainder.clone();
+        twice.add_assign(&remainder);
+
+        let round_up = match twice.cmp_magnitude(&divisor) {
+            Ordering::Greater => true,
+            Ordering::Equal => quotient.digit(0) % 2 == 1,
+            Ordering::Less => false,
+        };
+
+        if round_up {
+            // The rounded quotient also needs to fit the precision
+            let mut rounded = quotient;
+            rounded.increment();
+
+            if rounded.len() > limits.precision {
+                return None;
+            }
+
+            divisor.sub_assign(&remainder);
+
+            remainder = divisor;
+            is_negative = !is_negative;
+        }
+    }
+
+    Some(Finite {
+        is_negative,
+        coefficient: remainder,
+        exponent,
+    })
+}
+
+/**
+Divide two finite numbers, returning the integer quotient and the remainder.
+
+The remainder uses the smaller exponent of the operands, which is also returned.
+
+If the integer quotient has more digits than the precision then this method returns `None`.
+*/
+fn div_rem_integer<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: &Finite<C, E>,
+    b: &Finite<C, E>,
+    limits: &Limits<E>,
+) -> Option<(C, C, E)> {
+    let exponent = a.exponent.clone().min(b.exponent.clone());
+
+    // If the dividend is zero, or is much smaller than the divisor, then the quotient is zero
+    // This check also keeps the shifts needed to align the operands small
+    if a.coefficient.is_zero() || adjusted_exponent(a) < adjusted_exponent(b).lower(1) {
+        let mut remainder = a.coefficient.clone();
+        remainder.shift_left(distance(&exponent, &a.exponent));
+
+        return Some((C::default(), remainder, exponent));
+    }
+
+    // If the dividend is much larger than the divisor then the quotient won't fit
+    if adjusted_exponent(a) > adjusted_exponent(b).raise(limits.precision) {
+        return None;
+    }
+
+    let mut dividend = a.coefficient.clone();
+    dividend.shift_left(distance(&exponent, &a.exponent));
+
+    let mut divisor = b.coefficient.clone();
+    divisor.shift_left(distance(&exponent, &b.exponent));
+
+    let (quotient, remainder) = dividend.div_rem(&divisor);
+
+    if quotient.len() > limits.precision {
+        return None;
+    }
+
+    Some((quotient, remainder, exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::parse;
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::ArrayDigitBuf,
+    };
+
+    fn check<const N: usize>(
+        a: &str,
+        b: &str,
+        division: Division,
+        expected: &str,
+        expected_flags: Flags,
+    ) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = decimal_div::<_, ArrayDigitBuf<144>>(
+            &parse::<N>(a),
+            &parse::<N>(b),
+            division,
+            &limits,
+            RoundingMode::HalfEven,
+            &mut flags,
+        );
+
+        let mut s = String::new();
+        crate::convert::decimal_to_fmt(&result, &mut s).unwrap();
+
+        assert_eq!(expected, s, "{} {:?} {}", a, division, b);
+        assert_eq!(expected_flags, flags, "{} {:?} {}", a, division, b);
+    }
+
+    #[test]
+    fn div_quotient() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        for (a, b, expected, expected_flags) in [
+            ("1", "1", "1", Flags::default()),
+            ("2", "1", "2", Flags::default()),
+            ("1", "4", "0.25", Flags::default()),
+            ("1", "8", "0.125", Flags::default()),
+            ("12", "12", "1", Flags::default()),
+            ("8.00", "2", "4.00", Flags::default()),
+            ("2.400", "2.0", "1.20", Flags::default()),
+            ("1000", "100", "10", Flags::default()),
+            ("1000", "1", "1000", Flags::default()),
+            ("2.40e6", "2", "1.20e6", Flags::default()),
+            ("-6", "3", "-2", Flags::default()),
+            ("0", "3", "0", Flags::default()),
+            ("0.00", "-3", "-0.00", Flags::default()),
+            ("1", "3", "0.3333333", rounded),
+            ("2", "3", "0.6666667", rounded),
+            ("-2", "3", "-0.6666667", rounded),
+            ("1e90", "1e-10", "inf", Flags::OVERFLOW | rounded),
+        ] {
+            check::<4>(a, b, Division::Quotient, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn div_integer() {
+        for (a, b, expected, expected_flags) in [
+            ("2", "3", "0", Flags::default()),
+            ("10", "3", "3", Flags::default()),
+            ("1", "0.3", "3", Flags::default()),
+            ("-10", "3", "-3", Flags::default()),
+            ("10.5", "1", "10", Flags::default()),
+            ("1e10", "1", "nan", Flags::INVALID),
+            ("1", "0", "inf", Flags::DIVISION_BY_ZERO),
+            ("0", "0", "nan", Flags::INVALID),
+        ] {
+            check::<4>(a, b, Division::Integer, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn rem() {
+        for (a, b, expected, expected_flags) in [
+            ("2.1", "3", "2.1", Flags::default()),
+            ("10", "3", "1", Flags::default()),
+            ("-10", "3", "-1", Flags::default()),
+            ("10.2", "1", "0.2", Flags::default()),
+            ("10", "0.3", "0.1", Flags::default()),
+            ("3.6", "1.3", "1.0", Flags::default()),
+            ("-0", "3", "-0", Flags::default()),
+            ("1", "inf", "1", Flags::default()),
+            ("inf", "1", "nan", Flags::INVALID),
+            ("1", "0", "nan", Flags::INVALID),
+            ("1e10", "1", "nan", Flags::INVALID),
+        ] {
+            check::<4>(a, b, Division::Remainder, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn rem_near() {
+        for (a, b, expected) in [
+            ("2.1", "3", "-0.9"),
+            ("10", "6", "-2"),
+            ("10", "3", "1"),
+            ("-10", "3", "-1"),
+            ("10.2", "1", "0.2"),
+            ("10", "0.3", "0.1"),
+            ("3.6", "1.3", "-0.3"),
+            ("5", "2", "1"),
+            ("7", "2", "-1"),
+        ] {
+            check::<4>(a, b, Division::RemainderNear, expected, Flags::default());
+        }
+    }
+
+    #[test]
+    fn div_special() {
+        for (a, b, expected, expected_flags) in [
+            ("inf", "inf", "nan", Flags::INVALID),
+            ("inf", "-2", "-inf", Flags::default()),
+            ("2", "-inf", "-0e-101", Flags::CLAMPED),
+            ("1", "0", "inf", Flags::DIVISION_BY_ZERO),
+            ("-1", "0", "-inf", Flags::DIVISION_BY_ZERO),
+            ("1", "-0", "-inf", Flags::DIVISION_BY_ZERO),
+            ("0", "0", "nan", Flags::INVALID),
+            ("nan(4)", "0", "nan(4)", Flags::default()),
+            ("1", "snan(4)", "nan(4)", Flags::INVALID),
+        ] {
+            check::<4>(a, b, Division::Quotient, expected, expected_flags);
+        }
+    }
+
+    macro_rules! matches_decnumber {
+        ($name:ident, $n:literal, $dec:ty, $precision:literal, $emax:literal) => {
+            #[test]
+            #[cfg(target_endian = "little")]
+            fn $name() {
+                let mut rng = crate::math::tests::Rng(0xd1b54a32d192ed03);
+                let mut ctx = dec::Context::<$dec>::default();
+                let limits = Limits::from_storage_width_bits($n * 8);
+
+                for _ in 0..10_000 {
+                    let mut exponent = || {
+                        if rng.next(4) == 0 {
+                            rng.next(2 * $emax) as i32 - $emax as i32
+                        } else {
+                            rng.next(40) as i32 - 20
+                        }
+                    };
+                    let (ea, eb) = (exponent(), exponent());
+
+                    let a = rng.decimal($precision, ea);
+                    let b = rng.decimal($precision, eb);
+
+                    let (da, db) = (parse::<$n>(&a), parse::<$n>(&b));
+                    let dec = |d: &FixedBinaryBuf<$n, i32>| {
+                        <$dec>::from_ne_bytes(d.bytes().try_into().unwrap())
+                    };
+
+                    let div = |division| {
+                        decimal_div::<_, ArrayDigitBuf<144>>(
+                            &da,
+                            &db,
+                            division,
+                            &limits,
+                            RoundingMode::HalfEven,
+                            &mut Flags::default(),
+                        )
+                    };
+
+                    for (division, expected) in [
+                        (Division::Quotient, ctx.div(dec(&da), dec(&db))),
+                        (Division::Integer, ctx.div_integer(dec(&da), dec(&db))),
+                        (Division::Remainder, ctx.rem(dec(&da), dec(&db))),
+                        (Division::RemainderNear, ctx.rem_near(dec(&da), dec(&db))),
+                    ] {
+                        assert_eq!(
+                            expected.to_ne_bytes(),
+                            div(division).bytes(),
+                            "{} {:?} {}",
+                            a,
+                            division,
+                            b
+                        );
+                    }
+                }
+            }
+        };
+    }
+
+    matches_decnumber!(matches_decnumber_decimal64, 8, dec::Decimal64, 16, 369);
+    matches_decnumber!(matches_decnumber_decimal128, 16, dec::Decimal128, 34, 6000);
+}
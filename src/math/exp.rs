@@ -0,0 +1,1297 @@
+/*!
+Exponentials, logarithms, and powers of decimals.
+
+The results of these functions can't generally be calculated exactly. Instead, they're approximated
+using a working precision with some extra guard digits, and then rounded. If the approximation is
+too close to a rounding boundary to tell which way the exact result would round then it's calculated
+again with more guard digits. This is Ziv's strategy, and means results are correctly rounded.
+
+The approximations are calculated using series that converge quickly for small arguments:
+
+- `exp(x)` divides `x` by a power of ten until it's small, sums the Taylor series, and then raises
+  the result back up by the same power of ten.
+- `ln(x)` splits `x` into `m * 10^a`, takes square roots of `m` until it's close to `1`, and then
+  sums the series for `2 * atanh((m - 1) / (m + 1))`.
+- `pow(x, y)` multiplies by repeated squaring when `y` is a small integer, and otherwise
+  calculates `exp(y * ln(x))`.
+*/
+
+use core::fmt;
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        add_finite,
+        adjusted_exponent,
+        div_finite,
+        pack,
+        propagate_nan,
+        round_finite,
+        sqrt_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+};
+
+/**
+Raise `e` to the power of a decimal.
+*/
+pub(crate) fn decimal_exp<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(exp(unpack::<D, C>(a), limits, mode, flags))
+}
+
+/**
+Get the natural logarithm of a decimal.
+*/
+pub(crate) fn decimal_ln<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(ln(unpack::<D, C>(a), false, limits, mode, flags))
+}
+
+/**
+Get the base-10 logarithm of a decimal.
+*/
+pub(crate) fn decimal_log10<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(ln(unpack::<D, C>(a), true, limits, mode, flags))
+}
+
+/**
+Raise a decimal to the power of another.
+*/
+pub(crate) fn decimal_pow<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(pow(unpack::<D, C>(a), unpack(b), limits, mode, flags))
+}
+
+/**
+Raise `e` to the power of an unpacked decimal.
+*/
+pub(crate) fn exp<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return nan;
+    }
+
+    match a {
+        // exp(+inf)
+        Unpacked::Infinity { is_negative: false } => a,
+        // exp(-inf)
+        Unpacked::Infinity { is_negative: true } => Unpacked::Finite(int(0)),
+        // exp(0)
+        Unpacked::Finite(zero) if zero.coefficient.is_zero() => Unpacked::Finite(int(1)),
+        // exp(123)
+        Unpacked::Finite(x) => {
+            // If `x` is so small that `exp(x) = 1 + x` to well beyond the precision of the result
+            // then the remaining terms of the series can only affect rounding
+            if adjusted_exponent(&x) < E::from_i32(-(limits.precision as i32) - 3) {
+                return round_finite(add_finite(int(1), x, limits, mode), limits, mode, flags);
+            }
+
+            // If `x` is so large that the result is definitely out of range then don't calculate it
+            if let Some(is_overflow) = exp_out_of_range(&x, limits) {
+                return out_of_range(false, is_overflow, limits, mode, flags);
+            }
+
+            round_approximation(limits, mode, flags, |working, working_flags| {
+                working_flags.set(Flags::INEXACT);
+
+                exp_approx(&x, working)
+            })
+        }
+        Unpacked::Nan(_) => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Get the natural or base-10 logarithm of an unpacked decimal.
+*/
+pub(crate) fn ln<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    is_log10: bool,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return nan;
+    }
+
+    match a {
+        // ln(+inf)
+        Unpacked::Infinity { is_negative: false } => a,
+        // ln(±0)
+        Unpacked::Finite(zero) if zero.coefficient.is_zero() => {
+            Unpacked::Infinity { is_negative: true }
+        }
+        // ln(-inf), ln(-123)
+        Unpacked::Infinity { is_negative: true }
+        | Unpacked::Finite(Finite {
+            is_negative: true, ..
+        }) => Unpacked::invalid(flags),
+        // log10(10^n) is exactly `n`
+        Unpacked::Finite(x) if is_power_of_ten(&x) && (is_log10 || is_one(&x)) => round_finite(
+            integer_to_finite(&adjusted_exponent(&x)),
+            limits,
+            mode,
+            flags,
+        ),
+        // ln(123)
+        Unpacked::Finite(x) => {
+            round_approximation(limits, mode, flags, |working, working_flags| {
+                working_flags.set(Flags::INEXACT);
+
+                if is_log10 {
+                    let working = with_precision(working, working.precision + 2);
+
+                    div_working(
+                        ln_approx(&x, &working),
+                        ln_10(&working),
+                        &working,
+                        &mut Flags::default(),
+                    )
+                } else {
+                    ln_approx(&x, working)
+                }
+            })
+        }
+        Unpacked::Nan(_) => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Raise an unpacked decimal to the power of another.
+
+This follows the semantics of `power` in the General Decimal Arithmetic specification.
+*/
+pub(crate) fn pow<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a, &b], limits, flags) {
+        return nan;
+    }
+
+    let b_integer = match &b {
+        Unpacked::Finite(b) => integer(b),
+        _ => None,
+    };
+
+    // The result is only negative if `a` is negative and `b` is an odd integer
+    let is_negative = match (&a, b_integer) {
+        (Unpacked::Finite(Finite { is_negative, .. }), Some(Integral { is_odd, .. }))
+        | (Unpacked::Infinity { is_negative }, Some(Integral { is_odd, .. })) => {
+            *is_negative && is_odd
+        }
+        _ => false,
+    };
+
+    let a_is_zero = matches!(&a, Unpacked::Finite(a) if a.coefficient.is_zero());
+    let a_is_negative = matches!(
+        &a,
+        Unpacked::Finite(Finite {
+            is_negative: true,
+            ..
+        }) | Unpacked::Infinity { is_negative: true }
+    );
+
+    match (a, b) {
+        // 0 ^ 0
+        (_, Unpacked::Finite(b)) if a_is_zero && b.coefficient.is_zero() => {
+            Unpacked::invalid(flags)
+        }
+        // ±123 ^ 0
+        (_, Unpacked::Finite(b)) if b.coefficient.is_zero() => Unpacked::Finite(int(1)),
+        // -123 ^ 1.5, -123 ^ ±inf
+        _ if a_is_negative && !a_is_zero && b_integer.is_none() => Unpacked::invalid(flags),
+        // ±inf ^ ±123
+        (Unpacked::Infinity { .. }, Unpacked::Finite(b)) => {
+            if b.is_negative {
+                Unpacked::Finite(Finite {
+                    is_negative,
+                    ..int(0)
+                })
+            } else {
+                Unpacked::Infinity { is_negative }
+            }
+        }
+        // ±0 ^ ±123, ±0 ^ ±inf
+        (Unpacked::Finite(_), b) if a_is_zero => {
+            if matches!(
+                b,
+                Unpacked::Finite(Finite {
+                    is_negative: true,
+                    ..
+                }) | Unpacked::Infinity { is_negative: true }
+            ) {
+                Unpacked::Infinity { is_negative }
+            } else {
+                Unpacked::Finite(Finite {
+                    is_negative,
+                    ..int(0)
+                })
+            }
+        }
+        // ±123 ^ ±inf
+        (
+            Unpacked::Finite(a),
+            Unpacked::Infinity {
+                is_negative: b_is_negative,
+            },
+        ) => {
+            if is_one(&a) {
+                // 1 ^ ±inf is 1, but inexact
+                let mut one: Finite<C, E> = int(1);
+                one.coefficient.shift_left(limits.precision - 1);
+                one.exponent = one.exponent.lower(limits.precision - 1);
+
+                flags.set(Flags::INEXACT | Flags::ROUNDED);
+
+                Unpacked::Finite(one)
+            } else if (adjusted_exponent(&a) < E::zero()) != b_is_negative {
+                // 0.5 ^ +inf, 2 ^ -inf
+                Unpacked::Finite(int(0))
+            } else {
+                // 0.5 ^ -inf, 2 ^ +inf
+                Unpacked::Infinity { is_negative: false }
+            }
+        }
+        // ±123 ^ ±123
+        (Unpacked::Finite(a), Unpacked::Finite(b)) => {
+            pow_finite(a, b, b_integer, is_negative, limits, mode, flags)
+        }
+        _ => unreachable!("NaNs and infinities have already been handled"),
+    }
+}
+
+/**
+Raise a non-zero finite number to the power of a non-zero finite number.
+*/
+fn pow_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut a: Finite<C, E>,
+    b: Finite<C, E>,
+    b_integer: Option<Integral>,
+    is_negative: bool,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    a.is_negative = false;
+
+    // Estimate `b * ln(a)` to check whether the result is definitely out of range
+    // This also keeps the intermediate results of repeated squaring in range
+    let estimate = mul_working(
+        b.clone(),
+        ln_approx(&a, &working_limits(10, limits)),
+        &working_limits(10, limits),
+        &mut Flags::default(),
+    );
+
+    if let Some(is_overflow) = exp_out_of_range(&estimate, limits) {
+        return out_of_range(is_negative, is_overflow, limits, mode, flags);
+    }
+
+    // Small integer powers are calculated by repeated squaring
+    // These may be exact, like `2 ^ 10` or `0.5 ^ -2`
+    if let Some(Integral { value: Some(n), .. }) = b_integer {
+        let result = round_approximation(limits, mode, flags, |working, working_flags| {
+            let working = with_precision(working, working.precision + digits(n as u128) + 1);
+
+            let power = pow_integer(&a, n, &working, working_flags);
+
+            if b.is_negative {
+                div_working(int(1), power, &working, working_flags)
+            } else {
+                power
+            }
+        });
+
+        return negate_if(result, is_negative);
+    }
+
+    // Other powers are calculated as `exp(b * ln(a))`
+    // The absolute error of `b * ln(a)` becomes the relative error of the result,
+    // so it needs enough extra digits to cover its integer part
+    let integer_digits = adjusted_exponent(&estimate)
+        .to_i32()
+        .map(|adjusted| adjusted.max(0) as usize + 1)
+        .unwrap_or(1);
+
+    let result = round_approximation(limits, mode, flags, |working, working_flags| {
+        let product_limits = with_precision(working, working.precision + integer_digits + 3);
+
+        let product = mul_working(
+            b.clone(),
+            ln_approx(&a, &product_limits),
+            &product_limits,
+            working_flags,
+        );
+
+        if product.coefficient.is_zero() {
+            int(1)
+        } else {
+            working_flags.set(Flags::INEXACT);
+
+            exp_approx(&product, working)
+        }
+    });
+
+    negate_if(result, is_negative)
+}
+
+/**
+Round an approximation of a result so that it's correctly rounded.
+
+The `approximate` function is called with working limits, and must return a value within
+`10^(3 - precision)` of the exact result, relative to its magnitude, where `precision` is the
+precision of the working limits. If the approximation is inexact then the function must set
+the inexact flag it's given.
+*/
+fn round_approximation<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+    mut approximate: impl FnMut(&Limits<E>, &mut Flags) -> Finite<C, E>,
+) -> Unpacked<C, E> {
+    let attempts = [5, 20, limits.precision + 20];
+
+    for (attempt, guard) in attempts.iter().enumerate() {
+        let working = working_limits(limits.precision + guard + 3, limits);
+
+        let mut working_flags = Flags::default();
+        let approximation = approximate(&working, &mut working_flags);
+
+        // If the approximation is exact, or if it can't be wrong by enough to round differently
+        // than the exact result, then it can be rounded into the result
+        if !working_flags.contains(Flags::INEXACT)
+            || attempt == attempts.len() - 1
+            || is_roundable(&approximation, *guard, limits)
+        {
+            let result = round_finite(approximation, limits, mode, flags);
+
+            // The approximation may happen to have no more digits than the result,
+            // but the exact result still couldn't be represented
+            if working_flags.contains(Flags::INEXACT) {
+                flags.set(Flags::INEXACT | Flags::ROUNDED);
+            }
+
+            return result;
+        }
+    }
+
+    unreachable!("the last attempt is always rounded")
+}
+
+/**
+Whether an approximation can be rounded to the given limits without knowing its exact value.
+
+The approximation has `guard` extra digits after the precision, where the last few digits may be
+wrong. If the correct digits are close to the halfway point, or close to a representable number,
+then a small error in the approximation could change the way it rounds.
+*/
+fn is_roundable<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    approximation: &Finite<C, E>,
+    guard: usize,
+    limits: &Limits<E>,
+) -> bool {
+    let len = approximation.coefficient.len() as i64;
+    let adjusted = adjusted_exponent(approximation);
+
+    // Subnormal results are rounded to fewer digits than the precision
+    let kept = if adjusted < limits.emin {
+        match (limits.emin.clone() - adjusted).to_i32() {
+            Some(shortfall) => limits.precision as i64 - shortfall as i64,
+            None => return true,
+        }
+    } else {
+        limits.precision as i64
+    };
+
+    // If the approximation is below a tenth of the smallest subnormal then it can't be near
+    // a rounding boundary
+    if kept < 0 {
+        return true;
+    }
+
+    // The trailing digits of the guard may be wrong, so only look at the leading ones
+    let digit = |i: i64| {
+        let i = len - 1 - kept - i;
+
+        if i >= 0 && i < len {
+            approximation.coefficient.digit(i as usize)
+        } else {
+            0
+        }
+    };
+
+    let checked = (guard - 2) as i64;
+    let all = |from: i64, value: u8| (from..checked).all(|i| digit(i) == value);
+
+    let near_representable = all(0, 0) || all(0, 9);
+    let near_halfway = (digit(0) == 5 && all(1, 0)) || (digit(0) == 4 && all(1, 9));
+
+    !near_representable && !near_halfway
+}
+
+/**
+Approximate `exp(x)` for a non-zero finite number.
+*/
+fn exp_approx<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    x: &Finite<C, E>,
+    working: &Limits<E>,
+) -> Finite<C, E> {
+    let mut flags = Flags::default();
+
+    // Divide `x` by `10^k` so it's less than `0.01`
+    // Each time the result is raised to the power of ten it loses a digit of precision
+    let k = adjusted_exponent(x)
+        .to_i32()
+        .map(|adjusted| (adjusted + 3).max(0) as usize)
+        .expect("the argument to exp is in range");
+
+    let working = with_precision(working, working.precision + k + 3);
+
+    let t = Finite {
+        is_negative: x.is_negative,
+        coefficient: x.coefficient.clone(),
+        exponent: x.exponent.lower(k),
+    };
+
+    // Sum the Taylor series `1 + t + t^2/2! + t^3/3! + ...`
+    let cutoff = E::from_i32(-(working.precision as i32) - 2);
+
+    let mut sum = int(1);
+    let mut term = int(1);
+
+    for n in 1.. {
+        term = div_working(
+            mul_working(term, t.clone(), &working, &mut flags),
+            int(n),
+            &working,
+            &mut flags,
+        );
+
+        if term.coefficient.is_zero() || adjusted_exponent(&term) < cutoff {
+            break;
+        }
+
+        sum = add_working(sum, term.clone(), &working, &mut flags);
+    }
+
+    // Raise the sum back up to the power of `10^k`
+    for _ in 0..k {
+        let squared = mul_working(sum.clone(), sum, &working, &mut flags);
+        let fourth = mul_working(squared.clone(), squared.clone(), &working, &mut flags);
+        let eighth = mul_working(fourth.clone(), fourth, &working, &mut flags);
+
+        sum = mul_working(eighth, squared, &working, &mut flags);
+    }
+
+    sum
+}
+
+/**
+Approximate `ln(x)` for a positive, non-zero, finite number.
+*/
+fn ln_approx<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    x: &Finite<C, E>,
+    working: &Limits<E>,
+) -> Finite<C, E> {
+    let mut flags = Flags::default();
+
+    let working = with_precision(working, working.precision + 5);
+    let adjusted = adjusted_exponent(x);
+
+    // Numbers close to `1` are used as-is so their logarithm doesn't lose precision
+    // by cancelling out with `a * ln(10)`
+    let leading = x.coefficient.digit(x.coefficient.len() - 1);
+    let is_near_one =
+        (adjusted == E::zero() && leading == 1) || (adjusted == E::from_i32(-1) && leading >= 5);
+
+    if is_near_one {
+        return ln_reduce(x.clone(), &working);
+    }
+
+    // Split `x` into `m * 10^a`, where `m` is between `1` and `10`,
+    // so that `ln(x) = ln(m) + a * ln(10)`
+    let a = integer_to_finite::<C, E>(&adjusted);
+
+    let m = Finite {
+        is_negative: false,
+        coefficient: x.coefficient.clone(),
+        exponent: E::from_i32(0).lower(x.coefficient.len() - 1),
+    };
+
+    let ln_10_limits = with_precision(&working, working.precision + a.coefficient.len());
+
+    add_working(
+        ln_reduce(m, &working),
+        mul_working(a, ln_10(&ln_10_limits), &ln_10_limits, &mut flags),
+        &working,
+        &mut flags,
+    )
+}
+
+/**
+Approximate `ln(10)`.
+*/
+fn ln_10<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(working: &Limits<E>) -> Finite<C, E> {
+    ln_reduce(int(10), &with_precision(working, working.precision + 5))
+}
+
+/**
+Approximate `ln(y)` for a number between `0.5` and `10`.
+*/
+fn ln_reduce<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut y: Finite<C, E>,
+    working: &Limits<E>,
+) -> Finite<C, E> {
+    let mut flags = Flags::default();
+
+    let one = int(1);
+    let cutoff = E::from_i32(-2);
+
+    // Take square roots until `y` is within `0.01` of `1`
+    // Each square root halves the logarithm
+    let mut roots = 0;
+    loop {
+        let distance = add_working(y.clone(), negate(one.clone()), working, &mut flags);
+
+        if distance.coefficient.is_zero() {
+            return int(0);
+        }
+
+        if adjusted_exponent(&distance) < cutoff {
+            break;
+        }
+
+        y = round_working(sqrt_finite(y, working.precision), working, &mut flags);
+        roots += 1;
+    }
+
+    // Sum the series `z + z^3/3 + z^5/5 + ...` for `z = (y - 1) / (y + 1)`
+    let z = div_working(
+        add_working(y.clone(), negate(one.clone()), working, &mut flags),
+        add_working(y, one, working, &mut flags),
+        working,
+        &mut flags,
+    );
+
+    let z_squared = mul_working(z.clone(), z.clone(), working, &mut flags);
+    let cutoff = adjusted_exponent(&z).lower(working.precision + 2);
+
+    let mut sum = z.clone();
+    let mut power = z;
+
+    for n in (3..).step_by(2) {
+        power = mul_working(power, z_squared.clone(), working, &mut flags);
+
+        let term = div_working(power.clone(), int(n), working, &mut flags);
+
+        if term.coefficient.is_zero() || adjusted_exponent(&term) < cutoff {
+            break;
+        }
+
+        sum = add_working(sum, term, working, &mut flags);
+    }
+
+    // `ln(y) = 2 * atanh(z)`, and each square root halved it
+    mul_working(sum, int(2u128 << roots), working, &mut flags)
+}
+
+/**
+Raise a positive finite number to a positive integer power by repeated squaring.
+*/
+fn pow_integer<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: &Finite<C, E>,
+    mut n: u64,
+    working: &Limits<E>,
+    flags: &mut Flags,
+) -> Finite<C, E> {
+    let mut result = int(1);
+    let mut square = a.clone();
+
+    loop {
+        if n & 1 == 1 {
+            result = mul_working(result, square.clone(), working, flags);
+        }
+
+        n >>= 1;
+
+        if n == 0 {
+            return result;
+        }
+
+        square = mul_working(square.clone(), square, working, flags);
+    }
+}
+
+/**
+Check whether `exp(x)` is definitely too large or too small to represent.
+
+This method returns `Some(true)` if the result overflows, and `Some(false)` if it underflows to zero.
+If the result may be in range then it returns `None`.
+*/
+fn exp_out_of_range<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    x: &Finite<C, E>,
+    limits: &Limits<E>,
+) -> Option<bool> {
+    // The result is out of range if `|x| > (emax + 1) * ln(10)`, or `|x| > (1 - etiny) * ln(10)`
+    // This checks against a bound that's safely beyond both
+    let bound =
+        (limits.emax.clone().abs() + limits.etiny().abs() + E::from_i32(2)) * E::from_i32(3);
+    let bound = bound.to_i32().unwrap_or(i32::MAX);
+
+    let difference = add_finite(
+        Finite {
+            is_negative: false,
+            ..x.clone()
+        },
+        negate(int(bound as u128)),
+        &working_limits(1, limits),
+        RoundingMode::HalfEven,
+    );
+
+    if !difference.is_negative && !difference.coefficient.is_zero() {
+        Some(!x.is_negative)
+    } else {
+        None
+    }
+}
+
+/**
+Get a result that's too large or too small to represent.
+*/
+fn out_of_range<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    is_negative: bool,
+    is_overflow: bool,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    let exponent = if is_overflow {
+        limits.emax.raise(1)
+    } else {
+        limits.etiny().lower(2)
+    };
+
+    round_finite(
+        Finite {
+            is_negative,
+            coefficient: C::from_u128(1),
+            exponent,
+        },
+        limits,
+        mode,
+        flags,
+    )
+}
+
+/**
+Get limits for intermediate results with the given precision.
+
+Intermediate results have a much wider exponent range than the result so they never overflow.
+*/
+fn working_limits<E: BinaryExponent + BinaryExponentMath>(
+    precision: usize,
+    limits: &Limits<E>,
+) -> Limits<E> {
+    let range = (limits.emax.clone().abs() + limits.etiny().abs() + E::from_i32(precision as i32))
+        * E::from_i32(8);
+
+    Limits {
+        precision,
+        emax: range.clone(),
+        emin: E::zero() - range,
+    }
+}
+
+/**
+Get limits with a different precision.
+*/
+fn with_precision<E: BinaryExponent + BinaryExponentMath>(
+    limits: &Limits<E>,
+    precision: usize,
+) -> Limits<E> {
+    Limits {
+        precision,
+        ..limits.clone()
+    }
+}
+
+/**
+Round an intermediate result to its working precision.
+*/
+fn round_working<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    finite: Finite<C, E>,
+    working: &Limits<E>,
+    flags: &mut Flags,
+) -> Finite<C, E> {
+    match round_finite(finite, working, RoundingMode::HalfEven, flags) {
+        Unpacked::Finite(finite) => finite,
+        _ => unreachable!("intermediate results never overflow"),
+    }
+}
+
+fn add_working<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    working: &Limits<E>,
+    flags: &mut Flags,
+) -> Finite<C, E> {
+    round_working(
+        add_finite(a, b, working, RoundingMode::HalfEven),
+        working,
+        flags,
+    )
+}
+
+fn mul_working<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    working: &Limits<E>,
+    flags: &mut Flags,
+) -> Finite<C, E> {
+    round_working(
+        Finite {
+            is_negative: a.is_negative != b.is_negative,
+            coefficient: a.coefficient.mul(&b.coefficient),
+            exponent: a.exponent + b.exponent,
+        },
+        working,
+        flags,
+    )
+}
+
+fn div_working<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Finite<C, E>,
+    b: Finite<C, E>,
+    working: &Limits<E>,
+    flags: &mut Flags,
+) -> Finite<C, E> {
+    round_working(div_finite(a, b, working), working, flags)
+}
+
+/**
+A small positive integer.
+*/
+fn int<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(n: u128) -> Finite<C, E> {
+    Finite {
+        is_negative: false,
+        coefficient: C::from_u128(n),
+        exponent: E::zero(),
+    }
+}
+
+fn negate<C, E>(finite: Finite<C, E>) -> Finite<C, E> {
+    Finite {
+        is_negative: !finite.is_negative,
+        ..finite
+    }
+}
+
+fn negate_if<C: DigitBuf, E>(mut unpacked: Unpacked<C, E>, is_negative: bool) -> Unpacked<C, E> {
+    if is_negative {
+        unpacked.negate();
+    }
+
+    unpacked
+}
+
+/**
+The number of digits in an integer.
+*/
+fn digits(n: u128) -> usize {
+    n.checked_ilog10().unwrap_or(0) as usize + 1
+}
+
+/**
+Whether a finite number is `10^n` for some integer `n`.
+*/
+fn is_power_of_ten<C: DigitBuf, E>(finite: &Finite<C, E>) -> bool {
+    let digits = finite.coefficient.digits();
+
+    match digits.iter().rposition(|digit| *digit != 0) {
+        Some(msd) => digits[msd] == 1 && digits[..msd].iter().all(|digit| *digit == 0),
+        None => false,
+    }
+}
+
+/**
+Whether a finite number is exactly `1`, like `1` or `1.000`.
+*/
+fn is_one<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(finite: &Finite<C, E>) -> bool {
+    !finite.is_negative && is_power_of_ten(finite) && adjusted_exponent(finite) == E::zero()
+}
+
+/**
+An integral finite number.
+*/
+#[derive(Debug, Clone, Copy)]
+struct Integral {
+    /**
+    The magnitude of the number, if it's small enough to raise a number to its power by
+    repeated squaring.
+    */
+    value: Option<u64>,
+    is_odd: bool,
+}
+
+/**
+Check whether a finite number is an integer.
+*/
+fn integer<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    finite: &Finite<C, E>,
+) -> Option<Integral> {
+    let digits = finite.coefficient.digits();
+
+    // Find the position of the units digit, which may be beyond the coefficient
+    let units = match (E::zero() - finite.exponent.clone()).to_i32() {
+        Some(units) if units <= 0 => {
+            // Positive exponents multiply the coefficient by a power of ten
+            let is_odd = units == 0 && digits.first().copied().unwrap_or(0) % 2 == 1;
+
+            let value = if finite.coefficient.len() + (-units) as usize <= 9 {
+                let mut value = 0u64;
+
+                for digit in digits.iter().rev() {
+                    value = value * 10 + *digit as u64;
+                }
+
+                Some(value * 10u64.pow((-units) as u32))
+            } else {
+                None
+            };
+
+            return Some(Integral { value, is_odd });
+        }
+        Some(units) => units as usize,
+        // Negative exponents that are too large to fit are only integers if they're zero
+        None if finite.exponent < E::zero() => {
+            return finite.coefficient.is_zero().then_some(Integral {
+                value: Some(0),
+                is_odd: false,
+            });
+        }
+        None => {
+            return Some(Integral {
+                value: None,
+                is_odd: false,
+            })
+        }
+    };
+
+    if digits.iter().take(units).any(|digit| *digit != 0) {
+        return None;
+    }
+
+    let integral = digits.get(units..).unwrap_or(&[]);
+
+    let value = if integral.len() <= 9 {
+        let mut value = 0u64;
+
+        for digit in integral.iter().rev() {
+            value = value * 10 + *digit as u64;
+        }
+
+        Some(value)
+    } else {
+        None
+    };
+
+    Some(Integral {
+        value,
+        is_odd: integral.first().copied().unwrap_or(0) % 2 == 1,
+    })
+}
+
+/**
+Convert a binary integer into a finite number.
+*/
+fn integer_to_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(n: &E) -> Finite<C, E> {
+    struct Digits<C>(C);
+
+    impl<C: DigitBuf> fmt::Write for Digits<C> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for digit in s.bytes().filter(u8::is_ascii_digit) {
+                let len = self.0.len();
+
+                self.0.resize(len + 1);
+                self.0.digits_mut()[len] = digit - b'0';
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut digits = Digits(C::default());
+    n.to_fmt(&mut digits)
+        .expect("formatting an integer doesn't fail");
+
+    let mut coefficient = digits.0;
+    coefficient.digits_mut().reverse();
+    coefficient.trim();
+
+    Finite {
+        is_negative: n.is_negative(),
+        coefficient,
+        exponent: E::zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+    };
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::ArrayDigitBuf,
+    };
+
+    type Digits = ArrayDigitBuf<320>;
+
+    fn check<const N: usize>(
+        f: fn(
+            &FixedBinaryBuf<N, i32>,
+            &Limits<i32>,
+            RoundingMode,
+            &mut Flags,
+        ) -> FixedBinaryBuf<N, i32>,
+        a: &str,
+        expected: &str,
+        expected_flags: Flags,
+    ) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = f(&parse::<N>(a), &limits, RoundingMode::HalfEven, &mut flags);
+
+        assert_eq!(expected, to_string(&result), "f({})", a);
+        assert_eq!(expected_flags, flags, "f({})", a);
+    }
+
+    fn check_pow<const N: usize>(a: &str, b: &str, expected: &str, expected_flags: Flags) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = decimal_pow::<_, Digits>(
+            &parse::<N>(a),
+            &parse::<N>(b),
+            &limits,
+            RoundingMode::HalfEven,
+            &mut flags,
+        );
+
+        assert_eq!(expected, to_string(&result), "pow({}, {})", a, b);
+        assert_eq!(expected_flags, flags, "pow({}, {})", a, b);
+    }
+
+    #[test]
+    fn exp_values() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check::<8>(decimal_exp::<_, Digits>, "1", "2.718281828459045", rounded);
+        check::<8>(
+            decimal_exp::<_, Digits>,
+            "-1",
+            "0.3678794411714423",
+            rounded,
+        );
+        check::<8>(decimal_exp::<_, Digits>, "0", "1", Flags::default());
+        check::<8>(decimal_exp::<_, Digits>, "-0e5", "1", Flags::default());
+        check::<8>(
+            decimal_exp::<_, Digits>,
+            "1e-20",
+            "1.000000000000000",
+            rounded,
+        );
+        check::<8>(decimal_exp::<_, Digits>, "inf", "inf", Flags::default());
+        check::<8>(decimal_exp::<_, Digits>, "-inf", "0", Flags::default());
+        check::<8>(
+            decimal_exp::<_, Digits>,
+            "nan(1)",
+            "nan(1)",
+            Flags::default(),
+        );
+        check::<8>(
+            decimal_exp::<_, Digits>,
+            "snan(1)",
+            "nan(1)",
+            Flags::INVALID,
+        );
+
+        check::<16>(
+            decimal_exp::<_, Digits>,
+            "1",
+            "2.718281828459045235360287471352662",
+            rounded,
+        );
+    }
+
+    #[test]
+    fn exp_out_of_range() {
+        let overflow = Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED;
+        let underflow = Flags::UNDERFLOW | Flags::INEXACT | Flags::ROUNDED;
+
+        check::<8>(decimal_exp::<_, Digits>, "1000", "inf", overflow);
+        check::<8>(decimal_exp::<_, Digits>, "1e100", "inf", overflow);
+        check::<8>(
+            decimal_exp::<_, Digits>,
+            "-1e100",
+            "0e-398",
+            underflow | Flags::CLAMPED,
+        );
+    }
+
+    #[test]
+    fn ln_values() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check::<8>(decimal_ln::<_, Digits>, "10", "2.302585092994046", rounded);
+        check::<8>(decimal_ln::<_, Digits>, "2", "0.6931471805599453", rounded);
+        check::<8>(
+            decimal_ln::<_, Digits>,
+            "0.5",
+            "-0.6931471805599453",
+            rounded,
+        );
+        check::<8>(decimal_ln::<_, Digits>, "1.000", "0", Flags::default());
+        check::<8>(
+            decimal_ln::<_, Digits>,
+            "1.0000001",
+            "9.999999500000033e-8",
+            rounded,
+        );
+        check::<8>(
+            decimal_ln::<_, Digits>,
+            "1e-398",
+            "-916.4288670116302",
+            rounded,
+        );
+        check::<8>(decimal_ln::<_, Digits>, "0", "-inf", Flags::default());
+        check::<8>(decimal_ln::<_, Digits>, "-1", "nan", Flags::INVALID);
+        check::<8>(decimal_ln::<_, Digits>, "-inf", "nan", Flags::INVALID);
+        check::<8>(decimal_ln::<_, Digits>, "inf", "inf", Flags::default());
+    }
+
+    #[test]
+    fn log10_values() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check::<8>(decimal_log10::<_, Digits>, "1000", "3", Flags::default());
+        check::<8>(decimal_log10::<_, Digits>, "0.001", "-3", Flags::default());
+        check::<8>(decimal_log10::<_, Digits>, "1", "0", Flags::default());
+        check::<8>(
+            decimal_log10::<_, Digits>,
+            "2",
+            "0.3010299956639812",
+            rounded,
+        );
+        check::<8>(decimal_log10::<_, Digits>, "0", "-inf", Flags::default());
+        check::<8>(decimal_log10::<_, Digits>, "-2", "nan", Flags::INVALID);
+    }
+
+    #[test]
+    fn pow_values() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check_pow::<8>("2", "10", "1024", Flags::default());
+        check_pow::<8>("2", "-2", "0.25", Flags::default());
+        check_pow::<8>("-2", "3", "-8", Flags::default());
+        check_pow::<8>("1.0", "2", "1.00", Flags::default());
+        check_pow::<8>("2", "0.5", "1.414213562373095", rounded);
+        check_pow::<8>("4", "0.5", "2.000000000000000", rounded);
+        check_pow::<8>("-4", "0.5", "nan", Flags::INVALID);
+        check_pow::<8>("0", "0", "nan", Flags::INVALID);
+        check_pow::<8>("5", "0", "1", Flags::default());
+        check_pow::<8>("0", "-1", "inf", Flags::default());
+        check_pow::<8>("-0", "-1", "-inf", Flags::default());
+        check_pow::<8>("-0", "2", "0", Flags::default());
+        check_pow::<8>("-inf", "3", "-inf", Flags::default());
+        check_pow::<8>("-inf", "-3", "-0", Flags::default());
+        check_pow::<8>("1", "inf", "1.000000000000000", rounded);
+        check_pow::<8>("0.5", "inf", "0", Flags::default());
+        check_pow::<8>("2", "inf", "inf", Flags::default());
+        check_pow::<8>("2", "-inf", "0", Flags::default());
+        check_pow::<8>("10", "1000", "inf", Flags::OVERFLOW | rounded);
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn matches_decnumber() {
+        let mut rng = crate::math::tests::Rng(0x2545f4914f6cdd1d);
+        let mut ctx = dec::Context::<dec::Decimal<34>>::default();
+        ctx.set_precision(16).unwrap();
+        ctx.set_max_exponent(384).unwrap();
+        ctx.set_min_exponent(-383).unwrap();
+        ctx.set_clamp(true);
+        ctx.set_rounding(dec::Rounding::HalfEven);
+
+        // decNumber isn't always correctly rounded, so results are calculated with more digits
+        // and then rounded
+        let mut wide = dec::Context::<dec::Decimal<34>>::default();
+        wide.set_precision(40).unwrap();
+        wide.set_max_exponent(10_000).unwrap();
+        wide.set_min_exponent(-10_000).unwrap();
+        wide.set_rounding(dec::Rounding::HalfEven);
+
+        let limits = Limits::from_storage_width_bits(64);
+
+        let to_dec = |d: &FixedBinaryBuf<8, i32>| {
+            dec::Decimal::<34>::from(dec::Decimal64::from_ne_bytes(d.bytes().try_into().unwrap()))
+        };
+
+        for i in 0..1_200 {
+            let exponent = if rng.next(8) == 0 {
+                rng.next(2 * 369) as i32 - 369
+            } else {
+                rng.next(24) as i32 - 18
+            };
+
+            let a = rng.decimal(16, exponent);
+            if a.contains("nan") {
+                continue;
+            }
+
+            let da = parse::<8>(&a);
+            let mut expected = to_dec(&da);
+
+            let result = match i % 3 {
+                0 => {
+                    wide.exp(&mut expected);
+                    decimal_exp::<_, Digits>(
+                        &da,
+                        &limits,
+                        RoundingMode::HalfEven,
+                        &mut Flags::default(),
+                    )
+                }
+                1 => {
+                    wide.ln(&mut expected);
+                    decimal_ln::<_, Digits>(
+                        &da,
+                        &limits,
+                        RoundingMode::HalfEven,
+                        &mut Flags::default(),
+                    )
+                }
+                _ => {
+                    wide.log10(&mut expected);
+                    decimal_log10::<_, Digits>(
+                        &da,
+                        &limits,
+                        RoundingMode::HalfEven,
+                        &mut Flags::default(),
+                    )
+                }
+            };
+
+            ctx.plus(&mut expected);
+
+            // decNumber doesn't always use the smallest exponent for results that underflow to zero
+            if expected.is_zero() {
+                assert!(to_string(&result).starts_with('0'), "f{}({})", i % 3, a);
+                continue;
+            }
+
+            assert_eq!(
+                expected.to_decimal64().to_ne_bytes(),
+                result.bytes(),
+                "f{}({}) = {}, expected {}",
+                i % 3,
+                a,
+                to_string(&result),
+                expected.to_decimal64(),
+            );
+        }
+
+        for _ in 0..500 {
+            // The coefficient is small enough that its powers are exact in the wide context
+            let exponent = rng.next(8) as i32 - 4;
+            let a = rng.decimal(2, exponent);
+            let n = rng.next(41) as i32 - 20;
+            let b = n.to_string();
+
+            if a.contains("nan") {
+                continue;
+            }
+
+            let (da, db) = (parse::<8>(&a), parse::<8>(&b));
+
+            // decNumber isn't always exact for negative powers, so take the reciprocal instead
+            let mut expected = to_dec(&da);
+            if n < 0 && expected.is_finite() && !expected.is_zero() {
+                wide.pow(&mut expected, &dec::Decimal::<34>::from(-n));
+
+                let mut reciprocal = dec::Decimal::<34>::from(1);
+                wide.div(&mut reciprocal, &expected);
+
+                expected = reciprocal;
+            } else {
+                wide.pow(&mut expected, &to_dec(&db));
+            }
+
+            // Adding zero to a negative zero would make it positive
+            if !expected.is_zero() {
+                ctx.plus(&mut expected);
+            }
+
+            let result = decimal_pow::<_, Digits>(
+                &da,
+                &db,
+                &limits,
+                RoundingMode::HalfEven,
+                &mut Flags::default(),
+            );
+
+            assert_eq!(
+                expected.to_decimal64().to_ne_bytes(),
+                result.bytes(),
+                "pow({}, {}) = {}, expected {}",
+                a,
+                b,
+                to_string(&result),
+                expected.to_decimal64(),
+            );
+        }
+    }
+}
@@ -0,0 +1,194 @@
+/*!
+Hash binary-encoded decimals consistently with their numeric value.
+
+Numerically equal decimals can have different encodings, so their bytes can't be hashed directly.
+Instead, we hash a normalized form of the number: its sign, the exponent of its most significant
+digit, and its significant digits with leading and trailing zeros removed. This is the same
+information the comparison functions in `math::cmp` use to decide whether two numbers are equal.
+*/
+
+use core::{
+    fmt::{
+        self,
+        Write,
+    },
+    hash::{
+        Hash,
+        Hasher,
+    },
+    iter,
+};
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        decode_significand_trailing_declets,
+        is_infinite,
+        is_nan,
+        is_sign_negative,
+        BinaryBuf,
+        BinaryExponent,
+    },
+    num::Integer,
+};
+
+/**
+Hash a decimal by its numeric value.
+
+Decimals that are equal according to `decimal_eq` will produce the same hash, regardless of their
+width or exponent.
+*/
+pub(crate) fn decimal_hash<D: BinaryBuf, H: Hasher>(decimal: &D, state: &mut H) {
+    // NaNs are never equal, so they can all produce the same hash
+    if is_nan(decimal) {
+        state.write_u8(3);
+        return;
+    }
+
+    if is_infinite(decimal) {
+        state.write_u8(2);
+        is_sign_negative(decimal).hash(state);
+        return;
+    }
+
+    let (exp, msd) = decode_combination_finite(decimal);
+
+    let digits = || {
+        iter::once(msd.get_ascii())
+            .chain(decode_significand_trailing_declets(decimal).flatten())
+            .skip_while(|d| *d == b'0')
+    };
+
+    let significant_digits = digits().count();
+
+    // Zeros are equal regardless of their sign or exponent
+    if significant_digits == 0 {
+        state.write_u8(0);
+        return;
+    }
+
+    state.write_u8(1);
+    is_sign_negative(decimal).hash(state);
+
+    // The exponent is hashed through its text representation
+    // This keeps hashes consistent between decimals with different exponent types
+    let adjusted_exponent = exp.raise(significant_digits - 1);
+    let _ = adjusted_exponent.to_fmt(HashWriter(state));
+    state.write_u8(b';');
+
+    // Trailing zeros don't change the value of the number, so they're only
+    // hashed when they're followed by some other non-zero digit
+    let mut trailing_zeros = 0;
+    for digit in digits() {
+        if digit == b'0' {
+            trailing_zeros += 1;
+        } else {
+            for _ in 0..trailing_zeros {
+                state.write_u8(b'0');
+            }
+            trailing_zeros = 0;
+
+            state.write_u8(digit);
+        }
+    }
+}
+
+/**
+Hash a decimal by its encoding.
+
+Decimals that are numerically equal, like `1.0` and `1.00`, will usually produce different hashes.
+*/
+pub(crate) fn decimal_hash_repr<D: BinaryBuf, H: Hasher>(decimal: &D, state: &mut H) {
+    decimal.bytes().hash(state);
+}
+
+struct HashWriter<'a, H>(&'a mut H);
+
+impl<'a, H: Hasher> Write for HashWriter<'a, H> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write(s.as_bytes());
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+
+    use crate::math::tests::parse;
+
+    use crate::math::decimal_eq;
+
+    fn hash<D: BinaryBuf>(decimal: &D) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        decimal_hash(decimal, &mut hasher);
+        hasher.finish()
+    }
+
+    fn hash_repr<D: BinaryBuf>(decimal: &D) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        decimal_hash_repr(decimal, &mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hash_eq() {
+        for (a, b) in [
+            ("1", "1"),
+            ("1", "1.0"),
+            ("1.0", "1.00"),
+            ("10e-1", "1"),
+            ("1.2e3", "1200"),
+            ("1.200e3", "12e2"),
+            ("-123.4560", "-123.456"),
+            ("0", "-0"),
+            ("0", "0.000"),
+            ("0e10", "-0e-10"),
+            ("inf", "inf"),
+            ("-inf", "-inf"),
+        ] {
+            let (da, db) = (parse::<16>(a), parse::<16>(b));
+
+            assert!(decimal_eq(&da, &db), "{} == {}", a, b);
+            assert_eq!(hash(&da), hash(&db), "{} == {}", a, b);
+
+            let (da, db) = (parse::<8>(a), parse::<16>(b));
+
+            assert_eq!(hash(&da), hash(&db), "{} == {}", a, b);
+        }
+    }
+
+    #[test]
+    fn hash_ne() {
+        for (a, b) in [
+            ("1", "2"),
+            ("1", "-1"),
+            ("1", "10"),
+            ("1", "0.1"),
+            ("1", "11"),
+            ("1.01", "1.1"),
+            ("101", "11"),
+            ("0", "1e-10"),
+            ("inf", "-inf"),
+            ("inf", "nan"),
+            ("0", "nan"),
+        ] {
+            let (da, db) = (parse::<16>(a), parse::<16>(b));
+
+            assert_ne!(hash(&da), hash(&db), "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn hash_repr_ne() {
+        for (a, b) in [("1", "1.0"), ("0", "-0"), ("1.2e3", "1200")] {
+            let (da, db) = (parse::<16>(a), parse::<16>(b));
+
+            assert_eq!(hash(&da), hash(&db), "{} == {}", a, b);
+            assert_ne!(hash_repr(&da), hash_repr(&db), "{} != {}", a, b);
+        }
+    }
+}
@@ -0,0 +1,357 @@
+/*!
+Multiplication and fused multiply-add of decimals.
+
+The coefficient of a product is the product of the coefficients of its operands, and its exponent
+is the sum of their exponents. The product is calculated exactly, and then rounded to fit
+its format.
+
+A fused multiply-add calculates `a * b + c` with a single rounding step at the end. The exact
+product is added to `c` without being rounded first.
+*/
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        add_finite,
+        pack,
+        propagate_nan,
+        round_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+};
+
+/**
+Multiply two decimals.
+*/
+pub(crate) fn decimal_mul<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(mul(unpack::<D, C>(a), unpack(b), limits, mode, flags))
+}
+
+/**
+Multiply two decimals and add a third, with a single rounding step.
+*/
+pub(crate) fn decimal_fma<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    b: &D,
+    c: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(fma(
+        unpack::<D, C>(a),
+        unpack(b),
+        unpack(c),
+        limits,
+        mode,
+        flags,
+    ))
+}
+
+/**
+Multiply two unpacked decimals.
+*/
+pub(crate) fn mul<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a, &b], limits, flags) {
+        return nan;
+    }
+
+    match mul_exact(a, b, flags) {
+        Unpacked::Finite(product) => round_finite(product, limits, mode, flags),
+        product => product,
+    }
+}
+
+/**
+Multiply two unpacked decimals and add a third, with a single rounding step.
+*/
+pub(crate) fn fma<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    c: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a, &b, &c], limits, flags) {
+        return nan;
+    }
+
+    match (mul_exact(a, b, flags), c) {
+        // NaN + ±123
+        (nan @ Unpacked::Nan(_), _) => nan,
+        // ±inf + ±inf
+        (
+            Unpacked::Infinity {
+                is_negative: product_is_negative,
+            },
+            Unpacked::Infinity {
+                is_negative: c_is_negative,
+            },
+        ) => {
+            if product_is_negative == c_is_negative {
+                Unpacked::Infinity {
+                    is_negative: product_is_negative,
+                }
+            } else {
+                Unpacked::invalid(flags)
+            }
+        }
+        // ±inf + ±123
+        (inf @ Unpacked::Infinity { .. }, _) | (_, inf @ Unpacked::Infinity { .. }) => inf,
+        // ±123 + ±123
+        (Unpacked::Finite(product), Unpacked::Finite(c)) => {
+            round_finite(add_finite(product, c, limits, mode), limits, mode, flags)
+        }
+        _ => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Multiply two unpacked decimals that aren't NaNs, without rounding the result.
+*/
+fn mul_exact<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    b: Unpacked<C, E>,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    match (a, b) {
+        // ±123 * ±123
+        (Unpacked::Finite(a), Unpacked::Finite(b)) => Unpacked::Finite(Finite {
+            is_negative: a.is_negative != b.is_negative,
+            coefficient: a.coefficient.mul(&b.coefficient),
+            exponent: a.exponent + b.exponent,
+        }),
+        // ±inf * ±0
+        (Unpacked::Infinity { .. }, Unpacked::Finite(zero))
+        | (Unpacked::Finite(zero), Unpacked::Infinity { .. })
+            if zero.coefficient.is_zero() =>
+        {
+            Unpacked::invalid(flags)
+        }
+        // ±inf * ±123
+        (Unpacked::Infinity { is_negative: a }, Unpacked::Infinity { is_negative: b })
+        | (
+            Unpacked::Infinity { is_negative: a },
+            Unpacked::Finite(Finite { is_negative: b, .. }),
+        )
+        | (
+            Unpacked::Finite(Finite { is_negative: a, .. }),
+            Unpacked::Infinity { is_negative: b },
+        ) => Unpacked::Infinity {
+            is_negative: a != b,
+        },
+        _ => unreachable!("NaNs have already been handled"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+    };
+
+    use crate::{
+        binary::FixedBinaryBuf,
+        math::ArrayDigitBuf,
+    };
+
+    fn check_mul<const N: usize>(a: &str, b: &str, expected: &str, expected_flags: Flags) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = decimal_mul::<_, ArrayDigitBuf<144>>(
+            &parse::<N>(a),
+            &parse::<N>(b),
+            &limits,
+            RoundingMode::HalfEven,
+            &mut flags,
+        );
+
+        assert_eq!(expected, to_string(&result), "{} * {}", a, b);
+        assert_eq!(expected_flags, flags, "{} * {}", a, b);
+    }
+
+    fn check_fma<const N: usize>(a: &str, b: &str, c: &str, expected: &str, expected_flags: Flags) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = decimal_fma::<_, ArrayDigitBuf<144>>(
+            &parse::<N>(a),
+            &parse::<N>(b),
+            &parse::<N>(c),
+            &limits,
+            RoundingMode::HalfEven,
+            &mut flags,
+        );
+
+        assert_eq!(expected, to_string(&result), "{} * {} + {}", a, b, c);
+        assert_eq!(expected_flags, flags, "{} * {} + {}", a, b, c);
+    }
+
+    #[test]
+    fn mul_exact() {
+        for (a, b, expected) in [
+            ("2", "3", "6"),
+            ("1.20", "3", "3.60"),
+            ("7", "3", "21"),
+            ("0.9", "0.8", "0.72"),
+            ("0.9", "-0", "-0.0"),
+            ("-0", "-0", "0"),
+            ("654321", "654321", "428135971041"),
+            ("1e10", "1e10", "1e20"),
+        ] {
+            check_mul::<8>(a, b, expected, Flags::default());
+        }
+    }
+
+    #[test]
+    fn mul_rounded() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check_mul::<4>("1234567", "2", "2469134", Flags::default());
+        check_mul::<4>("1234567", "3", "3703701", Flags::default());
+        check_mul::<4>("1234567", "9", "1.111110e7", rounded);
+        check_mul::<4>("9999999", "9999999", "9.999998e13", rounded);
+        check_mul::<4>("1e90", "1e10", "inf", Flags::OVERFLOW | rounded);
+        check_mul::<4>(
+            "1e-60",
+            "1e-60",
+            "0e-101",
+            Flags::UNDERFLOW | Flags::CLAMPED | rounded,
+        );
+    }
+
+    #[test]
+    fn mul_special() {
+        for (a, b, expected, expected_flags) in [
+            ("inf", "2", "inf", Flags::default()),
+            ("inf", "-2", "-inf", Flags::default()),
+            ("-inf", "-inf", "inf", Flags::default()),
+            ("inf", "0", "nan", Flags::INVALID),
+            ("-0e5", "inf", "nan", Flags::INVALID),
+            ("nan(7)", "inf", "nan(7)", Flags::default()),
+            ("1", "snan(7)", "nan(7)", Flags::INVALID),
+        ] {
+            check_mul::<8>(a, b, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn fma_single_rounding() {
+        // `a * b` rounds to `1e16`, so rounding twice would give `0`
+        check_fma::<8>(
+            "99999999.5",
+            "100000000.5",
+            "-1e16",
+            "-0.25",
+            Flags::default(),
+        );
+
+        check_fma::<8>("3", "5", "7", "22", Flags::default());
+        check_fma::<8>("1.5", "2", "0.25", "3.25", Flags::default());
+        check_fma::<8>("2", "-3", "6", "0", Flags::default());
+        check_fma::<8>("1e300", "1e300", "-inf", "-inf", Flags::default());
+    }
+
+    #[test]
+    fn fma_special() {
+        check_fma::<8>("inf", "2", "-inf", "nan", Flags::INVALID);
+        check_fma::<8>("inf", "0", "1", "nan", Flags::INVALID);
+        check_fma::<8>("inf", "0", "nan(3)", "nan(3)", Flags::default());
+        check_fma::<8>("1", "2", "snan(3)", "nan(3)", Flags::INVALID);
+        check_fma::<8>("1", "2", "inf", "inf", Flags::default());
+    }
+
+    macro_rules! matches_decnumber {
+        ($name:ident, $n:literal, $dec:ty, $precision:literal, $emax:literal) => {
+            #[test]
+            #[cfg(target_endian = "little")]
+            fn $name() {
+                let mut rng = crate::math::tests::Rng(0x9e3779b97f4a7c15);
+                let mut ctx = dec::Context::<$dec>::default();
+                let limits = Limits::from_storage_width_bits($n * 8);
+
+                for _ in 0..10_000 {
+                    let mut exponent = || {
+                        if rng.next(4) == 0 {
+                            rng.next(2 * $emax) as i32 - $emax as i32
+                        } else {
+                            rng.next(40) as i32 - 20
+                        }
+                    };
+                    let (ea, eb, ec) = (exponent(), exponent(), exponent());
+
+                    let a = rng.decimal($precision, ea);
+                    let b = rng.decimal($precision, eb);
+                    let c = rng.decimal($precision, ec);
+
+                    let (da, db, dc) = (parse::<$n>(&a), parse::<$n>(&b), parse::<$n>(&c));
+                    let dec = |d: &FixedBinaryBuf<$n, i32>| {
+                        <$dec>::from_ne_bytes(d.bytes().try_into().unwrap())
+                    };
+
+                    let product = decimal_mul::<_, ArrayDigitBuf<144>>(
+                        &da,
+                        &db,
+                        &limits,
+                        RoundingMode::HalfEven,
+                        &mut Flags::default(),
+                    );
+                    let fma = decimal_fma::<_, ArrayDigitBuf<144>>(
+                        &da,
+                        &db,
+                        &dc,
+                        &limits,
+                        RoundingMode::HalfEven,
+                        &mut Flags::default(),
+                    );
+
+                    assert_eq!(
+                        ctx.mul(dec(&da), dec(&db)).to_ne_bytes(),
+                        product.bytes(),
+                        "{} * {}",
+                        a,
+                        b
+                    );
+                    assert_eq!(
+                        ctx.fma(dec(&da), dec(&db), dec(&dc)).to_ne_bytes(),
+                        fma.bytes(),
+                        "{} * {} + {}",
+                        a,
+                        b,
+                        c
+                    );
+                }
+            }
+        };
+    }
+
+    matches_decnumber!(matches_decnumber_decimal64, 8, dec::Decimal64, 16, 369);
+    matches_decnumber!(matches_decnumber_decimal128, 16, dec::Decimal128, 34, 6000);
+}
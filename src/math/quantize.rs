@@ -0,0 +1,363 @@
+/*!
+Changing the exponent of decimals.
+
+Decimals are non-normalized, so the same number can be encoded with different exponents, like
+`1.0` and `1.00`. Quantizing a decimal changes its exponent, either padding its coefficient with
+zeros or rounding away digits. If the coefficient would need more digits than the precision then
+the operation is invalid.
+*/
+
+use crate::{
+    binary::{
+        decode_combination_finite,
+        is_infinite,
+        is_nan,
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        adjusted_exponent,
+        cmp_integer,
+        distance,
+        pack,
+        propagate_nan,
+        round_digits,
+        round_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+    num::Integer,
+};
+
+/**
+Change the exponent of a decimal to match the exponent of another.
+*/
+pub(crate) fn decimal_quantize<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    like: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    let a = unpack::<D, C>(a);
+    let like = unpack::<D, C>(like);
+
+    if let Some(nan) = propagate_nan(&[&a, &like], limits, flags) {
+        return pack(nan);
+    }
+
+    pack(match (a, like) {
+        // ±inf quantize ±inf
+        (a @ Unpacked::Infinity { .. }, Unpacked::Infinity { .. }) => a,
+        // ±123 quantize ±123
+        (Unpacked::Finite(a), Unpacked::Finite(like)) => {
+            quantize(a, like.exponent, limits, mode, flags)
+        }
+        // ±inf quantize ±123, ±123 quantize ±inf
+        _ => Unpacked::invalid(flags),
+    })
+}
+
+/**
+Change the exponent of a decimal to the given value.
+*/
+pub(crate) fn decimal_rescale<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    exponent: i32,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    let a = unpack::<D, C>(a);
+
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return pack(nan);
+    }
+
+    pack(match a {
+        Unpacked::Finite(a) => quantize(a, D::Exponent::from_i32(exponent), limits, mode, flags),
+        _ => Unpacked::invalid(flags),
+    })
+}
+
+/**
+Round a decimal to an integer, using the given rounding mode.
+
+Decimals that are already integers, including those with positive exponents, are unchanged.
+*/
+pub(crate) fn decimal_round_to_integral<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    let a = unpack::<D, C>(a);
+
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return pack(nan);
+    }
+
+    pack(match a {
+        Unpacked::Finite(a) if a.exponent < D::Exponent::zero() => {
+            quantize(a, D::Exponent::zero(), limits, mode, flags)
+        }
+        a => a,
+    })
+}
+
+/**
+Whether two decimals have the same exponent.
+
+Infinities have the same quantum as each other, and so do NaNs.
+*/
+pub(crate) fn decimal_same_quantum<DA: BinaryBuf, DB: BinaryBuf>(a: &DA, b: &DB) -> bool {
+    match (is_nan(a), is_nan(b)) {
+        (true, true) => return true,
+        (false, false) => (),
+        _ => return false,
+    }
+
+    match (is_infinite(a), is_infinite(b)) {
+        (true, true) => return true,
+        (false, false) => (),
+        _ => return false,
+    }
+
+    let (a, _) = decode_combination_finite(a);
+    let (b, _) = decode_combination_finite(b);
+
+    cmp_integer(&a, &b).is_eq()
+}
+
+/**
+Change the exponent of a finite number.
+*/
+fn quantize<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut finite: Finite<C, E>,
+    exponent: E,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    // The exponent must be one that can be encoded
+    if exponent < limits.etiny() || exponent > limits.etop() {
+        return Unpacked::invalid(flags);
+    }
+
+    finite.coefficient.trim();
+
+    if finite.coefficient.is_zero() {
+        finite.exponent = exponent;
+
+        return Unpacked::Finite(finite);
+    }
+
+    if exponent > finite.exponent {
+        // If the exponent is increasing then digits are rounded away
+        // If all of the digits are rounded away then the number is only affected by rounding
+        let discard = if adjusted_exponent(&finite) < exponent.lower(1) {
+            finite.coefficient.len() + 1
+        } else {
+            distance(&finite.exponent, &exponent)
+        };
+
+        let is_inexact = round_digits(&mut finite, discard, mode);
+        finite.exponent = exponent;
+
+        flags.set(Flags::ROUNDED);
+
+        if is_inexact {
+            flags.set(Flags::INEXACT);
+
+            if !finite.coefficient.is_zero() && adjusted_exponent(&finite) < limits.emin {
+                flags.set(Flags::UNDERFLOW);
+            }
+        }
+    } else if exponent < finite.exponent {
+        // If the exponent is decreasing then the coefficient is padded with zeros
+        // If the coefficient doesn't fit then the operation is invalid
+        if adjusted_exponent(&finite) > exponent.raise(limits.precision - 1) {
+            return Unpacked::invalid(flags);
+        }
+
+        let pad = distance(&exponent, &finite.exponent);
+
+        finite.coefficient.shift_left(pad);
+        finite.exponent = exponent;
+    }
+
+    round_finite(finite, limits, mode, flags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+    };
+
+    use crate::math::ArrayDigitBuf;
+
+    fn check(a: &str, like: &str, mode: RoundingMode, expected: &str, expected_flags: Flags) {
+        let limits = Limits::from_storage_width_bits(32);
+        let mut flags = Flags::default();
+
+        let result = decimal_quantize::<_, ArrayDigitBuf<32>>(
+            &parse::<4>(a),
+            &parse::<4>(like),
+            &limits,
+            mode,
+            &mut flags,
+        );
+
+        assert_eq!(expected, to_string(&result), "quantize({}, {})", a, like);
+        assert_eq!(expected_flags, flags, "quantize({}, {})", a, like);
+    }
+
+    #[test]
+    fn quantize_exact() {
+        let exact = Flags::default();
+
+        check("1", "0.01", RoundingMode::HalfEven, "1.00", exact);
+        check(
+            "1.2300",
+            "0.01",
+            RoundingMode::HalfEven,
+            "1.23",
+            Flags::ROUNDED,
+        );
+        check("0", "1e5", RoundingMode::HalfEven, "0e5", exact);
+        check("-0.0", "1e-101", RoundingMode::HalfEven, "-0e-101", exact);
+        check(
+            "123",
+            "1e1",
+            RoundingMode::HalfEven,
+            "1.2e2",
+            Flags::ROUNDED | Flags::INEXACT,
+        );
+        check("1", "1e-6", RoundingMode::HalfEven, "1.000000", exact);
+    }
+
+    #[test]
+    fn quantize_rounded() {
+        let rounded = Flags::ROUNDED | Flags::INEXACT;
+
+        check("2.345", "0.01", RoundingMode::HalfEven, "2.34", rounded);
+        check("2.345", "0.01", RoundingMode::HalfUp, "2.35", rounded);
+        check("-2.341", "0.01", RoundingMode::Floor, "-2.35", rounded);
+        check("0.004", "0.01", RoundingMode::HalfEven, "0.00", rounded);
+        check("0.004", "0.01", RoundingMode::Up, "0.01", rounded);
+        check("1e-50", "1e10", RoundingMode::Ceiling, "1e10", rounded);
+        check("9.99", "0.1", RoundingMode::HalfEven, "10.0", rounded);
+    }
+
+    #[test]
+    fn quantize_invalid() {
+        let invalid = Flags::INVALID;
+
+        check("1", "1e-7", RoundingMode::HalfEven, "nan", invalid);
+        check("9999999", "0.1", RoundingMode::HalfEven, "nan", invalid);
+        check("inf", "1", RoundingMode::HalfEven, "nan", invalid);
+        check("1", "-inf", RoundingMode::HalfEven, "nan", invalid);
+        check(
+            "-inf",
+            "inf",
+            RoundingMode::HalfEven,
+            "-inf",
+            Flags::default(),
+        );
+        check(
+            "nan(3)",
+            "1",
+            RoundingMode::HalfEven,
+            "nan(3)",
+            Flags::default(),
+        );
+        check("1", "snan(4)", RoundingMode::HalfEven, "nan(4)", invalid);
+    }
+
+    #[test]
+    fn rescale_limits() {
+        let limits = Limits::from_storage_width_bits(32);
+
+        let rescale = |a: &str, exponent: i32, flags: &mut Flags| {
+            to_string(&decimal_rescale::<_, ArrayDigitBuf<32>>(
+                &parse::<4>(a),
+                exponent,
+                &limits,
+                RoundingMode::HalfEven,
+                flags,
+            ))
+        };
+
+        assert_eq!("0e90", rescale("0", 90, &mut Flags::default()));
+        assert_eq!("nan", rescale("0", 91, &mut Flags::default()));
+        assert_eq!("0e-100", rescale("1e-101", -100, &mut Flags::default()));
+        assert_eq!("nan", rescale("1", -102, &mut Flags::default()));
+
+        let mut flags = Flags::default();
+        assert_eq!("1e-100", rescale("6e-101", -100, &mut flags));
+        assert_eq!(Flags::ROUNDED | Flags::INEXACT | Flags::UNDERFLOW, flags);
+    }
+
+    #[test]
+    fn round_to_integral() {
+        let limits = Limits::from_storage_width_bits(32);
+
+        let round = |a: &str, mode: RoundingMode| {
+            to_string(&decimal_round_to_integral::<_, ArrayDigitBuf<32>>(
+                &parse::<4>(a),
+                &limits,
+                mode,
+                &mut Flags::default(),
+            ))
+        };
+
+        assert_eq!("2", round("17e-1", RoundingMode::HalfEven));
+        assert_eq!("1", round("17e-1", RoundingMode::Down));
+        assert_eq!("2", round("2.5", RoundingMode::HalfEven));
+        assert_eq!("3", round("2.5", RoundingMode::HalfUp));
+        assert_eq!("-3", round("-2.1", RoundingMode::Floor));
+        assert_eq!("-2", round("-2.1", RoundingMode::Ceiling));
+        assert_eq!("-0", round("-0.4", RoundingMode::HalfEven));
+        assert_eq!("0", round("0.000", RoundingMode::HalfEven));
+        assert_eq!("1", round("0.000001", RoundingMode::Up));
+        assert_eq!("1.2e3", round("1.2e3", RoundingMode::Down));
+        assert_eq!("1234567", round("1234567", RoundingMode::Down));
+        assert_eq!("-inf", round("-inf", RoundingMode::Down));
+        assert_eq!("nan(1)", round("snan(1)", RoundingMode::Down));
+    }
+
+    #[test]
+    fn same_quantum() {
+        assert!(decimal_same_quantum(
+            &parse::<4>("1.00"),
+            &parse::<8>("-9.99")
+        ));
+        assert!(decimal_same_quantum(
+            &parse::<4>("inf"),
+            &parse::<4>("-inf")
+        ));
+        assert!(decimal_same_quantum(
+            &parse::<4>("nan"),
+            &parse::<16>("snan")
+        ));
+        assert!(!decimal_same_quantum(
+            &parse::<4>("1.0"),
+            &parse::<4>("1.00")
+        ));
+        assert!(!decimal_same_quantum(
+            &parse::<4>("inf"),
+            &parse::<4>("nan")
+        ));
+        assert!(!decimal_same_quantum(&parse::<4>("1"), &parse::<4>("inf")));
+    }
+}
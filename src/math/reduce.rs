@@ -0,0 +1,137 @@
+/*!
+Removing trailing zeros from decimals.
+
+Reducing a decimal gives numbers that are numerically equal the same encoding, so `1.200e3`
+and `1200` both reduce to `12e2`. The exponent is never raised above the largest one the format can
+encode, so some large numbers keep a few trailing zeros.
+*/
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        pack,
+        propagate_nan,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        Unpacked,
+    },
+    num::Integer,
+};
+
+/**
+Remove trailing zeros from the coefficient of a decimal, raising its exponent.
+
+If `max_exponent` is given then the exponent isn't raised above it.
+*/
+pub(crate) fn decimal_reduce<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    max_exponent: Option<i32>,
+    limits: &Limits<D::Exponent>,
+    flags: &mut Flags,
+) -> D {
+    let a = unpack::<D, C>(a);
+
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return pack(nan);
+    }
+
+    let max_exponent = match max_exponent {
+        Some(max_exponent) => limits.etop().min(D::Exponent::from_i32(max_exponent)),
+        None => limits.etop(),
+    };
+
+    pack(match a {
+        Unpacked::Finite(finite) => Unpacked::Finite(reduce(finite, max_exponent)),
+        a => a,
+    })
+}
+
+/**
+Remove trailing zeros from the coefficient of a finite number.
+*/
+fn reduce<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut finite: Finite<C, E>,
+    max_exponent: E,
+) -> Finite<C, E> {
+    finite.coefficient.trim();
+
+    // Zeros are reduced to a zero exponent
+    if finite.coefficient.is_zero() {
+        if finite.exponent < max_exponent {
+            finite.exponent = E::zero().min(max_exponent);
+        }
+
+        return finite;
+    }
+
+    while finite.exponent < max_exponent && finite.coefficient.digit(0) == 0 {
+        finite.coefficient.shift_right(1);
+        finite.exponent = finite.exponent.raise(1);
+    }
+
+    finite
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::{
+        tests::{
+            parse,
+            to_string,
+        },
+        ArrayDigitBuf,
+    };
+
+    fn check(a: &str, max_exponent: Option<i32>, expected: &str) {
+        let limits = Limits::from_storage_width_bits(32);
+
+        let a = parse::<4>(a);
+
+        let result = decimal_reduce::<_, ArrayDigitBuf<32>>(
+            &a,
+            max_exponent,
+            &limits,
+            &mut Flags::default(),
+        );
+
+        assert_eq!(expected, to_string(&result), "reduce({:?})", max_exponent);
+    }
+
+    #[test]
+    fn reduce_finite() {
+        check("1.200e3", None, "1.2e3");
+        check("1200", None, "1.2e3");
+        check("1.200", None, "1.2");
+        check("-100", None, "-1e2");
+        check("0.000", None, "0");
+        check("-0e5", None, "-0");
+        check("1000000e84", None, "1e90");
+        check("1000000e85", None, "1.0e91");
+    }
+
+    #[test]
+    fn reduce_max_exponent() {
+        check("1.2000", Some(-2), "1.20");
+        check("1.2", Some(-2), "1.2");
+        check("1200", Some(-2), "1200");
+        check("1200", Some(1), "1.20e3");
+        check("0e5", Some(-2), "0e5");
+        check("0.0", Some(-2), "0.0");
+    }
+
+    #[test]
+    fn reduce_special() {
+        check("inf", None, "inf");
+        check("-nan(12)", None, "-nan(12)");
+        check("snan(12)", None, "nan(12)");
+    }
+}
@@ -0,0 +1,559 @@
+/*!
+Rounding the results of arithmetic to fit a decimal format.
+
+Arithmetic is first done exactly on unbounded coefficients, and then the result is rounded to fit
+the precision and exponent range of the format it'll be encoded into. This follows IEEE754-2019
+and the General Decimal Arithmetic specification:
+
+- If the coefficient has more digits than the precision then it's rounded.
+- If the exponent is below the smallest exponent for subnormal numbers then the coefficient
+  is rounded so that it fits. This may round the result to zero.
+- If the number is too large to encode then it overflows to an infinity, or to the largest finite
+  number, depending on the rounding mode.
+- If the exponent is above the largest that can be encoded, but the number is still in range, then
+  the coefficient is padded with zeros until the exponent fits.
+*/
+
+use crate::{
+    binary::{
+        emax,
+        emin,
+        precision_digits,
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        pack,
+        propagate_nan,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        RoundingMode,
+        Unpacked,
+    },
+    num::Integer,
+};
+
+/**
+The precision and exponent range of results.
+*/
+#[derive(Debug, Clone)]
+pub(crate) struct Limits<E> {
+    /**
+    The maximum number of digits in the coefficient.
+    */
+    pub(crate) precision: usize,
+    /**
+    The maximum adjusted exponent.
+    */
+    pub(crate) emax: E,
+    /**
+    The minimum adjusted exponent of normal numbers.
+    */
+    pub(crate) emin: E,
+}
+
+impl<E: BinaryExponent + BinaryExponentMath> Limits<E> {
+    /**
+    Get the limits of a decimal with the given bit-width.
+    */
+    pub(crate) fn from_storage_width_bits(storage_width_bits: usize) -> Self {
+        Limits {
+            precision: precision_digits(storage_width_bits),
+            emax: emax(storage_width_bits),
+            emin: emin(storage_width_bits),
+        }
+    }
+
+    /**
+    The smallest exponent a coefficient can have.
+    */
+    pub(crate) fn etiny(&self) -> E {
+        self.emin.lower(self.precision - 1)
+    }
+
+    /**
+    The largest exponent a coefficient can have.
+    */
+    pub(crate) fn etop(&self) -> E {
+        self.emax.lower(self.precision - 1)
+    }
+}
+
+/**
+Round a finite number to fit the given limits.
+
+The result may be an infinity if the number overflows.
+*/
+pub(crate) fn round_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut finite: Finite<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    finite.coefficient.trim();
+
+    let etiny = limits.etiny();
+    let etop = limits.etop();
+
+    // Zeros don't need rounding, but their exponents may need to be clamped
+    if finite.coefficient.is_zero() {
+        if finite.exponent < etiny {
+            finite.exponent = etiny;
+            flags.set(Flags::CLAMPED);
+        } else if finite.exponent > etop {
+            finite.exponent = etop;
+            flags.set(Flags::CLAMPED);
+        }
+
+        return Unpacked::Finite(finite);
+    }
+
+    // Work out how many digits need to be discarded
+    // This is either because there are too many of them, or because the exponent
+    // is too small and the number needs to be rounded to a subnormal
+    let len = finite.coefficient.len();
+    let mut discard = len.saturating_sub(limits.precision);
+
+    let mut is_subnormal_exponent = false;
+    if finite.exponent.raise(discard) < etiny {
+        is_subnormal_exponent = true;
+
+        // If the exponent is far below the minimum then all digits will be discarded
+        discard = (etiny.clone() - finite.exponent.clone())
+            .to_i32()
+            .map(|discard| discard as usize)
+            .filter(|discard| *discard <= len)
+            .unwrap_or(len + 1);
+    }
+
+    let was_rounded = discard > 0;
+    let is_inexact = round_digits(&mut finite, discard, mode);
+
+    if is_subnormal_exponent {
+        finite.exponent = etiny;
+
+        // If the number underflowed all the way to zero then its exponent has been clamped
+        if is_inexact && finite.coefficient.is_zero() {
+            flags.set(Flags::CLAMPED);
+        }
+    }
+
+    // Rounding may have carried into a new digit, like `999` to `1000`
+    // The new least significant digit is always zero, so it can be discarded exactly
+    if finite.coefficient.len() > limits.precision {
+        finite.coefficient.shift_right(1);
+        finite.exponent = finite.exponent.raise(1);
+    }
+
+    if was_rounded {
+        flags.set(Flags::ROUNDED);
+    }
+
+    if is_inexact {
+        flags.set(Flags::INEXACT);
+    }
+
+    // Check whether the number is too large to encode
+    let len = finite.coefficient.len();
+    if len > 0 && finite.exponent.raise(len - 1) > limits.emax {
+        flags.set(Flags::OVERFLOW | Flags::INEXACT | Flags::ROUNDED);
+
+        return overflow(finite.is_negative, limits, mode);
+    }
+
+    // Check whether the number is too small to encode exactly
+    if is_inexact && (len == 0 || finite.exponent.raise(len - 1) < limits.emin) {
+        flags.set(Flags::UNDERFLOW);
+    }
+
+    // If the exponent is too large then pad the coefficient with zeros
+    if finite.exponent > etop {
+        let pad = (finite.exponent.clone() - etop.clone())
+            .to_i32()
+            .expect("the exponent is within the precision of the decimal")
+            as usize;
+
+        finite.coefficient.shift_left(pad);
+        finite.exponent = etop;
+
+        flags.set(Flags::CLAMPED);
+    }
+
+    Unpacked::Finite(finite)
+}
+
+/**
+Round a decimal to fit a narrower format.
+
+This is used to convert numbers that have first been encoded in a wide intermediate decimal,
+like integers and binary floating points, into decimals that may not be able to represent them.
+The wide decimal may also be a `BigBitstring`, whose exponent might not fit in an `i32`.
+*/
+pub(crate) fn decimal_narrow<S: BinaryBuf, D: BinaryBuf, C: DigitBuf>(
+    decimal: &S,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    let unpacked = match unpack::<S, C>(decimal) {
+        Unpacked::Finite(Finite {
+            is_negative,
+            coefficient,
+            exponent,
+        }) => {
+            // The exponent of the intermediate decimal may be outside the range of the target
+            // format, or even an `i32`. Large exponents are clamped to half the range of an
+            // `i32`, which is still far outside the range of any format, so they still underflow
+            // or overflow in the same way without overflowing while they're rounded
+            const MAX_EXPONENT: i32 = i32::MAX / 2;
+
+            let exponent = D::Exponent::from_i32(match exponent.to_i32() {
+                Some(exponent) => exponent.clamp(-MAX_EXPONENT, MAX_EXPONENT),
+                None if exponent.is_negative() => -MAX_EXPONENT,
+                None => MAX_EXPONENT,
+            });
+
+            round_finite(
+                Finite {
+                    is_negative,
+                    coefficient,
+                    exponent,
+                },
+                limits,
+                mode,
+                flags,
+            )
+        }
+        Unpacked::Infinity { is_negative } => Unpacked::Infinity { is_negative },
+        Unpacked::Nan(nan) => {
+            let nan = Unpacked::Nan(nan);
+
+            propagate_nan(&[&nan], limits, flags).expect("the decimal is a NaN")
+        }
+    };
+
+    pack(unpacked)
+}
+
+/**
+Convert a decimal into a narrower format without rounding.
+
+This returns `None` if the decimal can't be encoded exactly within the given limits, because its
+coefficient or NaN payload has too many digits, or because its exponent is out of range.
+*/
+#[cfg(any(feature = "bson", test))]
+pub(crate) fn decimal_narrow_exact<S: BinaryBuf, D: BinaryBuf, C: DigitBuf>(
+    decimal: &S,
+    limits: &Limits<D::Exponent>,
+) -> Option<D> {
+    let unpacked = match unpack::<S, C>(decimal) {
+        Unpacked::Finite(Finite {
+            is_negative,
+            mut coefficient,
+            exponent,
+        }) => {
+            coefficient.trim();
+
+            let exponent = D::Exponent::from_i32(exponent.to_i32()?);
+
+            if coefficient.len() > limits.precision
+                || exponent < limits.etiny()
+                || exponent > limits.etop()
+            {
+                return None;
+            }
+
+            Unpacked::Finite(Finite {
+                is_negative,
+                coefficient,
+                exponent,
+            })
+        }
+        Unpacked::Infinity { is_negative } => Unpacked::Infinity { is_negative },
+        Unpacked::Nan(mut nan) => {
+            nan.payload.trim();
+
+            // The payload is stored in the trailing digits, so it has one less digit to fit in
+            if nan.payload.len() >= limits.precision {
+                return None;
+            }
+
+            Unpacked::Nan(nan)
+        }
+    };
+
+    Some(pack(unpacked))
+}
+
+/**
+Get the result of overflowing in the given rounding mode.
+
+The result is either an infinity, or the largest finite number.
+*/
+pub(crate) fn overflow<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    is_negative: bool,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+) -> Unpacked<C, E> {
+    let is_infinite = match mode {
+        RoundingMode::HalfEven
+        | RoundingMode::HalfUp
+        | RoundingMode::HalfDown
+        | RoundingMode::Up => true,
+        RoundingMode::Down | RoundingMode::ZeroFiveUp => false,
+        RoundingMode::Ceiling => !is_negative,
+        RoundingMode::Floor => is_negative,
+    };
+
+    if is_infinite {
+        Unpacked::Infinity { is_negative }
+    } else {
+        Unpacked::Finite(Finite {
+            is_negative,
+            coefficient: C::nines(limits.precision),
+            exponent: limits.etop(),
+        })
+    }
+}
+
+/**
+Discard the `n` least significant digits of a finite number, rounding the remaining digits.
+
+This method returns `true` if any of the discarded digits were non-zero.
+*/
+pub(crate) fn round_digits<C: DigitBuf, E: BinaryExponent>(
+    finite: &mut Finite<C, E>,
+    n: usize,
+    mode: RoundingMode,
+) -> bool {
+    if n == 0 {
+        return false;
+    }
+
+    let coefficient = &mut finite.coefficient;
+
+    // The first discarded digit decides whether we're above or below the halfway point
+    // The rest of the discarded digits decide whether we're exactly on it
+    let first = coefficient.digit(n - 1);
+    let rest = coefficient
+        .digits()
+        .iter()
+        .take(n - 1)
+        .any(|digit| *digit != 0);
+
+    coefficient.shift_right(n);
+    finite.exponent = finite.exponent.raise(n);
+
+    if first == 0 && !rest {
+        return false;
+    }
+
+    let last = coefficient.digit(0);
+
+    let round_up = match mode {
+        RoundingMode::HalfEven => first > 5 || (first == 5 && (rest || last % 2 == 1)),
+        RoundingMode::HalfUp => first >= 5,
+        RoundingMode::HalfDown => first > 5 || (first == 5 && rest),
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => !finite.is_negative,
+        RoundingMode::Floor => finite.is_negative,
+        RoundingMode::ZeroFiveUp => last == 0 || last == 5,
+    };
+
+    if round_up {
+        coefficient.increment();
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::{
+        tests::to_string,
+        ArrayDigitBuf,
+    };
+
+    type Buf = ArrayDigitBuf<32>;
+
+    fn finite(is_negative: bool, coefficient: &str, exponent: i32) -> Finite<Buf, i32> {
+        Finite {
+            is_negative,
+            coefficient: Buf::from_ascii(coefficient.bytes()),
+            exponent,
+        }
+    }
+
+    fn ascii(finite: &Finite<Buf, i32>) -> String {
+        String::from_utf8(finite.coefficient.to_ascii().digits().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn round_digits_modes() {
+        use RoundingMode::*;
+
+        for (coefficient, is_negative, mode, expected) in [
+            ("125", false, HalfEven, "12"),
+            ("135", false, HalfEven, "14"),
+            ("1251", false, HalfEven, "13"),
+            ("124", false, HalfEven, "12"),
+            ("126", false, HalfEven, "13"),
+            ("125", false, HalfUp, "13"),
+            ("124", false, HalfUp, "12"),
+            ("125", false, HalfDown, "12"),
+            ("1251", false, HalfDown, "13"),
+            ("121", false, Up, "13"),
+            ("129", false, Down, "12"),
+            ("121", false, Ceiling, "13"),
+            ("121", true, Ceiling, "12"),
+            ("121", false, Floor, "12"),
+            ("121", true, Floor, "13"),
+            ("101", false, ZeroFiveUp, "11"),
+            ("151", false, ZeroFiveUp, "16"),
+            ("121", false, ZeroFiveUp, "12"),
+            ("199", false, HalfEven, "20"),
+            ("995", false, HalfEven, "100"),
+            ("120", false, Up, "12"),
+        ] {
+            // Round away all but the two most significant digits
+            let n = coefficient.len() - 2;
+            let mut f = finite(is_negative, coefficient, 0);
+
+            round_digits(&mut f, n, mode);
+
+            assert_eq!(expected, ascii(&f), "{} {:?}", coefficient, mode);
+            assert_eq!(n as i32, f.exponent);
+        }
+    }
+
+    #[test]
+    fn round_finite_precision() {
+        let limits = Limits::<i32>::from_storage_width_bits(32);
+
+        for (coefficient, exponent, expected, expected_exponent, expected_flags) in [
+            ("1234567", 0, "1234567", 0, Flags::default()),
+            ("12345675", 0, "1234568", 1, Flags::INEXACT | Flags::ROUNDED),
+            ("12345670", 0, "1234567", 1, Flags::ROUNDED),
+            ("99999995", 0, "1000000", 2, Flags::INEXACT | Flags::ROUNDED),
+            ("1", 96, "1000000", 90, Flags::CLAMPED),
+            ("0", 200, "0", 90, Flags::CLAMPED),
+            ("0", -200, "0", -101, Flags::CLAMPED),
+            (
+                "15",
+                -102,
+                "2",
+                -101,
+                Flags::INEXACT | Flags::ROUNDED | Flags::UNDERFLOW,
+            ),
+            (
+                "1",
+                -200,
+                "0",
+                -101,
+                Flags::INEXACT | Flags::ROUNDED | Flags::UNDERFLOW | Flags::CLAMPED,
+            ),
+            ("10", -102, "1", -101, Flags::ROUNDED),
+        ] {
+            let mut flags = Flags::default();
+
+            match round_finite(
+                finite(false, coefficient, exponent),
+                &limits,
+                RoundingMode::HalfEven,
+                &mut flags,
+            ) {
+                Unpacked::Finite(f) => {
+                    assert_eq!(expected, ascii(&f), "{}e{}", coefficient, exponent);
+                    assert_eq!(
+                        expected_exponent, f.exponent,
+                        "{}e{}",
+                        coefficient, exponent
+                    );
+                }
+                _ => panic!("expected a finite number"),
+            }
+
+            assert_eq!(expected_flags, flags, "{}e{}", coefficient, exponent);
+        }
+    }
+
+    #[test]
+    fn round_finite_overflow() {
+        let limits = Limits::<i32>::from_storage_width_bits(32);
+
+        for (is_negative, mode, is_infinite) in [
+            (false, RoundingMode::HalfEven, true),
+            (true, RoundingMode::HalfEven, true),
+            (false, RoundingMode::Down, false),
+            (false, RoundingMode::Ceiling, true),
+            (true, RoundingMode::Ceiling, false),
+            (false, RoundingMode::Floor, false),
+            (true, RoundingMode::Floor, true),
+        ] {
+            let mut flags = Flags::default();
+
+            let result = round_finite(finite(is_negative, "1", 97), &limits, mode, &mut flags);
+
+            assert!(flags.contains(Flags::OVERFLOW));
+
+            match result {
+                Unpacked::Infinity {
+                    is_negative: result_is_negative,
+                } => {
+                    assert!(is_infinite, "{:?}", mode);
+                    assert_eq!(is_negative, result_is_negative);
+                }
+                Unpacked::Finite(f) => {
+                    assert!(!is_infinite, "{:?}", mode);
+                    assert_eq!(is_negative, f.is_negative);
+                    assert_eq!("9999999", ascii(&f));
+                    assert_eq!(90, f.exponent);
+                }
+                _ => panic!("expected a finite number or infinity"),
+            }
+        }
+    }
+
+    #[test]
+    fn narrow_exact() {
+        use crate::binary::{
+            DynamicBinaryBuf,
+            FixedBinaryBuf,
+        };
+
+        let limits = Limits::<i32>::from_storage_width_bits(32);
+
+        for (s, expected) in [
+            ("1.20", Some("1.20")),
+            ("-0.000", Some("-0.000")),
+            ("0001234567", Some("1234567")),
+            ("12345678", None),
+            ("1e90", Some("1e90")),
+            ("1e91", None),
+            ("1e-101", Some("1e-101")),
+            ("10e-102", None),
+            ("-inf", Some("-inf")),
+            ("-snan(123456)", Some("-snan(123456)")),
+            ("nan(1234567)", None),
+        ] {
+            let wide: DynamicBinaryBuf<20> =
+                crate::convert::decimal_from_str(s).expect("failed to parse decimal");
+
+            let narrow: Option<FixedBinaryBuf<4, i32>> =
+                decimal_narrow_exact::<_, _, Buf>(&wide, &limits);
+
+            assert_eq!(
+                expected.map(String::from),
+                narrow.map(|narrow| to_string(&narrow)),
+                "{}",
+                s
+            );
+        }
+    }
+}
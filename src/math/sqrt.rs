@@ -0,0 +1,355 @@
+/*!
+Square roots of decimals.
+
+The square root is calculated exactly from an integer square root of the coefficient, which is
+scaled so the root has at least one more digit than the precision. Any remainder is kept as a
+sticky digit so the result can be rounded correctly in any rounding mode.
+
+Exact results use the exponent closest to half the exponent of the operand, rounded down, so the
+square root of `1.00` is `1.0`.
+*/
+
+use crate::{
+    binary::{
+        BinaryBuf,
+        BinaryExponent,
+        BinaryExponentMath,
+    },
+    math::{
+        pack,
+        propagate_nan,
+        round_finite,
+        unpack,
+        DigitBuf,
+        Finite,
+        Flags,
+        Limits,
+        RoundingMode,
+        Unpacked,
+    },
+};
+
+/**
+Get the square root of a decimal.
+*/
+pub(crate) fn decimal_sqrt<D: BinaryBuf, C: DigitBuf>(
+    a: &D,
+    limits: &Limits<D::Exponent>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> D {
+    pack(sqrt(unpack::<D, C>(a), limits, mode, flags))
+}
+
+/**
+Get the square root of an unpacked decimal.
+*/
+pub(crate) fn sqrt<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    a: Unpacked<C, E>,
+    limits: &Limits<E>,
+    mode: RoundingMode,
+    flags: &mut Flags,
+) -> Unpacked<C, E> {
+    if let Some(nan) = propagate_nan(&[&a], limits, flags) {
+        return nan;
+    }
+
+    match a {
+        // sqrt(+inf)
+        Unpacked::Infinity { is_negative: false } => a,
+        // sqrt(-inf)
+        Unpacked::Infinity { is_negative: true } => Unpacked::invalid(flags),
+        // sqrt(±0)
+        Unpacked::Finite(zero) if zero.coefficient.is_zero() => round_finite(
+            Finite {
+                is_negative: zero.is_negative,
+                coefficient: zero.coefficient,
+                exponent: half_floor(zero.exponent),
+            },
+            limits,
+            mode,
+            flags,
+        ),
+        // sqrt(-123)
+        Unpacked::Finite(Finite {
+            is_negative: true, ..
+        }) => Unpacked::invalid(flags),
+        // sqrt(123)
+        Unpacked::Finite(finite) => {
+            round_finite(sqrt_finite(finite, limits.precision), limits, mode, flags)
+        }
+        Unpacked::Nan(_) => unreachable!("NaNs have already been handled"),
+    }
+}
+
+/**
+Get the square root of a positive, non-zero, finite number.
+
+The result has enough digits to be rounded correctly to the given precision.
+*/
+pub(crate) fn sqrt_finite<C: DigitBuf, E: BinaryExponent + BinaryExponentMath>(
+    mut finite: Finite<C, E>,
+    precision: usize,
+) -> Finite<C, E> {
+    finite.coefficient.trim();
+
+    let ideal_exponent = half_floor(finite.exponent.clone());
+
+    // Scale the coefficient so its root has at least one more digit than the precision,
+    // and so its exponent is even
+    let mut scale = (2 * (precision + 1)).saturating_sub(finite.coefficient.len());
+    if is_odd(&finite.exponent.lower(scale)) {
+        scale += 1;
+    }
+
+    let mut square = finite.coefficient;
+    square.shift_left(scale);
+
+    let exponent = half_floor(finite.exponent.lower(scale));
+
+    let mut coefficient = isqrt(&square);
+    let mut exponent = exponent;
+
+    if coefficient.mul(&coefficient).cmp_magnitude(&square).is_eq() {
+        // If the root is exact then remove trailing zeros until the exponent is ideal
+        while exponent < ideal_exponent && coefficient.digit(0) == 0 {
+            coefficient.shift_right(1);
+            exponent = exponent.raise(1);
+        }
+    } else {
+        // If the root is inexact then add a sticky digit so it rounds correctly
+        coefficient.shift_left(1);
+        coefficient.digits_mut()[0] = 1;
+        exponent = exponent.lower(1);
+    }
+
+    Finite {
+        is_negative: false,
+        coefficient,
+        exponent,
+    }
+}
+
+/**
+Get the integer square root of a coefficient, rounded down.
+
+The root of the leading half of the digits is used as a starting point for Newton's method,
+so only a few iterations are needed at the full width of the coefficient.
+*/
+fn isqrt<C: DigitBuf>(n: &C) -> C {
+    let mut n = n.clone();
+    n.trim();
+
+    if n.len() <= 2 {
+        let root = (0..=9u8)
+            .rev()
+            .find(|root| {
+                C::from_u128((root * root) as u128).cmp_magnitude(&n)
+                    != core::cmp::Ordering::Greater
+            })
+            .unwrap_or(0);
+
+        return C::from_u128(root as u128);
+    }
+
+    // Get the root of the leading digits, then scale it back up
+    // Adding one before scaling makes sure we start above the real root
+    let k = (n.len() / 4).max(1);
+
+    let mut leading = n.clone();
+    leading.shift_right(2 * k);
+
+    let mut x = isqrt(&leading);
+    x.increment();
+    x.shift_left(k);
+
+    let two = C::from_u128(2);
+
+    // Newton's method converges to the root from above
+    loop {
+        let (quotient, _) = n.div_rem(&x);
+
+        let mut next = x.clone();
+        next.add_assign(&quotient);
+        let (next, _) = next.div_rem(&two);
+
+        if next.cmp_magnitude(&x) != core::cmp::Ordering::Less {
+            return x;
+        }
+
+        x = next;
+    }
+}
+
+/**
+Halve an exponent, rounding towards negative infinity.
+*/
+fn half_floor<E: BinaryExponent + BinaryExponentMath>(exponent: E) -> E {
+    let two = E::from_i32(2);
+
+    if exponent < E::zero() && is_odd(&exponent) {
+        (exponent - E::from_i32(1)) / two
+    } else {
+        exponent / two
+    }
+}
+
+/**
+Whether an exponent is odd.
+*/
+fn is_odd<E: BinaryExponent + BinaryExponentMath>(exponent: &E) -> bool {
+    let two = E::from_i32(2);
+
+    exponent.clone() - (exponent.clone() / two.clone()) * two != E::zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::math::tests::{
+        parse,
+        to_string,
+    };
+
+    use crate::math::ArrayDigitBuf;
+
+    fn check<const N: usize>(a: &str, expected: &str, expected_flags: Flags) {
+        let limits = Limits::from_storage_width_bits(N * 8);
+        let mut flags = Flags::default();
+
+        let result = decimal_sqrt::<_, ArrayDigitBuf<144>>(
+            &parse::<N>(a),
+            &limits,
+            RoundingMode::HalfEven,
+            &mut flags,
+        );
+
+        assert_eq!(expected, to_string(&result), "sqrt({})", a);
+        assert_eq!(expected_flags, flags, "sqrt({})", a);
+    }
+
+    #[test]
+    fn isqrt_digits() {
+        for n in [
+            0u128,
+            1,
+            2,
+            3,
+            4,
+            15,
+            16,
+            17,
+            99,
+            100,
+            101,
+            12345678987654321,
+            u64::MAX as u128,
+        ] {
+            let root = isqrt(&ArrayDigitBuf::<64>::from_u128(n));
+
+            let mut expected = (n as f64).sqrt() as u128;
+            while expected * expected > n {
+                expected -= 1;
+            }
+            while (expected + 1) * (expected + 1) <= n {
+                expected += 1;
+            }
+
+            assert_eq!(
+                ArrayDigitBuf::<64>::from_u128(expected).digits(),
+                root.digits(),
+                "isqrt({})",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn sqrt_exact() {
+        for (a, expected) in [
+            ("4", "2"),
+            ("0.25", "0.5"),
+            ("1.00", "1.0"),
+            ("100", "10"),
+            ("1e2", "1e1"),
+            ("1e3", "31.62278"),
+            ("0", "0"),
+            ("-0", "-0"),
+            ("0e3", "0e1"),
+            ("0e-3", "0.00"),
+            ("1522756", "1234"),
+            ("7", "2.645751"),
+        ] {
+            let expected_flags = if expected.len() > 6 {
+                Flags::INEXACT | Flags::ROUNDED
+            } else {
+                Flags::default()
+            };
+
+            check::<4>(a, expected, expected_flags);
+        }
+    }
+
+    #[test]
+    fn sqrt_special() {
+        check::<8>("inf", "inf", Flags::default());
+        check::<8>("-inf", "nan", Flags::INVALID);
+        check::<8>("-1", "nan", Flags::INVALID);
+        check::<8>("nan(1)", "nan(1)", Flags::default());
+        check::<8>("snan(1)", "nan(1)", Flags::INVALID);
+    }
+
+    #[test]
+    fn sqrt_extremes() {
+        let rounded = Flags::INEXACT | Flags::ROUNDED;
+
+        check::<8>("9.999999999999999e384", "3.162277660168379e192", rounded);
+        check::<8>("1e-398", "1e-199", Flags::default());
+        check::<8>("2e-398", "1.414213562373095e-199", rounded);
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn matches_decnumber() {
+        let mut rng = crate::math::tests::Rng(0x9e3779b97f4a7c15);
+        let mut ctx = dec::Context::<dec::Decimal<34>>::default();
+        ctx.set_precision(16).unwrap();
+        ctx.set_max_exponent(384).unwrap();
+        ctx.set_min_exponent(-383).unwrap();
+        ctx.set_clamp(true);
+        ctx.set_rounding(dec::Rounding::HalfEven);
+
+        let limits = Limits::from_storage_width_bits(64);
+
+        for _ in 0..10_000 {
+            let exponent = if rng.next(4) == 0 {
+                rng.next(2 * 369) as i32 - 369
+            } else {
+                rng.next(40) as i32 - 20
+            };
+
+            let a = rng.decimal(16, exponent);
+            let da = parse::<8>(&a);
+
+            let result = decimal_sqrt::<_, ArrayDigitBuf<144>>(
+                &da,
+                &limits,
+                RoundingMode::HalfEven,
+                &mut Flags::default(),
+            );
+
+            let mut expected = dec::Decimal::<34>::from(dec::Decimal64::from_ne_bytes(
+                da.bytes().try_into().unwrap(),
+            ));
+            ctx.sqrt(&mut expected);
+
+            assert_eq!(
+                expected.to_decimal64().to_ne_bytes(),
+                result.bytes(),
+                "sqrt({})",
+                a
+            );
+        }
+    }
+}
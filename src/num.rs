@@ -77,7 +77,7 @@ pub trait Integer {
 
 pub struct AsDisplay<T>(T);
 
-impl<'a, T: Integer> fmt::Display for AsDisplay<&'a T> {
+impl<T: Integer> fmt::Display for AsDisplay<&T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.0.to_fmt(f)
     }
@@ -216,11 +216,12 @@ macro_rules! impl_binary_unsigned_integer {
             impl Integer for $i {
                 type Bytes = $bytes;
 
-                fn try_from_ascii<I: Iterator<Item = u8>>(is_negative: bool, ascii: I) -> Option<Self> {
+                fn try_from_ascii<I: Iterator<Item = u8>>(is_negative: bool, mut ascii: I) -> Option<Self> {
                     let mut i: $i = 0;
 
+                    // Negative zero is still zero
                     if is_negative {
-                        return None;
+                        return ascii.all(|b| b == b'0').then_some(0);
                     } else {
                         for b in ascii {
                             i = i.checked_mul(10)?;
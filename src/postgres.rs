@@ -0,0 +1,103 @@
+/*!
+Conversions to and from Postgres' `NUMERIC` through `postgres-types`.
+
+This module is only available when the `postgres` feature is enabled.
+
+Decimals are sent and received in the binary format of `NUMERIC`. See the `to_pg_numeric_bytes`
+and `try_from_pg_numeric_bytes` methods for details on how values are converted.
+*/
+
+use crate::Bitstring;
+use bytes::BytesMut;
+use postgres_types::{
+    accepts,
+    to_sql_checked,
+    FromSql,
+    IsNull,
+    ToSql,
+    Type,
+};
+use std::error;
+
+#[cfg(feature = "arbitrary-precision")]
+use crate::BigBitstring;
+
+macro_rules! postgres {
+    ($d:ident) => {
+        impl ToSql for $d {
+            fn to_sql(
+                &self,
+                _: &Type,
+                out: &mut BytesMut,
+            ) -> Result<IsNull, Box<dyn error::Error + Sync + Send>> {
+                out.extend(self.to_pg_numeric_bytes()?);
+
+                Ok(IsNull::No)
+            }
+
+            accepts!(NUMERIC);
+
+            to_sql_checked!();
+        }
+
+        impl<'a> FromSql<'a> for $d {
+            fn from_sql(
+                _: &Type,
+                raw: &'a [u8],
+            ) -> Result<$d, Box<dyn error::Error + Sync + Send>> {
+                Ok($d::try_from_pg_numeric_bytes(raw)?)
+            }
+
+            accepts!(NUMERIC);
+        }
+    };
+}
+
+postgres!(Bitstring);
+
+#[cfg(feature = "arbitrary-precision")]
+postgres!(BigBitstring);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_from_sql() {
+        let d = Bitstring::try_parse_str("-12.50").expect("failed to parse decimal");
+
+        let mut buf = BytesMut::new();
+
+        assert!(matches!(
+            d.to_sql_checked(&Type::NUMERIC, &mut buf),
+            Ok(IsNull::No)
+        ));
+        assert_eq!(
+            &[0x00, 0x02, 0x00, 0x00, 0x40, 0x00, 0x00, 0x02, 0x00, 0x0c, 0x13, 0x88],
+            &buf[..]
+        );
+
+        let roundtrip = Bitstring::from_sql(&Type::NUMERIC, &buf).expect("failed to convert");
+        assert_eq!("-12.50", roundtrip.to_string());
+
+        assert!(d.to_sql_checked(&Type::TEXT, &mut buf).is_err());
+        assert!(!<Bitstring as FromSql>::accepts(&Type::FLOAT8));
+    }
+
+    #[test]
+    #[cfg(feature = "arbitrary-precision")]
+    fn to_from_sql_big() {
+        let d = BigBitstring::try_parse_str("12345678901234567890123456789012345678901234567890")
+            .expect("failed to parse decimal");
+
+        let mut buf = BytesMut::new();
+
+        assert!(matches!(
+            d.to_sql_checked(&Type::NUMERIC, &mut buf),
+            Ok(IsNull::No)
+        ));
+
+        let roundtrip = BigBitstring::from_sql(&Type::NUMERIC, &buf).expect("failed to convert");
+        assert_eq!(d.to_string(), roundtrip.to_string());
+    }
+}
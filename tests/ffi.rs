@@ -0,0 +1,59 @@
+/*!
+Tests for the C ABI, using a C shim compiled with GCC.
+
+The shim is only compiled on x86-64 Linux when GCC is available.
+*/
+
+#![cfg(all(feature = "ffi", decstr_ffi_shim))]
+
+use core::ffi::c_int;
+
+use decstr::{
+    ffi::{
+        decstr_d128_format,
+        Decimal128,
+        DECSTR_FORMAT_ERR,
+    },
+    Bitstring128,
+};
+
+extern "C" {
+    fn decstr_ffi_shim_check() -> c_int;
+
+    fn decstr_ffi_shim_sum(a: *const Decimal128, b: *const Decimal128, out: *mut Decimal128);
+}
+
+#[test]
+fn shim_check() {
+    let line = unsafe { decstr_ffi_shim_check() };
+
+    assert_eq!(
+        0, line,
+        "the check on line {} of `tests/ffi/shim.c` failed",
+        line
+    );
+}
+
+#[test]
+fn shim_sum() {
+    let a = Decimal128::from(Bitstring128::try_parse_str("0.1").unwrap());
+    let b = Decimal128::from(Bitstring128::try_parse_str("-1.25e-20").unwrap());
+    let mut out = Decimal128::from(Bitstring128::ZERO);
+
+    unsafe { decstr_ffi_shim_sum(&a, &b, &mut out) };
+
+    assert_eq!(
+        "0.0999999999999999999875",
+        Bitstring128::from(out).to_string()
+    );
+}
+
+#[test]
+fn format_null() {
+    let mut buf = [0x7f; 8];
+
+    assert_eq!(DECSTR_FORMAT_ERR, unsafe {
+        decstr_d128_format(core::ptr::null(), buf.as_mut_ptr(), buf.len())
+    });
+    assert_eq!([0x7f; 8], buf);
+}
@@ -0,0 +1,99 @@
+/*
+ * A small C program that calls into decstr using GCC's decimal types.
+ *
+ * This is compiled by `build.rs` and linked into `tests/ffi.rs`.
+ */
+
+#include <string.h>
+
+#include "decstr.h"
+
+#define CHECK(cond)          \
+    do {                     \
+        if (!(cond)) {       \
+            return __LINE__; \
+        }                    \
+    } while (0)
+
+#define CHECK_FORMAT(format, value, expected)                                        \
+    do {                                                                             \
+        char buf[64];                                                                \
+        CHECK(format(&(value), buf, sizeof buf) == strlen(expected));                \
+        CHECK(strcmp(buf, expected) == 0);                                           \
+    } while (0)
+
+/*
+ * Run all checks, returning `0` if they pass, or the line of the first check that failed.
+ */
+int decstr_ffi_shim_check(void) {
+    _Decimal32 d32;
+    _Decimal64 d64;
+    _Decimal128 d128;
+    int64_t i;
+    uint64_t u;
+    double f;
+
+    /* Values parsed by decstr are usable as GCC decimals */
+    CHECK(decstr_d64_parse("1.25", 4, &d64) == DECSTR_OK);
+    CHECK(d64 == 1.25DD);
+
+    d64 = d64 * 2;
+    CHECK_FORMAT(decstr_d64_format, d64, "2.50");
+
+    CHECK(decstr_d32_parse("-7.5", 4, &d32) == DECSTR_OK);
+    CHECK(d32 == -7.5DF);
+
+    CHECK(decstr_d128_parse("1e-6143", 7, &d128) == DECSTR_OK);
+    CHECK(d128 == 1E-6143DL);
+
+    CHECK(decstr_d32_parse("1.2.3", 5, &d32) == DECSTR_ERR);
+    CHECK(decstr_d32_format(NULL, NULL, 0) == DECSTR_FORMAT_ERR);
+
+    /* Values computed by GCC are formatted by decstr */
+    d32 = 9999999E90DF;
+    CHECK_FORMAT(decstr_d32_format, d32, "9.999999e96");
+
+    d64 = 0.1DD + 0.2DD;
+    CHECK_FORMAT(decstr_d64_format, d64, "0.3");
+
+    d128 = -123456789012345678901234567890.1234DL;
+    CHECK_FORMAT(decstr_d128_format, d128, "-123456789012345678901234567890.1234");
+
+    d64 = __builtin_infd64();
+    CHECK_FORMAT(decstr_d64_format, d64, "inf");
+
+    d64 = -__builtin_nand64("");
+    CHECK_FORMAT(decstr_d64_format, d64, "-nan");
+
+    /* Integers */
+    CHECK(decstr_d64_from_i64(-42, &d64) == DECSTR_OK);
+    CHECK(d64 == -42.DD);
+    CHECK(decstr_d64_to_i64(&d64, &i) == DECSTR_OK);
+    CHECK(i == -42);
+    CHECK(decstr_d64_to_u64(&d64, &u) == DECSTR_ERR);
+
+    CHECK(decstr_d32_from_u64(UINT64_MAX, &d32) == DECSTR_ERR);
+
+    CHECK(decstr_d128_from_u64(UINT64_MAX, &d128) == DECSTR_OK);
+    CHECK(d128 == 18446744073709551615.DL);
+
+    d128 = 1.5DL;
+    CHECK(decstr_d128_to_i64(&d128, &i) == DECSTR_ERR);
+
+    /* Doubles */
+    CHECK(decstr_d64_from_double(0.1, &d64) == DECSTR_OK);
+    CHECK(d64 == 0.1DD);
+
+    d32 = 0.5DF;
+    CHECK(decstr_d32_to_double(&d32, &f) == DECSTR_OK);
+    CHECK(f == 0.5);
+
+    return 0;
+}
+
+/*
+ * Compute a value using GCC's decimal arithmetic.
+ */
+void decstr_ffi_shim_sum(const _Decimal128 *a, const _Decimal128 *b, _Decimal128 *out) {
+    *out = *a + *b;
+}